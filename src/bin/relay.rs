@@ -0,0 +1,275 @@
+//! Relay server pairing two online players and forwarding their messages
+//!
+//! A client sends `JOIN <room>` and gets `SIDE 0` or `SIDE 1` back. Once the room
+//! has two players both receive `START` and answer `READY`, then every line is
+//! forwarded to the other player until one of them disconnects, which sends `LEFT`
+//! to the other one. A waiting player who disconnects frees the room.
+//!
+//! Usage: `cargo run --bin relay -- [address]`, listening on 127.0.0.1:7878 by default.
+
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+/// How often a waiting player is checked for a disconnect or a partner
+const WAIT_POLL: Duration = Duration::from_millis(100);
+/// How long a player has to answer `START`
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Player waiting for a partner in a room
+struct Waiting {
+    id: u64,
+    stream: TcpStream,
+    partners: Sender<Partner>,
+}
+
+/// Player joining a waiting one, told whether the waiting player is ready
+struct Partner {
+    stream: TcpStream,
+    ready: Sender<bool>,
+}
+
+type Rooms = Arc<Mutex<HashMap<String, Waiting>>>;
+
+fn main() -> io::Result<()> {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let listener = TcpListener::bind(&address)?;
+    println!("Relay listening on {}", listener.local_addr()?);
+    serve(listener);
+    Ok(())
+}
+
+fn serve(listener: TcpListener) {
+    let rooms = Rooms::default();
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let rooms = rooms.clone();
+                thread::spawn(move || {
+                    if let Err(error) = join(stream, &rooms) {
+                        eprintln!("Client dropped: {}", error);
+                    }
+                });
+            }
+            Err(error) => eprintln!("Connection failed: {}", error),
+        }
+    }
+}
+
+/// Reads the room of a new client and pairs it with the player waiting there
+fn join(stream: TcpStream, rooms: &Rooms) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let room = line
+        .trim()
+        .strip_prefix("JOIN ")
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected JOIN, got {:?}", line.trim()),
+            )
+        })?
+        .to_string();
+
+    loop {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let (partners, partner_receiver) = mpsc::channel();
+        let waiting = {
+            let mut rooms = rooms.lock().unwrap();
+            let waiting = rooms.remove(&room);
+            if waiting.is_none() {
+                rooms.insert(
+                    room.clone(),
+                    Waiting {
+                        id,
+                        stream: stream.try_clone()?,
+                        partners,
+                    },
+                );
+            }
+            waiting
+        };
+        let Some(waiting) = waiting else {
+            println!("Player waiting in room {}", room);
+            writeln!(&stream, "SIDE 0")?;
+            return wait(stream, reader, rooms, &room, id, partner_receiver);
+        };
+        // the waiting player may have left in the meantime, take its place then
+        let (ready, ready_receiver) = mpsc::channel();
+        let partner = Partner {
+            stream: stream.try_clone()?,
+            ready,
+        };
+        if waiting.partners.send(partner).is_err() || !ready_receiver.recv().unwrap_or(false) {
+            continue;
+        }
+        println!("Game starting in room {}", room);
+        writeln!(&stream, "SIDE 1")?;
+        writeln!(&stream, "START")?;
+        if let Err(error) = await_ready(&stream, &mut reader) {
+            let _ = writeln!(&waiting.stream, "LEFT");
+            let _ = waiting.stream.shutdown(Shutdown::Both);
+            return Err(error);
+        }
+        forward(reader, waiting.stream);
+        return Ok(());
+    }
+}
+
+/// Watches a waiting player until a partner joins, freeing the room if it leaves
+fn wait(
+    stream: TcpStream,
+    mut reader: BufReader<TcpStream>,
+    rooms: &Rooms,
+    room: &str,
+    id: u64,
+    partners: mpsc::Receiver<Partner>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(WAIT_POLL))?;
+    let mut line = String::new();
+    loop {
+        match partners.try_recv() {
+            Ok(partner) => {
+                writeln!(&stream, "START")?;
+                let ready = await_ready(&stream, &mut reader).is_ok();
+                let _ = partner.ready.send(ready);
+                if !ready {
+                    println!("Player in room {} never answered START", room);
+                    return Ok(());
+                }
+                forward(reader, partner.stream);
+                return Ok(());
+            }
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => {}
+        }
+        line.clear();
+        let left = match reader.read_line(&mut line) {
+            Ok(0) => Ok(()),
+            Ok(_) => continue,
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue
+            }
+            Err(error) => Err(error),
+        };
+        let mut rooms = rooms.lock().unwrap();
+        // a partner may have taken this player out of the room already
+        if rooms.get(room).is_some_and(|waiting| waiting.id == id) {
+            rooms.remove(room);
+            println!("Player left room {}", room);
+        }
+        return left;
+    }
+}
+
+/// Waits for the player to acknowledge `START`, then stops timing its reads out
+fn await_ready(stream: &TcpStream, reader: &mut BufReader<TcpStream>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READY_TIMEOUT))?;
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if line.trim() != "READY" {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("expected READY, got {:?}", line.trim()),
+        ));
+    }
+    stream.set_read_timeout(None)
+}
+
+/// Sends every line of one player to the other, until either disconnects
+fn forward(from: impl BufRead, mut to: TcpStream) {
+    for line in from.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if writeln!(to, "{}", line).is_err() {
+            break;
+        }
+    }
+    let _ = writeln!(to, "LEFT");
+    let _ = to.shutdown(Shutdown::Both);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn connect(address: &str, room: &str) -> (TcpStream, BufReader<TcpStream>) {
+        let stream = TcpStream::connect(address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        writeln!(&stream, "JOIN {}", room).unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        (stream, reader)
+    }
+
+    fn read(reader: &mut BufReader<TcpStream>) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        line.trim().to_string()
+    }
+
+    #[test]
+    fn test_relay_pairs_and_forwards() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let (host, mut host_reader) = connect(&address, "test");
+        assert_eq!(read(&mut host_reader), "SIDE 0");
+        let (_other_room, mut other_room_reader) = connect(&address, "other");
+        assert_eq!(read(&mut other_room_reader), "SIDE 0");
+        let (guest, mut guest_reader) = connect(&address, "test");
+        assert_eq!(read(&mut host_reader), "START");
+        writeln!(&host, "READY").unwrap();
+        assert_eq!(read(&mut guest_reader), "SIDE 1");
+        assert_eq!(read(&mut guest_reader), "START");
+        writeln!(&guest, "READY").unwrap();
+
+        writeln!(&host, "hello").unwrap();
+        assert_eq!(read(&mut guest_reader), "hello");
+        writeln!(&guest, "hi").unwrap();
+        assert_eq!(read(&mut host_reader), "hi");
+
+        host.shutdown(Shutdown::Both).unwrap();
+        assert_eq!(read(&mut guest_reader), "LEFT");
+    }
+
+    #[test]
+    fn test_relay_frees_rooms_of_departed_players() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let (host, mut host_reader) = connect(&address, "test");
+        assert_eq!(read(&mut host_reader), "SIDE 0");
+        host.shutdown(Shutdown::Both).unwrap();
+        thread::sleep(WAIT_POLL * 5);
+
+        let (_guest, mut guest_reader) = connect(&address, "test");
+        assert_eq!(read(&mut guest_reader), "SIDE 0");
+    }
+}
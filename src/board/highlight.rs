@@ -9,7 +9,11 @@ use crate::{
         common::{Piece, Team},
         health::DeathAnimation,
         movement::{move_piece, MovePieceEvent},
-        player::{spawn::Player, upgrades::data::Upgrades},
+        player::{
+            consumables::{hero_movement_types, Promoted},
+            spawn::Player,
+            upgrades::data::Upgrades,
+        },
     },
     states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
 };
@@ -52,9 +56,12 @@ pub fn update_highlight_cache(
         (&BoardPosition, &Team),
         (With<Piece>, Without<Player>, Without<DeathAnimation>),
     >,
-    player: Query<(&BoardPosition, &Upgrades, &Team), (With<Piece>, With<Player>)>,
+    player: Query<
+        (&BoardPosition, &Upgrades, &Team, Option<&Promoted>),
+        (With<Piece>, With<Player>),
+    >,
 ) {
-    let (player_board_position, player_upgrades, player_team) = player.single();
+    let (player_board_position, player_upgrades, player_team, promoted) = player.single();
     if highlight.player_moves.is_empty() && highlight.player_attacks.is_empty() {
         let enemies_board_positions = HashSet::from_iter(
             other_pieces
@@ -68,7 +75,7 @@ pub fn update_highlight_cache(
                 .iter()
                 .map(|(board_position, _)| *board_position),
         );
-        let movement_types = player_upgrades.get_movement_types_set();
+        let movement_types = hero_movement_types(player_upgrades, promoted);
 
         // fill the highlight with valid moves and attacks
         for movement_type in movement_types {
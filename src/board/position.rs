@@ -1,4 +1,4 @@
-use std::ops::Sub;
+use std::{fmt, ops::Sub};
 
 use bevy::{prelude::*, utils::HashSet};
 use rand::prelude::*;
@@ -34,6 +34,19 @@ impl BoardPosition {
         self.y = y;
     }
 
+    /// Squares crossed moving in a straight line or a diagonal to `other`, without the ends
+    pub fn squares_between(&self, other: &BoardPosition) -> Vec<BoardPosition> {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+            return Vec::new();
+        }
+        (1..dx.abs().max(dy.abs()))
+            .filter_map(|step| {
+                BoardPosition::new(self.x + dx.signum() * step, self.y + dy.signum() * step)
+            })
+            .collect()
+    }
+
     pub fn is_white(&self) -> bool {
         (self.x + self.y) % 2 == 0
     }
@@ -72,8 +85,10 @@ impl BoardPosition {
         self.x >= 0 && self.x < BOARD_SIZE && self.y >= 0 && self.y < BOARD_SIZE
     }
 
-    pub fn get_random_empty_position(other_positions: &HashSet<BoardPosition>) -> Self {
-        let mut rng = thread_rng();
+    pub fn get_random_empty_position(
+        rng: &mut impl Rng,
+        other_positions: &HashSet<BoardPosition>,
+    ) -> Self {
         loop {
             let pos =
                 Self::new(rng.gen_range(0..BOARD_SIZE), rng.gen_range(0..BOARD_SIZE)).unwrap();
@@ -84,10 +99,10 @@ impl BoardPosition {
     }
 
     pub fn get_random_position_limited(
+        rng: &mut impl Rng,
         other_positions: &HashSet<BoardPosition>,
         side_available: &[PositionAvailable],
     ) -> Self {
-        let mut rng = thread_rng();
         loop {
             let pos =
                 Self::new(rng.gen_range(0..BOARD_SIZE), rng.gen_range(0..BOARD_SIZE)).unwrap();
@@ -128,6 +143,13 @@ impl Sub<BoardPosition> for BoardPosition {
     }
 }
 
+// chess notation, with a1 at the bottom left of the board
+impl fmt::Display for BoardPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'a' + self.x as u8) as char, self.y + 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy::math::Vec2;
@@ -170,6 +192,27 @@ mod tests {
         assert!(BoardPosition::new(1, 1).unwrap().is_white());
     }
 
+    #[test]
+    fn test_squares_between() {
+        let origin = BoardPosition::new(1, 1).unwrap();
+        assert_eq!(
+            origin.squares_between(&BoardPosition::new(4, 4).unwrap()),
+            vec![
+                BoardPosition::new(2, 2).unwrap(),
+                BoardPosition::new(3, 3).unwrap()
+            ]
+        );
+        assert_eq!(
+            origin.squares_between(&BoardPosition::new(1, 0).unwrap()),
+            vec![]
+        );
+        // knight jumps cross nothing
+        assert_eq!(
+            origin.squares_between(&BoardPosition::new(2, 3).unwrap()),
+            vec![]
+        );
+    }
+
     #[test]
     fn test_from_global_position() {
         let tile_size = globals::TILE_SIZE;
@@ -228,4 +271,11 @@ mod tests {
             Some(BoardPosition::new(1, 1).unwrap())
         );
     }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(BoardPosition::new(0, 0).unwrap().to_string(), "a1");
+        assert_eq!(BoardPosition::new(4, 3).unwrap().to_string(), "e4");
+        assert_eq!(BoardPosition::new(7, 7).unwrap().to_string(), "h8");
+    }
 }
@@ -0,0 +1,218 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::{
+    globals::{
+        ASCENSION_ENEMY_STAT_MULTIPLIER, ASCENSION_EXTRA_ENEMIES, ASCENSION_GOLD_PERCENT,
+        ASCENSION_SCORE_PERCENT_STEP, ASCENSION_SHOP_PRICE_PERCENT, ASCENSION_SPAWN_TURN_SHIFT,
+        ASCENSION_UNLOCK_TURN, ELITE_SPAWN_TURN,
+    },
+    states::game_state::GameState,
+};
+
+use super::{
+    balance::Balance,
+    profile::{earn_meta_currency, Profile},
+    run_config::RunConfig,
+    run_stats::{record_run, RunFinished},
+};
+
+/// Modifier added by an ascension level, on top of the previous levels
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AscensionModifier {
+    EarlierSpawns,
+    MoreEnemies,
+    StrongerEnemies,
+    PricierShop,
+    LessGold,
+    EarlyElites,
+}
+
+impl fmt::Display for AscensionModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AscensionModifier::EarlierSpawns => write!(
+                f,
+                "Enemies spawn {} turns earlier",
+                ASCENSION_SPAWN_TURN_SHIFT
+            ),
+            AscensionModifier::MoreEnemies => {
+                write!(f, "{} more enemies on the board", ASCENSION_EXTRA_ENEMIES)
+            }
+            AscensionModifier::StrongerEnemies => write!(
+                f,
+                "Enemies have x{} health and damage",
+                ASCENSION_ENEMY_STAT_MULTIPLIER
+            ),
+            AscensionModifier::PricierShop => {
+                write!(f, "Shop prices at {}%", ASCENSION_SHOP_PRICE_PERCENT)
+            }
+            AscensionModifier::LessGold => {
+                write!(f, "Gold per kill at {}%", ASCENSION_GOLD_PERCENT)
+            }
+            AscensionModifier::EarlyElites => write!(f, "Elites spawn from turn 1"),
+        }
+    }
+}
+
+/// Level `n` applies the first `n` modifiers
+pub const ASCENSION_LADDER: [AscensionModifier; 6] = [
+    AscensionModifier::EarlierSpawns,
+    AscensionModifier::MoreEnemies,
+    AscensionModifier::StrongerEnemies,
+    AscensionModifier::PricierShop,
+    AscensionModifier::LessGold,
+    AscensionModifier::EarlyElites,
+];
+
+pub const MAX_ASCENSION: usize = ASCENSION_LADDER.len();
+
+/// Difficulty of a run, level 0 has no modifiers
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Ascension(pub usize);
+
+impl Ascension {
+    pub fn modifiers(&self) -> &'static [AscensionModifier] {
+        &ASCENSION_LADDER[..self.0.min(MAX_ASCENSION)]
+    }
+
+    pub fn has(&self, modifier: AscensionModifier) -> bool {
+        self.modifiers().contains(&modifier)
+    }
+
+    pub fn spawn_turn(&self, spawn_turn: usize) -> usize {
+        if self.has(AscensionModifier::EarlierSpawns) {
+            spawn_turn.saturating_sub(ASCENSION_SPAWN_TURN_SHIFT).max(1)
+        } else {
+            spawn_turn
+        }
+    }
+
+    pub fn target_num_enemies(&self, balance: &Balance) -> usize {
+        if self.has(AscensionModifier::MoreEnemies) {
+            balance.target_num_enemies + ASCENSION_EXTRA_ENEMIES
+        } else {
+            balance.target_num_enemies
+        }
+    }
+
+    /// Multiplier of the health and damage of enemies
+    pub fn enemy_stat_multiplier(&self) -> f32 {
+        if self.has(AscensionModifier::StrongerEnemies) {
+            ASCENSION_ENEMY_STAT_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    pub fn shop_price(&self, cost: usize) -> usize {
+        if self.has(AscensionModifier::PricierShop) {
+            cost * ASCENSION_SHOP_PRICE_PERCENT / 100
+        } else {
+            cost
+        }
+    }
+
+    pub fn gold_per_kill(&self, gold: usize) -> usize {
+        if self.has(AscensionModifier::LessGold) {
+            gold * ASCENSION_GOLD_PERCENT / 100
+        } else {
+            gold
+        }
+    }
+
+    pub fn elite_spawn_turn(&self) -> usize {
+        if self.has(AscensionModifier::EarlyElites) {
+            1
+        } else {
+            ELITE_SPAWN_TURN
+        }
+    }
+
+    pub fn score_percent(&self) -> usize {
+        100 + ASCENSION_SCORE_PERCENT_STEP * self.0.min(MAX_ASCENSION)
+    }
+
+    /// Score of a run from the value of its kills
+    pub fn score(&self, base_score: usize) -> usize {
+        base_score * self.score_percent() / 100
+    }
+}
+
+impl fmt::Display for Ascension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ascension {} (score x{:.2})",
+            self.0,
+            self.score_percent() as f32 / 100.0
+        )
+    }
+}
+
+/// Winning or reaching `ASCENSION_UNLOCK_TURN` unlocks the next level
+fn unlock_next_ascension(
+    mut run_finished: EventReader<RunFinished>,
+    config: Res<RunConfig>,
+    mut profile: ResMut<Profile>,
+) {
+    for RunFinished(summary) in run_finished.read() {
+        if summary.victory || summary.turns >= ASCENSION_UNLOCK_TURN {
+            unlock_ascension(&mut profile, config.difficulty + 1);
+        }
+    }
+}
+
+pub fn unlock_ascension(profile: &mut Profile, level: usize) {
+    let level = level.min(MAX_ASCENSION);
+    if level > profile.max_ascension {
+        debug!("Unlocked ascension {}", level);
+        profile.max_ascension = level;
+        profile.save();
+    }
+}
+
+pub struct AscensionPlugin;
+
+impl Plugin for AscensionPlugin {
+    fn build(&self, app: &mut App) {
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(
+                OnEnter(state),
+                unlock_next_ascension
+                    .after(record_run)
+                    .after(earn_meta_currency),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modifiers_stack() {
+        let base = Ascension(0);
+        assert!(base.modifiers().is_empty());
+        assert_eq!(base.score(40), 40);
+
+        let ascension = Ascension(4);
+        assert_eq!(ascension.spawn_turn(15), 5);
+        assert_eq!(ascension.spawn_turn(1), 1);
+        assert_eq!(
+            ascension.target_num_enemies(&Balance::default()),
+            Balance::default().target_num_enemies + ASCENSION_EXTRA_ENEMIES
+        );
+        assert_eq!(ascension.shop_price(10), 15);
+        assert!(!ascension.has(AscensionModifier::LessGold));
+        assert_eq!(ascension.gold_per_kill(10), 10);
+        assert_eq!(ascension.score(40), 80);
+
+        assert_eq!(
+            Ascension(MAX_ASCENSION + 3).modifiers().len(),
+            MAX_ASCENSION
+        );
+    }
+}
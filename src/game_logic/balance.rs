@@ -0,0 +1,452 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::globals::BALANCE_ASSET_PATH;
+
+use super::run_config::{GameMode, RunConfig};
+
+/// Embedded so the values exist before the asset server loads the file
+const BALANCE_FILE: &str = include_str!("../../assets/game.balance.ron");
+
+/// Gameplay tuning values of the mode being played
+#[derive(Resource, Deserialize, Clone, Debug, PartialEq)]
+pub struct Balance {
+    pub player_health: f32,
+    pub enemy_base_health: f32,
+    /// Turn number to spawn kings
+    pub king_spawn_turn: usize,
+    /// Max number of enemies on the board
+    pub target_num_enemies: usize,
+    /// Multiplier for the value of pieces in the shop
+    pub shop_piece_value_gold_multiplier: f32,
+    pub refresh_shop_cost: usize,
+    pub queen_unique_chance: f32,
+    pub convert_enemy_turns_to_convert: usize,
+    /// Damage multiplier of each piece pierced through, until the second bishop tier
+    pub pierce_damage_falloff: f32,
+    /// Block granted by rook attacks with the second rook tier
+    pub advanced_rook_block: usize,
+    /// Upgrades offered on each level up
+    pub draft_size: usize,
+    /// Gold to draw other upgrades of a draft
+    pub draft_reroll_cost: usize,
+    /// Gold received for skipping a draft
+    pub draft_skip_gold: usize,
+    /// Gold back for a sold upgrade, in percent of its price
+    pub shop_sell_percent: usize,
+    /// Added to the refresh cost by each refresh of the turn
+    pub shop_refresh_cost_increase: usize,
+    /// Chance of each offer to be on sale
+    pub shop_sale_chance: f64,
+    /// Price of an offer on sale, in percent of its price
+    pub shop_sale_percent: usize,
+    /// Weight multiplier of rare upgrades at the start of a run
+    pub rare_weight: f32,
+    /// Weight multiplier of epic upgrades at the start of a run
+    pub epic_weight: f32,
+    /// Added to the rare and epic weight multipliers each progression step
+    pub rarity_weight_per_step: f32,
+    /// Turns played for the shop odds to improve like a level up
+    pub rarity_turns_per_step: usize,
+    /// Shop price of every consumable
+    pub consumable_cost: usize,
+    /// Chance of a killed enemy to drop a consumable
+    pub consumable_drop_chance: f64,
+    /// Health restored by a healing potion
+    pub healing_potion_amount: f32,
+    /// Damage of a bomb to every enemy around the hero
+    pub bomb_damage: f32,
+    /// Tiles kept between a teleported hero and the enemies when possible
+    pub teleport_safe_distance: i32,
+    /// Enemy turns skipped by a time stop
+    pub time_stop_turns: usize,
+    /// Turns the scroll of promotion lasts
+    pub promotion_turns: usize,
+    /// Shop price of a relic
+    pub relic_cost: usize,
+    /// Health restored for each enemy killed
+    pub relic_kill_heal: f32,
+    /// Gold gained on each level up
+    pub relic_level_up_gold: usize,
+    /// Damage multiplier of the first hit of each turn
+    pub relic_first_hit_multiplier: f32,
+    /// Damage of a reverting ally to the enemies around it
+    pub relic_explosion_damage: f32,
+    /// Gold gained for each move
+    pub relic_move_gold: usize,
+    /// Damage to the enemies around the hero when it is hurt
+    pub relic_thorns_damage: f32,
+    /// Movement types allowed from a number of movement upgrades, in increasing order
+    pub movement_type_limits: Vec<(usize, usize)>,
+}
+
+/// Values replaced in one game mode
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct BalanceOverride {
+    pub player_health: Option<f32>,
+    pub enemy_base_health: Option<f32>,
+    pub king_spawn_turn: Option<usize>,
+    pub target_num_enemies: Option<usize>,
+    pub shop_piece_value_gold_multiplier: Option<f32>,
+    pub refresh_shop_cost: Option<usize>,
+    pub queen_unique_chance: Option<f32>,
+    pub convert_enemy_turns_to_convert: Option<usize>,
+    pub pierce_damage_falloff: Option<f32>,
+    pub advanced_rook_block: Option<usize>,
+    pub draft_size: Option<usize>,
+    pub draft_reroll_cost: Option<usize>,
+    pub draft_skip_gold: Option<usize>,
+    pub shop_sell_percent: Option<usize>,
+    pub shop_refresh_cost_increase: Option<usize>,
+    pub shop_sale_chance: Option<f64>,
+    pub shop_sale_percent: Option<usize>,
+    pub rare_weight: Option<f32>,
+    pub epic_weight: Option<f32>,
+    pub rarity_weight_per_step: Option<f32>,
+    pub rarity_turns_per_step: Option<usize>,
+    pub consumable_cost: Option<usize>,
+    pub consumable_drop_chance: Option<f64>,
+    pub healing_potion_amount: Option<f32>,
+    pub bomb_damage: Option<f32>,
+    pub teleport_safe_distance: Option<i32>,
+    pub time_stop_turns: Option<usize>,
+    pub promotion_turns: Option<usize>,
+    pub relic_cost: Option<usize>,
+    pub relic_kill_heal: Option<f32>,
+    pub relic_level_up_gold: Option<usize>,
+    pub relic_first_hit_multiplier: Option<f32>,
+    pub relic_explosion_damage: Option<f32>,
+    pub relic_move_gold: Option<usize>,
+    pub relic_thorns_damage: Option<f32>,
+    pub movement_type_limits: Option<Vec<(usize, usize)>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidBalance {
+    Parse(String),
+    /// The movement type limits must start at zero upgrades and increase
+    MovementTypeLimits(Option<GameMode>),
+}
+
+impl fmt::Display for InvalidBalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidBalance::Parse(error) => write!(f, "invalid balance file: {}", error),
+            InvalidBalance::MovementTypeLimits(None) => {
+                write!(f, "movement type limits must start at 0 and increase")
+            }
+            InvalidBalance::MovementTypeLimits(Some(mode)) => write!(
+                f,
+                "movement type limits of {} must start at 0 and increase",
+                mode
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidBalance {}
+
+/// Balance of every game mode, loaded from a `.balance.ron` file
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct BalanceConfig {
+    pub base: Balance,
+    #[serde(default)]
+    pub modes: HashMap<GameMode, BalanceOverride>,
+}
+
+impl BalanceConfig {
+    pub fn parse(content: &str) -> Result<Self, InvalidBalance> {
+        let config: Self =
+            ron::from_str(content).map_err(|error| InvalidBalance::Parse(error.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), InvalidBalance> {
+        let modes = [GameMode::Standard, GameMode::Daily, GameMode::Endless];
+        for (mode, balance) in modes.iter().map(|mode| (*mode, self.for_mode(*mode))) {
+            let limits = &balance.movement_type_limits;
+            let starts_at_zero = limits.first().is_some_and(|(upgrades, _)| *upgrades == 0);
+            let increasing = limits.windows(2).all(|pair| pair[0].0 < pair[1].0);
+            if !starts_at_zero || !increasing {
+                let mode = (*limits != self.base.movement_type_limits).then_some(mode);
+                return Err(InvalidBalance::MovementTypeLimits(mode));
+            }
+        }
+        Ok(())
+    }
+
+    /// Base values with the overrides of the mode
+    pub fn for_mode(&self, mode: GameMode) -> Balance {
+        let mut balance = self.base.clone();
+        let Some(changes) = self.modes.get(&mode).cloned() else {
+            return balance;
+        };
+        if let Some(value) = changes.player_health {
+            balance.player_health = value;
+        }
+        if let Some(value) = changes.enemy_base_health {
+            balance.enemy_base_health = value;
+        }
+        if let Some(value) = changes.king_spawn_turn {
+            balance.king_spawn_turn = value;
+        }
+        if let Some(value) = changes.target_num_enemies {
+            balance.target_num_enemies = value;
+        }
+        if let Some(value) = changes.shop_piece_value_gold_multiplier {
+            balance.shop_piece_value_gold_multiplier = value;
+        }
+        if let Some(value) = changes.refresh_shop_cost {
+            balance.refresh_shop_cost = value;
+        }
+        if let Some(value) = changes.queen_unique_chance {
+            balance.queen_unique_chance = value;
+        }
+        if let Some(value) = changes.convert_enemy_turns_to_convert {
+            balance.convert_enemy_turns_to_convert = value;
+        }
+        if let Some(value) = changes.pierce_damage_falloff {
+            balance.pierce_damage_falloff = value;
+        }
+        if let Some(value) = changes.advanced_rook_block {
+            balance.advanced_rook_block = value;
+        }
+        if let Some(value) = changes.draft_size {
+            balance.draft_size = value;
+        }
+        if let Some(value) = changes.draft_reroll_cost {
+            balance.draft_reroll_cost = value;
+        }
+        if let Some(value) = changes.draft_skip_gold {
+            balance.draft_skip_gold = value;
+        }
+        if let Some(value) = changes.shop_sell_percent {
+            balance.shop_sell_percent = value;
+        }
+        if let Some(value) = changes.shop_refresh_cost_increase {
+            balance.shop_refresh_cost_increase = value;
+        }
+        if let Some(value) = changes.shop_sale_chance {
+            balance.shop_sale_chance = value;
+        }
+        if let Some(value) = changes.shop_sale_percent {
+            balance.shop_sale_percent = value;
+        }
+        if let Some(value) = changes.rare_weight {
+            balance.rare_weight = value;
+        }
+        if let Some(value) = changes.epic_weight {
+            balance.epic_weight = value;
+        }
+        if let Some(value) = changes.rarity_weight_per_step {
+            balance.rarity_weight_per_step = value;
+        }
+        if let Some(value) = changes.rarity_turns_per_step {
+            balance.rarity_turns_per_step = value;
+        }
+        if let Some(value) = changes.consumable_cost {
+            balance.consumable_cost = value;
+        }
+        if let Some(value) = changes.consumable_drop_chance {
+            balance.consumable_drop_chance = value;
+        }
+        if let Some(value) = changes.healing_potion_amount {
+            balance.healing_potion_amount = value;
+        }
+        if let Some(value) = changes.bomb_damage {
+            balance.bomb_damage = value;
+        }
+        if let Some(value) = changes.teleport_safe_distance {
+            balance.teleport_safe_distance = value;
+        }
+        if let Some(value) = changes.time_stop_turns {
+            balance.time_stop_turns = value;
+        }
+        if let Some(value) = changes.promotion_turns {
+            balance.promotion_turns = value;
+        }
+        if let Some(value) = changes.relic_cost {
+            balance.relic_cost = value;
+        }
+        if let Some(value) = changes.relic_kill_heal {
+            balance.relic_kill_heal = value;
+        }
+        if let Some(value) = changes.relic_level_up_gold {
+            balance.relic_level_up_gold = value;
+        }
+        if let Some(value) = changes.relic_first_hit_multiplier {
+            balance.relic_first_hit_multiplier = value;
+        }
+        if let Some(value) = changes.relic_explosion_damage {
+            balance.relic_explosion_damage = value;
+        }
+        if let Some(value) = changes.relic_move_gold {
+            balance.relic_move_gold = value;
+        }
+        if let Some(value) = changes.relic_thorns_damage {
+            balance.relic_thorns_damage = value;
+        }
+        if let Some(value) = changes.movement_type_limits {
+            balance.movement_type_limits = value;
+        }
+        balance
+    }
+}
+
+impl Balance {
+    /// Movement types allowed with this many movement upgrades
+    pub fn movement_type_limit(&self, movement_upgrades: usize) -> usize {
+        self.movement_type_limits
+            .iter()
+            .rev()
+            .find(|(upgrades, _)| movement_upgrades >= *upgrades)
+            .map_or(0, |(_, limit)| *limit)
+    }
+}
+
+/// Embedded values, replaced once the asset server loads the file
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        BalanceConfig::parse(BALANCE_FILE).expect("Balance file is valid")
+    }
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        BalanceConfig::default().for_mode(GameMode::default())
+    }
+}
+
+#[derive(Default)]
+struct BalanceConfigLoader;
+
+impl AssetLoader for BalanceConfigLoader {
+    type Asset = BalanceConfig;
+    type Settings = ();
+    type Error = InvalidBalance;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<BalanceConfig, InvalidBalance> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| InvalidBalance::Parse(error.to_string()))?;
+        let content = std::str::from_utf8(&bytes)
+            .map_err(|error| InvalidBalance::Parse(error.to_string()))?;
+        // rejected files keep the previous values
+        BalanceConfig::parse(content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["balance.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct BalanceConfigHandle(Handle<BalanceConfig>);
+
+fn load_balance_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(BalanceConfigHandle(asset_server.load(BALANCE_ASSET_PATH)));
+}
+
+/// Swaps in the values of the file, also when it changes on disk in debug builds
+fn reload_balance(
+    mut events: EventReader<AssetEvent<BalanceConfig>>,
+    configs: Res<Assets<BalanceConfig>>,
+    handle: Res<BalanceConfigHandle>,
+    mut balance_config: ResMut<BalanceConfig>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        if let Some(loaded) = configs.get(*id) {
+            *balance_config = loaded.clone();
+            debug!("Balance loaded from {}", BALANCE_ASSET_PATH);
+        }
+    }
+}
+
+/// Uses the overrides of the mode picked for the next run, only changes the values when they differ
+pub fn apply_mode_balance(
+    config: Res<RunConfig>,
+    balance_config: Res<BalanceConfig>,
+    mut balance: ResMut<Balance>,
+) {
+    balance.set_if_neq(balance_config.for_mode(config.mode));
+}
+
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BalanceConfig>()
+            .init_asset_loader::<BalanceConfigLoader>()
+            .init_resource::<BalanceConfig>()
+            .init_resource::<Balance>()
+            .add_systems(Startup, load_balance_config)
+            .add_systems(
+                Update,
+                reload_balance.run_if(on_event::<AssetEvent<BalanceConfig>>),
+            )
+            // after the menus changed the config, before the run starts next frame
+            .add_systems(
+                PostUpdate,
+                apply_mode_balance
+                    .run_if(resource_changed::<RunConfig>.or(resource_changed::<BalanceConfig>)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_file_is_valid() {
+        let config = BalanceConfig::parse(BALANCE_FILE).unwrap();
+        let balance = config.for_mode(GameMode::Standard);
+        assert_eq!(balance.movement_type_limit(0), 2);
+        assert_eq!(balance.movement_type_limit(4), 3);
+        assert_eq!(balance.movement_type_limit(100), 4);
+    }
+
+    #[test]
+    fn test_mode_overrides() {
+        let content = BALANCE_FILE.replace(
+            "modes: {}",
+            "modes: { Endless: (target_num_enemies: Some(12)) }",
+        );
+        let config = BalanceConfig::parse(&content).unwrap();
+        assert_eq!(config.for_mode(GameMode::Endless).target_num_enemies, 12);
+        assert_eq!(
+            config.for_mode(GameMode::Daily),
+            config.for_mode(GameMode::Standard)
+        );
+
+        let unordered = BALANCE_FILE.replace(
+            "modes: {}",
+            "modes: { Daily: (movement_type_limits: Some([(0, 2), (0, 3)])) }",
+        );
+        assert_eq!(
+            BalanceConfig::parse(&unordered).err(),
+            Some(InvalidBalance::MovementTypeLimits(Some(GameMode::Daily)))
+        );
+    }
+}
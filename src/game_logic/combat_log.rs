@@ -0,0 +1,265 @@
+use std::fmt;
+
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    globals::COMBAT_LOG_EXPORT_FILE,
+    pieces::{
+        health::PieceDeathEvent, movement::MovePieceEvent,
+        player::upgrades::unique_upgrades::SideEffect,
+    },
+    states::{game_state::GameState, turn_state::TurnInfo},
+    utils::storage::write_file,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LogCategory {
+    Move,
+    Attack,
+    SideEffect,
+    Death,
+    Reward,
+    Spawn,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 6] = [
+        LogCategory::Move,
+        LogCategory::Attack,
+        LogCategory::SideEffect,
+        LogCategory::Death,
+        LogCategory::Reward,
+        LogCategory::Spawn,
+    ];
+}
+
+impl fmt::Display for LogCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogCategory::Move => write!(f, "Move"),
+            LogCategory::Attack => write!(f, "Attack"),
+            LogCategory::SideEffect => write!(f, "Effect"),
+            LogCategory::Death => write!(f, "Death"),
+            LogCategory::Reward => write!(f, "Reward"),
+            LogCategory::Spawn => write!(f, "Spawn"),
+        }
+    }
+}
+
+/// Adds a line to the combat log, for the current turn
+#[derive(Event, Clone, Debug)]
+pub struct CombatLogEvent {
+    pub category: LogCategory,
+    pub message: String,
+}
+
+impl CombatLogEvent {
+    pub fn new(category: LogCategory, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CombatLogEntry {
+    pub turn: usize,
+    pub category: LogCategory,
+    pub message: String,
+}
+
+#[derive(Resource, Default)]
+pub struct CombatLog {
+    pub entries: Vec<CombatLogEntry>,
+    pub hidden_categories: HashSet<LogCategory>,
+}
+
+impl CombatLog {
+    pub fn is_visible(&self, category: LogCategory) -> bool {
+        !self.hidden_categories.contains(&category)
+    }
+
+    pub fn toggle_category(&mut self, category: LogCategory) {
+        if !self.hidden_categories.remove(&category) {
+            self.hidden_categories.insert(category);
+        }
+    }
+
+    pub fn visible_entries(&self) -> impl Iterator<Item = &CombatLogEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_visible(entry.category))
+    }
+
+    /// Plain text version of the visible entries, grouped by turn
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let mut current_turn = None;
+        for entry in self.visible_entries() {
+            if current_turn != Some(entry.turn) {
+                current_turn = Some(entry.turn);
+                text.push_str(&format!("Turn {}\n", entry.turn));
+            }
+            text.push_str(&format!("  [{}] {}\n", entry.category, entry.message));
+        }
+        text
+    }
+}
+
+/// Writes the visible entries of the combat log next to the executable
+pub fn export_combat_log(log: &CombatLog) -> std::io::Result<&'static str> {
+    write_file(COMBAT_LOG_EXPORT_FILE, &log.to_text())?;
+    Ok(COMBAT_LOG_EXPORT_FILE)
+}
+
+fn record_combat_log(
+    mut events: EventReader<CombatLogEvent>,
+    turn_info: Res<TurnInfo>,
+    mut log: ResMut<CombatLog>,
+) {
+    for event in events.read() {
+        debug!("[{}] {}", event.category, event.message);
+        log.entries.push(CombatLogEntry {
+            turn: turn_info.number,
+            category: event.category,
+            message: event.message.clone(),
+        });
+    }
+}
+
+fn log_moves(
+    mut move_events: EventReader<MovePieceEvent>,
+    names: Query<&Name>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    for event in move_events.read() {
+        if let Ok(name) = names.get(event.entity) {
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Move,
+                format!("{} moves to {}", name, event.destination),
+            ));
+        }
+    }
+}
+
+fn log_side_effects(
+    mut side_effects: EventReader<SideEffect>,
+    names: Query<&Name>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    let name_of = |entity: Entity| {
+        names
+            .get(entity)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|_| "Unknown".to_string())
+    };
+    for side_effect in side_effects.read() {
+        let message = match side_effect {
+            SideEffect::AttackRandomTarget {
+                generator_event, ..
+            } => format!(
+                "{} attacks a random target",
+                name_of(generator_event.attacker)
+            ),
+            SideEffect::Chain {
+                generator_event, ..
+            } => format!(
+                "{}'s attack chains from {}",
+                name_of(generator_event.attacker),
+                generator_event.destination
+            ),
+            SideEffect::Pierce {
+                generator_event, ..
+            } => format!(
+                "{}'s attack pierces through {}",
+                name_of(generator_event.attacker),
+                generator_event.destination
+            ),
+            SideEffect::ConvertPiece {
+                turns_to_convert: Some(turns),
+                entity,
+                ..
+            } => format!("{} is converted for {} turns", name_of(*entity), turns),
+            SideEffect::ConvertPiece {
+                turns_to_convert: None,
+                entity,
+                ..
+            } => format!("{} is converted for good", name_of(*entity)),
+            SideEffect::Block { amount, entity } => {
+                format!("{} gains Block({})", name_of(*entity), amount)
+            }
+            SideEffect::Nothing => continue,
+        };
+        log_writer.send(CombatLogEvent::new(LogCategory::SideEffect, message));
+    }
+}
+
+fn log_deaths(
+    mut death_events: EventReader<PieceDeathEvent>,
+    names: Query<&Name>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    for event in death_events.read() {
+        if let Ok(name) = names.get(event.entity) {
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Death,
+                format!("{} dies", name),
+            ));
+        }
+    }
+}
+
+fn reset_combat_log(mut log: ResMut<CombatLog>) {
+    debug!("Resetting combat log");
+    log.entries.clear();
+}
+
+pub struct CombatLogPlugin;
+
+impl Plugin for CombatLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatLog>()
+            .add_event::<CombatLogEvent>()
+            .add_systems(
+                Update,
+                ((log_moves, log_side_effects, log_deaths), record_combat_log)
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnEnter(GameState::Game), reset_combat_log);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(turn: usize, category: LogCategory, message: &str) -> CombatLogEntry {
+        CombatLogEntry {
+            turn,
+            category,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_filter_and_export() {
+        let mut log = CombatLog::default();
+        log.entries
+            .push(entry(1, LogCategory::Move, "Player moves to e4"));
+        log.entries
+            .push(entry(1, LogCategory::Attack, "Player hits Pawn"));
+        log.entries.push(entry(2, LogCategory::Death, "Pawn dies"));
+
+        log.toggle_category(LogCategory::Move);
+        assert_eq!(log.visible_entries().count(), 2);
+        assert_eq!(
+            log.to_text(),
+            "Turn 1\n  [Attack] Player hits Pawn\nTurn 2\n  [Death] Pawn dies\n"
+        );
+
+        log.toggle_category(LogCategory::Move);
+        assert_eq!(log.visible_entries().count(), 3);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::position::BoardPosition,
+    globals::{COOP_REVIVE_HEALTH_FRACTION, DOWNED_HERO_COLOR, HERO_COLORS},
+    pieces::{
+        health::Health,
+        movement_type::MovementType,
+        player::{
+            character::CharacterRoster,
+            gold::Gold,
+            spawn::{Hero, Player},
+            upgrades::data::UpgradeCatalog,
+        },
+    },
+    states::{game_state::GameState, turn_state::TurnState},
+    ui::{
+        messages::{ClearMessages, MessageEvent},
+        shop::ApplyUpgrades,
+    },
+};
+
+use super::{
+    combat_log::{CombatLogEvent, LogCategory},
+    defeat::check_defeat,
+    profile::Profile,
+    run_config::RunConfig,
+};
+
+/// How the co-op heroes pay for their upgrades
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GoldSharing {
+    Shared,
+    /// Each hero only spends the gold earned during its own actions
+    Split,
+}
+
+impl fmt::Display for GoldSharing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldSharing::Shared => write!(f, "shared gold"),
+            GoldSharing::Split => write!(f, "split gold"),
+        }
+    }
+}
+
+/// Hero that is down until its partner ends an action next to it
+#[derive(Component)]
+pub struct Downed;
+
+#[derive(Resource, Default)]
+pub struct Coop {
+    /// Side of the hero playing
+    pub active: usize,
+    /// Hero still to act before the enemies play
+    pub next_hero: Option<usize>,
+    acted: [bool; 2],
+    /// Gold of the other hero when the gold is split
+    waiting_gold: usize,
+}
+
+impl Coop {
+    /// First hero of `alive` that did not act this turn
+    fn next_to_act(&self, alive: [bool; 2]) -> Option<usize> {
+        (0..2).find(|&side| alive[side] && !self.acted[side])
+    }
+}
+
+pub fn is_coop(config: Res<RunConfig>) -> bool {
+    config.coop.is_some()
+}
+
+/// The turn goes back to the heroes instead of the allies and enemies
+pub fn partner_waiting(coop: Res<Coop>) -> bool {
+    coop.next_hero.is_some()
+}
+
+fn alive_heroes(heroes: &Query<(Entity, &Hero, Has<Downed>)>) -> [bool; 2] {
+    let mut alive = [false; 2];
+    for (_, hero, downed) in heroes.iter() {
+        alive[hero.side] = !downed;
+    }
+    alive
+}
+
+/// Gives the `Player` marker to the hero acting next
+fn start_hero_action(
+    mut commands: Commands,
+    mut coop: ResMut<Coop>,
+    heroes: Query<(Entity, &Hero, Has<Downed>)>,
+    config: Res<RunConfig>,
+    mut gold: ResMut<Gold>,
+    mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    catalog: Res<UpgradeCatalog>,
+    mut clear_messages_writer: EventWriter<ClearMessages>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    let next = match coop.next_hero.take() {
+        Some(side) => side,
+        None => {
+            // new turn, every standing hero acts again
+            coop.acted = [false; 2];
+            match coop.next_to_act(alive_heroes(&heroes)) {
+                Some(side) => side,
+                None => return,
+            }
+        }
+    };
+    if next == coop.active {
+        return;
+    }
+    coop.active = next;
+    if config.coop == Some(GoldSharing::Split) {
+        std::mem::swap(&mut coop.waiting_gold, &mut gold.amount);
+    }
+    for (entity, hero, _) in heroes.iter() {
+        if hero.side == next {
+            commands.entity(entity).insert(Player);
+        } else {
+            commands.entity(entity).remove::<Player>();
+        }
+    }
+    // refreshes the movement limit, highlights and character info of the new hero
+    apply_upgrades_event_writer.send(ApplyUpgrades(
+        catalog.get_movement_upgrade(&MovementType::King),
+    ));
+    clear_messages_writer.send(ClearMessages);
+    message_event_writer.send(MessageEvent {
+        message: format!("Player {}'s turn", next + 1),
+        timer: Some(Timer::from_seconds(2.0, TimerMode::Once)),
+    });
+}
+
+fn end_hero_action(mut coop: ResMut<Coop>, heroes: Query<(Entity, &Hero, Has<Downed>)>) {
+    let active = coop.active;
+    coop.acted[active] = true;
+    coop.next_hero = coop.next_to_act(alive_heroes(&heroes));
+}
+
+fn down_heroes(
+    mut commands: Commands,
+    mut heroes: Query<(Entity, &Hero, &Health, &mut Sprite), Without<Downed>>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    for (entity, hero, health, mut sprite) in heroes.iter_mut() {
+        if health.is_dead() {
+            commands.entity(entity).insert(Downed);
+            sprite.color = DOWNED_HERO_COLOR;
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Death,
+                format!(
+                    "Player {} is down, end an action next to them to revive",
+                    hero.side + 1
+                ),
+            ));
+        }
+    }
+}
+
+/// A hero ending its action next to its downed partner brings it back
+fn revive_partner(
+    mut commands: Commands,
+    player: Query<&BoardPosition, (With<Player>, Without<Downed>)>,
+    mut downed: Query<(Entity, &Hero, &BoardPosition, &mut Health, &mut Sprite), With<Downed>>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    let Ok(player_position) = player.get_single() else {
+        return;
+    };
+    for (entity, hero, position, mut health, mut sprite) in downed.iter_mut() {
+        if position.distance(*player_position) <= 1 {
+            let revived_health = health.max_value.upgraded_value * COOP_REVIVE_HEALTH_FRACTION;
+            health.set_health(revived_health.ceil());
+            sprite.color = HERO_COLORS[hero.side];
+            commands.entity(entity).remove::<Downed>();
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Reward,
+                format!("Player {} is revived", hero.side + 1),
+            ));
+        }
+    }
+}
+
+/// Co-op runs are lost only once both heroes are down
+fn check_coop_defeat(
+    heroes: Query<&Health, With<Hero>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if !heroes.is_empty() && heroes.iter().all(|health| health.is_dead()) {
+        game_state.set(GameState::Defeat);
+    }
+}
+
+fn reset_coop(
+    mut coop: ResMut<Coop>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    characters: Res<CharacterRoster>,
+) {
+    *coop = Coop {
+        waiting_gold: config.character(&profile, &characters).starting_gold(),
+        ..default()
+    };
+}
+
+pub struct CoopPlugin;
+
+impl Plugin for CoopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Coop>()
+            .add_systems(OnEnter(GameState::Game), reset_coop)
+            .add_systems(
+                OnEnter(TurnState::PlayerInput),
+                start_hero_action.run_if(in_state(GameState::Game).and(is_coop)),
+            )
+            .add_systems(
+                OnEnter(TurnState::PlayerAnimation),
+                end_hero_action.run_if(in_state(GameState::Game).and(is_coop)),
+            )
+            .add_systems(
+                OnExit(TurnState::PlayerAnimation),
+                revive_partner.run_if(in_state(GameState::Game).and(is_coop)),
+            )
+            .add_systems(
+                Update,
+                // a victory in the same frame is checked first, like for a single hero
+                (down_heroes, check_coop_defeat.after(check_defeat))
+                    .run_if(in_state(GameState::Game).and(is_coop)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_hero_to_act() {
+        let mut coop = Coop::default();
+        assert_eq!(coop.next_to_act([true, true]), Some(0));
+        coop.acted[0] = true;
+        assert_eq!(coop.next_to_act([true, true]), Some(1));
+        assert_eq!(coop.next_to_act([true, false]), None);
+        coop.acted = [false; 2];
+        assert_eq!(coop.next_to_act([false, true]), Some(1));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::{DAILY_MAX_MUTATORS, GLASS_CANNON_MULTIPLIER},
+    pieces::player::character::CharacterRoster,
+    utils::date::today,
+};
+
+use super::{
+    run_config::{GameMode, RunConfig},
+    victory::VictoryCondition,
+};
+
+/// Rule change of a daily challenge
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Mutator {
+    AllKnights,
+    NoShopRefresh,
+    PawnsPromoteToQueens,
+    GlassCannon,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 4] = [
+        Mutator::AllKnights,
+        Mutator::NoShopRefresh,
+        Mutator::PawnsPromoteToQueens,
+        Mutator::GlassCannon,
+    ];
+}
+
+impl fmt::Display for Mutator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutator::AllKnights => write!(f, "All enemies are knights"),
+            Mutator::NoShopRefresh => write!(f, "No shop refresh"),
+            Mutator::PawnsPromoteToQueens => write!(f, "Pawns promote to queens"),
+            Mutator::GlassCannon => write!(
+                f,
+                "Glass cannon: x{} attack, health divided by {}",
+                GLASS_CANNON_MULTIPLIER, GLASS_CANNON_MULTIPLIER
+            ),
+        }
+    }
+}
+
+/// Seed of the daily challenge, the same for everyone on a given date
+pub fn daily_seed(date: &str) -> u64 {
+    // FNV-1a, stable across builds unlike the std hasher
+    date.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub struct DailyChallenge {
+    pub date: String,
+    pub seed: u64,
+    pub character: String,
+    pub mutators: Vec<Mutator>,
+}
+
+impl DailyChallenge {
+    pub fn for_date(date: &str, characters: &CharacterRoster) -> Self {
+        let seed = daily_seed(date);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let character = characters
+            .characters
+            .choose(&mut rng)
+            .expect("No characters defined")
+            .name
+            .clone();
+        let count = rng.gen_range(1..=DAILY_MAX_MUTATORS.min(Mutator::ALL.len()));
+        let mutators = Mutator::ALL
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect();
+        Self {
+            date: date.to_string(),
+            seed,
+            character,
+            mutators,
+        }
+    }
+
+    pub fn today(characters: &CharacterRoster) -> Self {
+        Self::for_date(&today(), characters)
+    }
+
+    /// Sets up the next run as this challenge
+    pub fn configure(&self, config: &mut RunConfig) {
+        *config = RunConfig {
+            mode: GameMode::Daily,
+            fixed_seed: Some(self.seed),
+            character: Some(self.character.clone()),
+            mutators: self.mutators.clone(),
+            // daily runs are scored until the player dies
+            victory: VictoryCondition::Endless,
+            ..default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_is_deterministic() {
+        let characters = CharacterRoster::default();
+        let challenge = DailyChallenge::for_date("2026-03-14", &characters);
+        let again = DailyChallenge::for_date("2026-03-14", &characters);
+        assert_eq!(challenge.seed, again.seed);
+        assert_eq!(challenge.character, again.character);
+        assert_eq!(challenge.mutators, again.mutators);
+        assert!((1..=DAILY_MAX_MUTATORS).contains(&challenge.mutators.len()));
+
+        assert_ne!(challenge.seed, daily_seed("2026-03-15"));
+    }
+}
@@ -0,0 +1,337 @@
+use std::{cmp::Reverse, collections::BTreeMap, fmt};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::{LEADERBOARD_FILE, LEADERBOARD_SIZE},
+    pieces::enemies::roster::EnemyRoster,
+    states::game_state::GameState,
+    utils::{
+        date::today,
+        storage::{read_file, remove_file, write_file},
+    },
+};
+
+use super::{
+    ascension::Ascension,
+    replay::{save_replay, RunReplay},
+    run_config::{GameMode, RunConfig},
+    run_stats::RunFinished,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub mode: GameMode,
+    pub difficulty: usize,
+    pub seed: u64,
+    pub score: usize,
+    pub turns: usize,
+    pub date: String,
+    pub build: String,
+    pub kills: BTreeMap<String, usize>,
+    /// Replay file of the run, if one was recorded
+    pub replay: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InvalidEntry {
+    UnknownPiece(String),
+    ScoreMismatch { score: usize, expected: usize },
+    NoTurns,
+}
+
+impl fmt::Display for InvalidEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidEntry::UnknownPiece(name) => write!(f, "unknown piece killed: {}", name),
+            InvalidEntry::ScoreMismatch { score, expected } => write!(
+                f,
+                "score {} does not match the kills, expected {}",
+                score, expected
+            ),
+            InvalidEntry::NoTurns => write!(f, "run has no turns"),
+        }
+    }
+}
+
+impl LeaderboardEntry {
+    /// Checks that the entry could have been played,
+    /// the score has to match the value of the kills in the roster
+    /// with the score multiplier of the ascension level
+    pub fn validate(&self, roster: &EnemyRoster) -> Result<(), InvalidEntry> {
+        if self.turns == 0 {
+            return Err(InvalidEntry::NoTurns);
+        }
+        let mut expected = 0;
+        for (name, count) in self.kills.iter() {
+            let value = roster
+                .value(name)
+                .ok_or_else(|| InvalidEntry::UnknownPiece(name.clone()))?;
+            expected += value * count;
+        }
+        let expected = Ascension(self.difficulty).score(expected);
+        if expected != self.score {
+            return Err(InvalidEntry::ScoreMismatch {
+                score: self.score,
+                expected,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+    /// Rank of the last finished run, if it made it on the leaderboard
+    pub last_rank: Option<usize>,
+}
+
+impl Leaderboard {
+    /// Parses the leaderboard file, dropping invalid entries
+    pub fn parse(content: &str, roster: &EnemyRoster) -> Self {
+        let entries: Vec<LeaderboardEntry> = match ron::from_str(content) {
+            Ok(entries) => entries,
+            Err(error) => {
+                warn!("Could not read leaderboard: {}", error);
+                Vec::new()
+            }
+        };
+        let mut leaderboard = Leaderboard::default();
+        for entry in entries {
+            if let Err(error) = leaderboard.insert(entry, roster) {
+                warn!("Dropping leaderboard entry: {}", error);
+            }
+        }
+        leaderboard
+    }
+
+    /// Best entries of a mode and difficulty, over all seeds
+    pub fn top(&self, mode: GameMode, difficulty: usize) -> Vec<&LeaderboardEntry> {
+        let mut entries: Vec<&LeaderboardEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.mode == mode && entry.difficulty == difficulty)
+            .collect();
+        entries.sort_by_key(|entry| Reverse(entry.score));
+        entries
+    }
+
+    /// Best entries of a daily challenge, daily runs all use the same difficulty
+    pub fn top_daily(&self, seed: u64) -> Vec<&LeaderboardEntry> {
+        self.top(GameMode::Daily, 0)
+            .into_iter()
+            .filter(|entry| entry.seed == seed)
+            .collect()
+    }
+
+    /// Adds a valid entry, keeping the best `LEADERBOARD_SIZE` entries
+    /// of each mode, difficulty and seed.
+    /// Returns the rank of the entry for its mode and difficulty,
+    /// or for its day in daily mode, if it was kept.
+    pub fn insert(
+        &mut self,
+        entry: LeaderboardEntry,
+        roster: &EnemyRoster,
+    ) -> Result<Option<usize>, InvalidEntry> {
+        entry.validate(roster)?;
+        let (mode, difficulty, seed) = (entry.mode, entry.difficulty, entry.seed);
+        self.entries.push(entry.clone());
+
+        let mut same_key: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| {
+                let other = &self.entries[i];
+                other.mode == mode && other.difficulty == difficulty && other.seed == seed
+            })
+            .collect();
+        same_key.sort_by_key(|&i| Reverse(self.entries[i].score));
+        let mut removed: Vec<usize> = same_key.into_iter().skip(LEADERBOARD_SIZE).collect();
+        removed.sort_unstable_by_key(|&i| Reverse(i));
+        for i in removed {
+            self.entries.remove(i);
+        }
+
+        let ranking = match mode {
+            GameMode::Daily => self.top_daily(seed),
+            _ => self.top(mode, difficulty),
+        };
+        Ok(ranking
+            .iter()
+            .position(|other| **other == entry)
+            .map(|rank| rank + 1))
+    }
+
+    pub fn save(&self) {
+        let content = match ron::ser::to_string_pretty(&self.entries, default()) {
+            Ok(content) => content,
+            Err(error) => {
+                warn!("Could not serialize leaderboard: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = write_file(LEADERBOARD_FILE, &content) {
+            warn!("Could not save leaderboard: {}", error);
+        }
+    }
+}
+
+fn load_leaderboard(mut leaderboard: ResMut<Leaderboard>, roster: Res<EnemyRoster>) {
+    if let Ok(content) = read_file(LEADERBOARD_FILE) {
+        *leaderboard = Leaderboard::parse(&content, &roster);
+        debug!("Loaded {} leaderboard entries", leaderboard.entries.len());
+    }
+}
+
+pub fn record_leaderboard_entry(
+    mut run_finished: EventReader<RunFinished>,
+    config: Res<RunConfig>,
+    replay: Res<RunReplay>,
+    roster: Res<EnemyRoster>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    for RunFinished(summary) in run_finished.read() {
+        let mut replays: Vec<String> = leaderboard
+            .entries
+            .iter()
+            .filter_map(|entry| entry.replay.clone())
+            .chain(replay.file.clone())
+            .collect();
+        let entry = LeaderboardEntry {
+            mode: config.mode,
+            difficulty: config.difficulty,
+            seed: config.seed,
+            score: summary.score,
+            turns: summary.turns,
+            date: today(),
+            build: summary.build_description(),
+            kills: summary.kills.clone(),
+            replay: replay.file.clone(),
+        };
+        match leaderboard.insert(entry, &roster) {
+            Ok(rank) => {
+                leaderboard.last_rank = rank;
+                leaderboard.save();
+            }
+            Err(error) => {
+                leaderboard.last_rank = None;
+                warn!("Run rejected from the leaderboard: {}", error);
+            }
+        }
+        // replays of the runs off the leaderboard can not be watched anymore
+        replays.retain(|file| {
+            !leaderboard
+                .entries
+                .iter()
+                .any(|entry| entry.replay.as_ref() == Some(file))
+        });
+        for file in replays {
+            if let Err(error) = remove_file(&file) {
+                warn!("Could not remove replay {}: {}", file, error);
+            }
+        }
+    }
+}
+
+fn reset_last_rank(mut leaderboard: ResMut<Leaderboard>) {
+    leaderboard.last_rank = None;
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Leaderboard>()
+            .add_systems(Startup, load_leaderboard)
+            .add_systems(OnEnter(GameState::Game), reset_last_rank);
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(OnEnter(state), record_leaderboard_entry.after(save_replay));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: usize, seed: u64, kills: &[(&str, usize)]) -> LeaderboardEntry {
+        LeaderboardEntry {
+            mode: GameMode::Standard,
+            difficulty: 0,
+            seed,
+            score,
+            turns: 10,
+            date: "2026-01-01".to_string(),
+            build: String::new(),
+            kills: kills
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect(),
+            replay: None,
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_entries() {
+        let roster = EnemyRoster::default();
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(
+            leaderboard.insert(entry(100, 1, &[("Queen", 1)]), &roster),
+            Err(InvalidEntry::ScoreMismatch {
+                score: 100,
+                expected: 9
+            })
+        );
+        assert_eq!(
+            leaderboard.insert(entry(1, 1, &[("Dragon", 1)]), &roster),
+            Err(InvalidEntry::UnknownPiece("Dragon".to_string()))
+        );
+        assert!(leaderboard.entries.is_empty());
+
+        // ascension 4 doubles the score
+        let ascended = LeaderboardEntry {
+            difficulty: 4,
+            ..entry(18, 1, &[("Queen", 1)])
+        };
+        assert_eq!(leaderboard.insert(ascended, &roster), Ok(Some(1)));
+
+        // the values of the roster are checked, not the ones of the entry
+        let mut retuned = roster.clone();
+        retuned.enemies[0].value += 1;
+        let name = retuned.enemies[0].name.clone();
+        let value = roster.value(&name).unwrap();
+        assert!(leaderboard
+            .insert(entry(value, 2, &[(name.as_str(), 1)]), &retuned)
+            .is_err());
+        assert_eq!(
+            leaderboard.insert(entry(value + 1, 2, &[(name.as_str(), 1)]), &retuned),
+            Ok(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_ranks_and_keeps_best_per_seed() {
+        let roster = EnemyRoster::default();
+        let mut leaderboard = Leaderboard::default();
+        for count in 1..=LEADERBOARD_SIZE + 2 {
+            leaderboard
+                .insert(entry(count, 1, &[("White Pawn", count)]), &roster)
+                .unwrap();
+        }
+        assert_eq!(leaderboard.entries.len(), LEADERBOARD_SIZE);
+        assert_eq!(
+            leaderboard.top(GameMode::Standard, 0)[0].score,
+            LEADERBOARD_SIZE + 2
+        );
+
+        let rank = leaderboard
+            .insert(entry(14, 2, &[("Rook", 1), ("Queen", 1)]), &roster)
+            .unwrap();
+        assert_eq!(rank, Some(1));
+        // too low for its seed
+        let rank = leaderboard
+            .insert(entry(1, 1, &[("Black Pawn", 1)]), &roster)
+            .unwrap();
+        assert_eq!(rank, None);
+    }
+}
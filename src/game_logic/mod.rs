@@ -3,15 +3,51 @@ use defeat::check_defeat;
 
 use crate::states::game_state::GameState;
 
+pub mod ascension;
+pub mod balance;
+pub mod combat_log;
+pub mod coop;
+pub mod daily;
 pub mod defeat;
+pub mod leaderboard;
+pub mod profile;
+pub mod puzzle;
+pub mod replay;
+pub mod run_config;
+pub mod run_stats;
 pub mod score;
+pub mod tutorial;
+pub mod versus;
+pub mod victory;
 
 pub struct GameLogicPlugin;
 
 impl Plugin for GameLogicPlugin {
     fn build(&self, app: &mut App) {
-        app
-            // .add_systems(OnEnter(GameState::Defeat), defeat::reset_game)
-            .add_systems(Update, check_defeat.run_if(in_state(GameState::Game)));
+        app.add_plugins((
+            balance::BalancePlugin,
+            combat_log::CombatLogPlugin,
+            run_config::RunConfigPlugin,
+            run_stats::RunStatsPlugin,
+            replay::ReplayPlugin,
+            leaderboard::LeaderboardPlugin,
+            profile::ProfilePlugin,
+            ascension::AscensionPlugin,
+            victory::VictoryPlugin,
+            puzzle::PuzzlePlugin,
+            tutorial::TutorialPlugin,
+            versus::VersusPlugin,
+            coop::CoopPlugin,
+        ))
+        // .add_systems(OnEnter(GameState::Defeat), defeat::reset_game)
+        // games with two heroes check their own end, whichever hero is playing
+        .add_systems(
+            Update,
+            check_defeat.run_if(
+                in_state(GameState::Game)
+                    .and(not(versus::is_versus))
+                    .and(not(coop::is_coop)),
+            ),
+        );
     }
 }
@@ -0,0 +1,381 @@
+use std::{collections::BTreeMap, fmt};
+
+use bevy::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::{
+        META_ATTACK_BONUS, META_CURRENCY_SCORE_DIVISOR, META_HEALTH_BONUS, PROFILE_FILE,
+        SHOP_UPGRADES_COUNT_MOVEMENT, SHOP_UPGRADES_COUNT_STATS, VICTORY_META_CURRENCY_BONUS,
+    },
+    pieces::player::{
+        character::{Character, CharacterRoster, DEFAULT_CHARACTER},
+        upgrades::data::{Rarity, Upgrade, UpgradeCatalog},
+    },
+    states::game_state::GameState,
+    utils::storage::{read_file, write_file},
+};
+
+use super::run_stats::{record_run, RunFinished};
+
+/// Something bought with meta-currency, kept between runs
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Unlock {
+    Character(String),
+    /// Locked movement upgrade of the shop, by name
+    MovementUpgrade(String),
+    EpicStatUpgrades,
+    ExtraMovementSlot,
+    ExtraStatsSlot,
+    HealthBonus(usize),
+    AttackBonus(usize),
+}
+
+pub struct UnlockInfo {
+    pub unlock: Unlock,
+    pub name: String,
+    pub cost: usize,
+    pub requires: Option<Unlock>,
+}
+
+pub static UNLOCKS: Lazy<Vec<UnlockInfo>> = Lazy::new(|| {
+    vec![
+        UnlockInfo {
+            unlock: Unlock::EpicStatUpgrades,
+            name: "Epic stat upgrades in shop".to_string(),
+            cost: 30,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::Character("Rook Specialist".to_string()),
+            name: "Rook Specialist character".to_string(),
+            cost: 40,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::Character("Knight Rider".to_string()),
+            name: "Knight Rider character".to_string(),
+            cost: 40,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::Character("Pawn Commander".to_string()),
+            name: "Pawn Commander character".to_string(),
+            cost: 50,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::MovementUpgrade("Archbishop Movement".to_string()),
+            name: "Archbishop movement in shop".to_string(),
+            cost: 50,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::MovementUpgrade("Chancellor Movement".to_string()),
+            name: "Chancellor movement in shop".to_string(),
+            cost: 50,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::ExtraMovementSlot,
+            name: "Extra movement slot in shop".to_string(),
+            cost: 60,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::ExtraStatsSlot,
+            name: "Extra stats slot in shop".to_string(),
+            cost: 60,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::HealthBonus(1),
+            name: format!("+{} max health", META_HEALTH_BONUS),
+            cost: 25,
+            requires: None,
+        },
+        UnlockInfo {
+            unlock: Unlock::HealthBonus(2),
+            name: format!("+{} max health", META_HEALTH_BONUS),
+            cost: 50,
+            requires: Some(Unlock::HealthBonus(1)),
+        },
+        UnlockInfo {
+            unlock: Unlock::AttackBonus(1),
+            name: format!("+{} attack", META_ATTACK_BONUS),
+            cost: 50,
+            requires: None,
+        },
+    ]
+});
+
+#[derive(Debug, PartialEq)]
+pub enum UnlockError {
+    AlreadyUnlocked,
+    MissingRequirement,
+    NotEnoughCurrency,
+}
+
+impl fmt::Display for UnlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnlockError::AlreadyUnlocked => write!(f, "Already unlocked."),
+            UnlockError::MissingRequirement => write!(f, "Requires a previous unlock."),
+            UnlockError::NotEnoughCurrency => write!(f, "Not enough meta-currency."),
+        }
+    }
+}
+
+/// Meta progression kept between runs
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub meta_currency: usize,
+    pub unlocks: Vec<Unlock>,
+    pub selected_character: String,
+    /// Highest ascension level that can be selected
+    #[serde(default)]
+    pub max_ascension: usize,
+    /// Date of the last daily challenge attempt
+    #[serde(default)]
+    pub last_daily: Option<String>,
+    /// Best stars earned on each puzzle, by name
+    #[serde(default)]
+    pub puzzle_stars: BTreeMap<String, usize>,
+    /// Whether the tutorial was played to the end
+    #[serde(default)]
+    pub tutorial_done: bool,
+    /// Meta-currency earned by the last run
+    #[serde(skip)]
+    pub last_earned: usize,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            meta_currency: 0,
+            unlocks: Vec::new(),
+            selected_character: DEFAULT_CHARACTER.to_string(),
+            max_ascension: 0,
+            last_daily: None,
+            puzzle_stars: BTreeMap::new(),
+            tutorial_done: false,
+            last_earned: 0,
+        }
+    }
+}
+
+impl Profile {
+    pub fn has(&self, unlock: &Unlock) -> bool {
+        self.unlocks.contains(unlock)
+    }
+
+    pub fn can_unlock(&self, info: &UnlockInfo) -> Result<(), UnlockError> {
+        if self.has(&info.unlock) {
+            return Err(UnlockError::AlreadyUnlocked);
+        }
+        if info
+            .requires
+            .as_ref()
+            .is_some_and(|unlock| !self.has(unlock))
+        {
+            return Err(UnlockError::MissingRequirement);
+        }
+        if self.meta_currency < info.cost {
+            return Err(UnlockError::NotEnoughCurrency);
+        }
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, info: &UnlockInfo) -> Result<(), UnlockError> {
+        self.can_unlock(info)?;
+        self.meta_currency -= info.cost;
+        self.unlocks.push(info.unlock.clone());
+        Ok(())
+    }
+
+    pub fn played_daily(&self, date: &str) -> bool {
+        self.last_daily.as_deref() == Some(date)
+    }
+
+    pub fn is_character_unlocked(&self, character: &Character) -> bool {
+        character.name == DEFAULT_CHARACTER || self.has(&Unlock::Character(character.name.clone()))
+    }
+
+    /// Character of the next run, the default one if the selection is locked
+    pub fn character<'a>(&self, characters: &'a CharacterRoster) -> &'a Character {
+        characters
+            .get(&self.selected_character)
+            .filter(|character| self.is_character_unlocked(character))
+            .unwrap_or_else(|| characters.default_character())
+    }
+
+    /// Movement upgrades the shop can offer, the locked ones have to be unlocked
+    pub fn movement_pool(&self, catalog: &UpgradeCatalog) -> Vec<Upgrade> {
+        catalog
+            .movement
+            .iter()
+            .filter(|upgrade| {
+                !upgrade.locked || self.has(&Unlock::MovementUpgrade(upgrade.display_name.clone()))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Stat upgrades the shop can offer, the epic ones have to be unlocked
+    pub fn stats_pool(&self, catalog: &UpgradeCatalog) -> Vec<Upgrade> {
+        catalog
+            .stats
+            .iter()
+            .filter(|upgrade| upgrade.rarity != Rarity::Epic || self.has(&Unlock::EpicStatUpgrades))
+            .cloned()
+            .collect()
+    }
+
+    pub fn shop_movement_count(&self) -> usize {
+        SHOP_UPGRADES_COUNT_MOVEMENT + self.has(&Unlock::ExtraMovementSlot) as usize
+    }
+
+    pub fn shop_stats_count(&self) -> usize {
+        SHOP_UPGRADES_COUNT_STATS + self.has(&Unlock::ExtraStatsSlot) as usize
+    }
+
+    pub fn health_bonus(&self) -> f32 {
+        self.unlocks
+            .iter()
+            .filter(|unlock| matches!(unlock, Unlock::HealthBonus(_)))
+            .count() as f32
+            * META_HEALTH_BONUS
+    }
+
+    pub fn attack_bonus(&self) -> f32 {
+        self.unlocks
+            .iter()
+            .filter(|unlock| matches!(unlock, Unlock::AttackBonus(_)))
+            .count() as f32
+            * META_ATTACK_BONUS
+    }
+
+    pub fn save(&self) {
+        let content = match ron::ser::to_string_pretty(self, default()) {
+            Ok(content) => content,
+            Err(error) => {
+                warn!("Could not serialize profile: {}", error);
+                return;
+            }
+        };
+        if let Err(error) = write_file(PROFILE_FILE, &content) {
+            warn!("Could not save profile: {}", error);
+        }
+    }
+}
+
+fn load_profile(mut profile: ResMut<Profile>) {
+    if let Ok(content) = read_file(PROFILE_FILE) {
+        match ron::from_str(&content) {
+            Ok(loaded) => *profile = loaded,
+            Err(error) => warn!("Could not read profile: {}", error),
+        }
+    }
+}
+
+pub fn earn_meta_currency(
+    mut run_finished: EventReader<RunFinished>,
+    mut profile: ResMut<Profile>,
+) {
+    for RunFinished(summary) in run_finished.read() {
+        let mut earned = summary.score / META_CURRENCY_SCORE_DIVISOR;
+        if summary.victory {
+            earned += VICTORY_META_CURRENCY_BONUS;
+        }
+        debug!("Earned {} meta-currency", earned);
+        profile.meta_currency += earned;
+        profile.last_earned = earned;
+        profile.save();
+    }
+}
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Profile>()
+            .add_systems(Startup, load_profile);
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(OnEnter(state), earn_meta_currency.after(record_run));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::{movement_type::MovementType, player::upgrades::data::Effect};
+
+    fn unlock_info(unlock: &Unlock) -> &'static UnlockInfo {
+        UNLOCKS.iter().find(|info| info.unlock == *unlock).unwrap()
+    }
+
+    #[test]
+    fn test_unlocks_gate_shop_pools() {
+        let catalog = UpgradeCatalog::default();
+        let mut profile = Profile {
+            meta_currency: 100,
+            ..default()
+        };
+        // the movement upgrades of the base game are always sold
+        assert!(profile
+            .movement_pool(&catalog)
+            .iter()
+            .any(|upgrade| upgrade.effect == Effect::MovementType(vec![MovementType::Rook])));
+        let sells = |profile: &Profile, name: &str| {
+            profile
+                .movement_pool(&catalog)
+                .iter()
+                .any(|upgrade| upgrade.display_name == name)
+        };
+        assert!(!sells(&profile, "Archbishop Movement"));
+        profile
+            .unlock(unlock_info(&Unlock::MovementUpgrade(
+                "Archbishop Movement".to_string(),
+            )))
+            .unwrap();
+        assert!(sells(&profile, "Archbishop Movement"));
+        assert!(!sells(&profile, "Chancellor Movement"));
+        profile.meta_currency += 50;
+        let has_epic = |profile: &Profile| {
+            profile
+                .stats_pool(&catalog)
+                .iter()
+                .any(|upgrade| upgrade.rarity == Rarity::Epic)
+        };
+        assert!(!has_epic(&profile));
+        assert!(profile
+            .stats_pool(&catalog)
+            .iter()
+            .any(|upgrade| upgrade.rarity == Rarity::Rare));
+        profile
+            .unlock(unlock_info(&Unlock::EpicStatUpgrades))
+            .unwrap();
+        assert!(has_epic(&profile));
+
+        let second_health = unlock_info(&Unlock::HealthBonus(2));
+        assert_eq!(
+            profile.unlock(second_health),
+            Err(UnlockError::MissingRequirement)
+        );
+        let rook_specialist = unlock_info(&Unlock::Character("Rook Specialist".to_string()));
+        profile.unlock(rook_specialist).unwrap();
+        assert_eq!(profile.meta_currency, 30);
+        assert_eq!(
+            profile.unlock(rook_specialist),
+            Err(UnlockError::AlreadyUnlocked)
+        );
+
+        let characters = CharacterRoster::default();
+        profile.selected_character = "Rook Specialist".to_string();
+        assert_eq!(profile.character(&characters).name, "Rook Specialist");
+        profile.selected_character = "Knight Rider".to_string();
+        assert_eq!(profile.character(&characters).name, DEFAULT_CHARACTER);
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::{
+    board::position::BoardPosition,
+    pieces::{
+        common::{Piece, Team},
+        enemies::{roster::EnemyRoster, PieceInfo},
+        health::DeathAnimation,
+        movement_type::MovementType,
+    },
+    states::{game_state::GameState, turn_state::TurnState},
+};
+
+use super::{balance::Balance, defeat::check_defeat, profile::Profile, run_config::RunConfig};
+
+/// Scenario files, embedded so puzzles also work on the web
+const PUZZLE_FILES: [&str; 3] = [
+    include_str!("../../assets/puzzles/knight_chain.ron"),
+    include_str!("../../assets/puzzles/bishop_pierce.ron"),
+    include_str!("../../assets/puzzles/pawn_convert.ron"),
+];
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PuzzleGoal {
+    /// Kill every enemy within this many player turns
+    KillAll(usize),
+    /// Stay alive for this many enemy turns
+    Survive(usize),
+}
+
+impl fmt::Display for PuzzleGoal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleGoal::KillAll(turns) => write!(f, "Kill all enemies in {} turns", turns),
+            PuzzleGoal::Survive(turns) => write!(f, "Survive {} enemy turns", turns),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct PuzzlePlayer {
+    pub position: (i32, i32),
+    pub health: f32,
+    pub attack: f32,
+    #[serde(default)]
+    pub block: usize,
+    /// One movement upgrade per entry, repeat a type to level it up
+    pub movement_types: Vec<MovementType>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct PuzzleEnemy {
+    pub movement_type: MovementType,
+    pub position: (i32, i32),
+    pub health: f32,
+    /// Damage of the piece, the roster damage if not set
+    #[serde(default)]
+    pub damage: Option<f32>,
+}
+
+impl PuzzleEnemy {
+    /// Roster piece with the stats of the scenario
+    pub fn piece_info(&self, roster: &EnemyRoster, balance: &Balance) -> Option<PieceInfo> {
+        let mut piece_info = roster
+            .pieces(balance)
+            .into_iter()
+            .find(|piece| piece.movement_type == self.movement_type)?;
+        piece_info.health = self.health;
+        if let Some(damage) = self.damage {
+            piece_info.damage = damage;
+        }
+        Some(piece_info)
+    }
+}
+
+/// Hand-authored scenario, played without enemy spawns
+#[derive(Deserialize, Clone, Debug)]
+pub struct Puzzle {
+    pub name: String,
+    pub description: String,
+    pub player: PuzzlePlayer,
+    pub enemies: Vec<PuzzleEnemy>,
+    pub goal: PuzzleGoal,
+    /// Player turns needed for the best grade
+    pub par: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InvalidPuzzle {
+    Parse(String),
+    OutOfBoard((i32, i32)),
+    Overlap((i32, i32)),
+    UnknownEnemy(MovementType),
+    NoEnemies,
+}
+
+impl fmt::Display for InvalidPuzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidPuzzle::Parse(error) => write!(f, "could not parse puzzle: {}", error),
+            InvalidPuzzle::OutOfBoard(position) => {
+                write!(f, "position {:?} is outside the board", position)
+            }
+            InvalidPuzzle::Overlap(position) => {
+                write!(f, "two pieces are placed on {:?}", position)
+            }
+            InvalidPuzzle::UnknownEnemy(movement_type) => {
+                write!(f, "no enemy moves like a {:?}", movement_type)
+            }
+            InvalidPuzzle::NoEnemies => write!(f, "puzzle has no enemies"),
+        }
+    }
+}
+
+impl Puzzle {
+    pub fn parse(content: &str) -> Result<Self, InvalidPuzzle> {
+        let puzzle: Puzzle =
+            ron::from_str(content).map_err(|error| InvalidPuzzle::Parse(error.to_string()))?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    fn validate(&self) -> Result<(), InvalidPuzzle> {
+        if self.enemies.is_empty() {
+            return Err(InvalidPuzzle::NoEnemies);
+        }
+        let mut occupied = Vec::new();
+        let positions = std::iter::once(self.player.position)
+            .chain(self.enemies.iter().map(|enemy| enemy.position));
+        for position in positions {
+            if BoardPosition::new(position.0, position.1).is_none() {
+                return Err(InvalidPuzzle::OutOfBoard(position));
+            }
+            if occupied.contains(&position) {
+                return Err(InvalidPuzzle::Overlap(position));
+            }
+            occupied.push(position);
+        }
+        // against the embedded roster, the file reloaded later has the same pieces
+        let (roster, balance) = (EnemyRoster::default(), Balance::default());
+        for enemy in self.enemies.iter() {
+            if enemy.piece_info(&roster, &balance).is_none() {
+                return Err(InvalidPuzzle::UnknownEnemy(enemy.movement_type.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Stars earned for solving the puzzle in `turns_used` player turns
+    pub fn stars(&self, turns_used: usize) -> usize {
+        if turns_used <= self.par {
+            3
+        } else if turns_used <= self.par + 1 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+pub static PUZZLES: Lazy<Vec<Puzzle>> = Lazy::new(|| {
+    PUZZLE_FILES
+        .iter()
+        .filter_map(|content| match Puzzle::parse(content) {
+            Ok(puzzle) => Some(puzzle),
+            Err(error) => {
+                warn!("Skipping puzzle: {}", error);
+                None
+            }
+        })
+        .collect()
+});
+
+/// Turns played in the current puzzle
+#[derive(Resource, Default)]
+pub struct PuzzleProgress {
+    pub player_turns: usize,
+    pub enemy_turns: usize,
+}
+
+pub fn is_puzzle(config: Res<RunConfig>) -> bool {
+    config.puzzle().is_some()
+}
+
+fn count_player_turn(mut progress: ResMut<PuzzleProgress>) {
+    progress.player_turns += 1;
+}
+
+fn count_enemy_turn(mut progress: ResMut<PuzzleProgress>) {
+    progress.enemy_turns += 1;
+}
+
+fn check_puzzle_goal(
+    config: Res<RunConfig>,
+    progress: Res<PuzzleProgress>,
+    turn_state: Res<State<TurnState>>,
+    pieces: Query<&Team, (With<Piece>, Without<DeathAnimation>)>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(puzzle) = config.puzzle() else {
+        return;
+    };
+    let board_cleared = !pieces.iter().any(|team| *team == Team::Enemy);
+    let solved = match puzzle.goal {
+        PuzzleGoal::KillAll(_) => board_cleared,
+        PuzzleGoal::Survive(turns) => board_cleared || progress.enemy_turns >= turns,
+    };
+    // out of turns once the player gets a turn they are not allowed to play
+    let failed = match puzzle.goal {
+        PuzzleGoal::KillAll(turns) => {
+            *turn_state.get() == TurnState::PlayerInput && progress.player_turns >= turns
+        }
+        PuzzleGoal::Survive(_) => false,
+    };
+    if solved {
+        game_state.set(GameState::Victory);
+    } else if failed {
+        game_state.set(GameState::Defeat);
+    }
+}
+
+fn record_puzzle_stars(
+    config: Res<RunConfig>,
+    progress: Res<PuzzleProgress>,
+    mut profile: ResMut<Profile>,
+) {
+    if let Some(puzzle) = config.puzzle() {
+        let stars = puzzle.stars(progress.player_turns);
+        let best = profile.puzzle_stars.entry(puzzle.name.clone()).or_default();
+        if stars > *best {
+            *best = stars;
+            profile.save();
+        }
+    }
+}
+
+fn reset_puzzle_progress(mut progress: ResMut<PuzzleProgress>) {
+    *progress = PuzzleProgress::default();
+}
+
+pub struct PuzzlePlugin;
+
+impl Plugin for PuzzlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PuzzleProgress>()
+            .add_systems(OnEnter(GameState::Game), reset_puzzle_progress)
+            .add_systems(
+                OnEnter(TurnState::PlayerAnimation),
+                count_player_turn.run_if(in_state(GameState::Game).and(is_puzzle)),
+            )
+            .add_systems(
+                OnEnter(TurnState::EnemySpawn),
+                count_enemy_turn.run_if(in_state(GameState::Game).and(is_puzzle)),
+            )
+            .add_systems(
+                Update,
+                // a defeat in the same frame wins over the victory
+                check_puzzle_goal
+                    .before(check_defeat)
+                    .run_if(in_state(GameState::Game).and(is_puzzle)),
+            )
+            .add_systems(
+                OnEnter(GameState::Victory),
+                record_puzzle_stars.run_if(is_puzzle),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_files_are_valid() {
+        for content in PUZZLE_FILES {
+            let puzzle = Puzzle::parse(content).unwrap();
+            assert_eq!(puzzle.stars(puzzle.par), 3);
+        }
+
+        let overlapping = PUZZLE_FILES[0].replace("(2, 3)", "(1, 1)");
+        assert_eq!(
+            Puzzle::parse(&overlapping).unwrap_err(),
+            InvalidPuzzle::Overlap((1, 1))
+        );
+    }
+}
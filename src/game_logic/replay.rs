@@ -0,0 +1,240 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::REPLAY_FILE_PREFIX,
+    input::{
+        click_tile::click_tile_update_player_position, keyboard,
+        touch::tap_tile_update_player_position,
+    },
+    network::{
+        is_online, protocol::PlayerAction, ActionEventWriters, LocalActionEvents, NetSession,
+    },
+    pieces::player::{character::CharacterRoster, spawn::Player},
+    states::{
+        game_state::GameState,
+        pause_state::GamePauseState,
+        turn_state::{TurnInfo, TurnState},
+    },
+    ui::hotbar::press_hotbar_buttons,
+    utils::{
+        date::today,
+        storage::{read_file, write_file},
+    },
+};
+
+use super::{
+    daily::Mutator,
+    profile::Profile,
+    run_config::{GameMode, RunConfig},
+    run_stats::{record_run, RunFinished},
+    victory::VictoryCondition,
+};
+
+/// Everything needed to play a finished run again
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub mode: GameMode,
+    pub difficulty: usize,
+    pub seed: u64,
+    pub character: String,
+    pub mutators: Vec<Mutator>,
+    #[serde(default)]
+    pub victory: VictoryCondition,
+    /// Unlocks and bonuses the run was played with
+    pub profile: Profile,
+    /// Actions of the heroes, with the turn they were taken on
+    pub actions: Vec<(usize, PlayerAction)>,
+}
+
+impl Replay {
+    pub fn load(file: &str) -> Result<Self, String> {
+        let content = read_file(file).map_err(|error| error.to_string())?;
+        ron::from_str(&content).map_err(|error| error.to_string())
+    }
+
+    /// Sets up the next run to play this replay back
+    pub fn configure(&self, config: &mut RunConfig) {
+        *config = RunConfig {
+            mode: self.mode,
+            difficulty: self.difficulty,
+            fixed_seed: Some(self.seed),
+            character: Some(self.character.clone()),
+            mutators: self.mutators.clone(),
+            victory: self.victory,
+            shared_profile: Some(self.profile.clone()),
+            replay: Some(self.clone()),
+            ..default()
+        };
+    }
+}
+
+/// Inputs of the current run, saved to a replay file when it ends
+#[derive(Resource, Default)]
+pub struct RunReplay {
+    actions: Vec<(usize, PlayerAction)>,
+    /// Online and co-op runs miss the actions of the other hero, they have no replay
+    partial: bool,
+    /// Actions of the watched replay left to play
+    playback: VecDeque<(usize, PlayerAction)>,
+    /// Replay file of the last finished run
+    pub file: Option<String>,
+}
+
+/// Records the actions of the heroes, read from the events the input sent
+fn record_actions(
+    mut replay: ResMut<RunReplay>,
+    player: Query<Entity, With<Player>>,
+    turn_info: Res<TurnInfo>,
+    mut local_actions: LocalActionEvents,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    for action in local_actions.read(player) {
+        replay.actions.push((turn_info.number, action));
+    }
+}
+
+/// Saves the replay of the finished run
+pub fn save_replay(
+    mut run_finished: EventReader<RunFinished>,
+    mut replay: ResMut<RunReplay>,
+    config: Res<RunConfig>,
+    profile: Res<Profile>,
+    characters: Res<CharacterRoster>,
+) {
+    for RunFinished(summary) in run_finished.read() {
+        replay.file = None;
+        if replay.partial {
+            continue;
+        }
+        let content = Replay {
+            mode: config.mode,
+            difficulty: config.difficulty,
+            seed: config.seed,
+            character: config.character(&profile, &characters).name.clone(),
+            mutators: config.mutators.clone(),
+            victory: config.victory,
+            profile: config.run_profile(&profile).clone(),
+            actions: replay.actions.clone(),
+        };
+        let content = match ron::ser::to_string_pretty(&content, default()) {
+            Ok(content) => content,
+            Err(error) => {
+                warn!("Could not serialize replay: {}", error);
+                continue;
+            }
+        };
+        let file = format!(
+            "{}{}_{}_{}.ron",
+            REPLAY_FILE_PREFIX,
+            today(),
+            config.seed,
+            summary.score
+        );
+        match write_file(&file, &content) {
+            Ok(()) => replay.file = Some(file),
+            Err(error) => warn!("Could not save replay: {}", error),
+        }
+    }
+}
+
+/// Plays the next action of the watched replay once the run reaches its turn
+fn play_replay(
+    mut replay: ResMut<RunReplay>,
+    turn_info: Res<TurnInfo>,
+    mut action_writers: ActionEventWriters,
+) {
+    let Some(&(turn, action)) = replay.playback.front() else {
+        return;
+    };
+    if turn > turn_info.number {
+        return;
+    }
+    replay.playback.pop_front();
+    debug!("Playing back {:?}", action);
+    action_writers.play(action);
+}
+
+fn reset_replay(mut replay: ResMut<RunReplay>, session: Res<NetSession>, config: Res<RunConfig>) {
+    *replay = RunReplay {
+        partial: session.is_connected() || config.coop.is_some(),
+        playback: config
+            .replay
+            .as_ref()
+            .map(|watched| watched.actions.iter().copied().collect())
+            .unwrap_or_default(),
+        ..default()
+    };
+}
+
+fn is_watching_replay(config: Res<RunConfig>) -> bool {
+    config.replay.is_some()
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunReplay>()
+            .add_systems(OnEnter(GameState::Game), reset_replay)
+            .add_systems(
+                Update,
+                // reads the events of the input in the frame they are sent
+                record_actions
+                    .after(click_tile_update_player_position)
+                    .after(tap_tile_update_player_position)
+                    .after(keyboard::use_consumable)
+                    .after(press_hotbar_buttons)
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(not(is_online)),
+            )
+            .add_systems(
+                Update,
+                play_replay
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(in_state(GamePauseState::Playing))
+                    .run_if(is_watching_replay),
+            );
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(OnEnter(state), save_replay.after(record_run));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::shop::ShopAction;
+
+    #[test]
+    fn test_replay_round_trip() {
+        let replay = Replay {
+            mode: GameMode::Daily,
+            difficulty: 2,
+            seed: 42,
+            character: "Knight".to_string(),
+            mutators: vec![Mutator::NoShopRefresh],
+            victory: VictoryCondition::Endless,
+            profile: Profile::default(),
+            actions: vec![
+                (1, PlayerAction::Move { x: 2, y: 3 }),
+                (1, PlayerAction::Shop(ShopAction::ToggleLock(0))),
+                (2, PlayerAction::Attack),
+            ],
+        };
+        let content = ron::ser::to_string_pretty(&replay, default()).unwrap();
+        assert_eq!(ron::from_str::<Replay>(&content).unwrap(), replay);
+
+        let mut config = RunConfig::default();
+        replay.configure(&mut config);
+        assert_eq!(config.fixed_seed, Some(42));
+        assert_eq!(config.victory, VictoryCondition::Endless);
+        assert_eq!(config.replay, Some(replay));
+    }
+}
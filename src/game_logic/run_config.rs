@@ -0,0 +1,130 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pieces::player::character::{Character, CharacterRoster},
+    states::game_state::GameState,
+    utils::rng::{random_seed, GameRng},
+};
+
+use super::{
+    ascension::Ascension,
+    coop::GoldSharing,
+    daily::Mutator,
+    profile::Profile,
+    puzzle::{Puzzle, PuzzlePlayer, PUZZLES},
+    replay::Replay,
+    tutorial::TUTORIAL,
+    victory::VictoryCondition,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Daily,
+    Endless,
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Standard => write!(f, "Standard"),
+            GameMode::Daily => write!(f, "Daily"),
+            GameMode::Endless => write!(f, "Endless"),
+        }
+    }
+}
+
+/// How the next run is played
+#[derive(Resource, Default)]
+pub struct RunConfig {
+    pub mode: GameMode,
+    /// Ascension level of the run
+    pub difficulty: usize,
+    /// Seed to use instead of a random one
+    pub fixed_seed: Option<u64>,
+    /// Seed of the current run
+    pub seed: u64,
+    /// Character to play instead of the one selected in the profile
+    pub character: Option<String>,
+    pub mutators: Vec<Mutator>,
+    pub victory: VictoryCondition,
+    /// Index of the puzzle played instead of a run
+    pub puzzle: Option<usize>,
+    /// Plays the tutorial instead of a run
+    pub tutorial: bool,
+    /// Two heroes fight each other on one machine instead of a run
+    pub versus: bool,
+    /// Two heroes play the run together, with the way they share gold
+    pub coop: Option<GoldSharing>,
+    /// Profile of the online host, so both players unlock the same upgrades and bonuses
+    pub shared_profile: Option<Profile>,
+    /// Replay watched instead of playing, its actions replace the input
+    pub replay: Option<Replay>,
+}
+
+impl RunConfig {
+    pub fn ascension(&self) -> Ascension {
+        Ascension(self.difficulty)
+    }
+
+    pub fn has_mutator(&self, mutator: Mutator) -> bool {
+        self.mutators.contains(&mutator)
+    }
+
+    pub fn puzzle(&self) -> Option<&'static Puzzle> {
+        self.puzzle.and_then(|index| PUZZLES.get(index))
+    }
+
+    /// Puzzles and the tutorial place their own pieces
+    pub fn is_scripted(&self) -> bool {
+        self.puzzle.is_some() || self.tutorial
+    }
+
+    /// Player setup of the scripted scenario being played
+    pub fn scenario_player(&self) -> Option<&'static PuzzlePlayer> {
+        if self.tutorial {
+            return Some(&TUTORIAL.player);
+        }
+        self.puzzle().map(|puzzle| &puzzle.player)
+    }
+
+    /// Profile the unlocks and bonuses of the run come from
+    pub fn run_profile<'a>(&'a self, profile: &'a Profile) -> &'a Profile {
+        self.shared_profile.as_ref().unwrap_or(profile)
+    }
+
+    pub fn character<'a>(
+        &self,
+        profile: &Profile,
+        characters: &'a CharacterRoster,
+    ) -> &'a Character {
+        self.character
+            .as_deref()
+            .and_then(|name| characters.get(name))
+            .unwrap_or_else(|| self.run_profile(profile).character(characters))
+    }
+}
+
+/// Picks the seed of the run and seeds the game rng with it
+pub fn start_run(mut config: ResMut<RunConfig>, mut rng: ResMut<GameRng>) {
+    config.seed = config.fixed_seed.unwrap_or_else(random_seed);
+    debug!(
+        "Starting {} run, difficulty {}, seed {}",
+        config.mode, config.difficulty, config.seed
+    );
+    rng.reseed(config.seed);
+}
+
+pub struct RunConfigPlugin;
+
+impl Plugin for RunConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunConfig>()
+            .init_resource::<GameRng>()
+            .add_systems(OnEnter(GameState::Game), start_run);
+    }
+}
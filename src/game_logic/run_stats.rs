@@ -0,0 +1,316 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::RUN_HISTORY_FILE,
+    pieces::{
+        common::Team,
+        health::{spawn_health_change_text, Health, PieceDeathEvent},
+        player::{
+            gold::Gold,
+            spawn::Player,
+            upgrades::{data::Upgrades, unique_upgrades::SideEffect},
+        },
+    },
+    states::{game_state::GameState, turn_state::TurnInfo},
+    utils::storage::{append_line, read_file},
+};
+
+use super::{run_config::RunConfig, score::GameScore};
+
+/// Statistics of the current run
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub kills: BTreeMap<String, usize>,
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+    pub biggest_hit: f32,
+    pub gold_earned: usize,
+    pub gold_spent: usize,
+    pub side_effects: BTreeMap<String, usize>,
+    pub build: BTreeMap<String, usize>,
+    last_gold: Option<usize>,
+    recorded: bool,
+}
+
+/// Summary of a finished run, one line of the run history file
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RunSummary {
+    pub score: usize,
+    pub turns: usize,
+    pub kills: BTreeMap<String, usize>,
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+    pub biggest_hit: f32,
+    pub gold_earned: usize,
+    pub gold_spent: usize,
+    pub side_effects: BTreeMap<String, usize>,
+    pub build: BTreeMap<String, usize>,
+    /// Whether the run ended with its victory condition met
+    #[serde(default)]
+    pub victory: bool,
+}
+
+impl RunSummary {
+    pub fn total_kills(&self) -> usize {
+        self.kills.values().sum()
+    }
+
+    /// The movement type the run invested the most in
+    pub fn build_name(&self) -> String {
+        self.build
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(movement_type, _)| movement_type.clone())
+            .unwrap_or_else(|| "None".to_string())
+    }
+
+    /// Movement types of the build, most upgraded first
+    pub fn build_description(&self) -> String {
+        let mut build: Vec<(&String, &usize)> = self.build.iter().collect();
+        build.sort_by_key(|(_, count)| Reverse(**count));
+        build
+            .iter()
+            .map(|(movement_type, count)| format!("{} x{}", movement_type, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Sent once when a run ends, with its summary
+#[derive(Event)]
+pub struct RunFinished(pub RunSummary);
+
+#[derive(Resource, Default)]
+pub struct RunHistory {
+    pub runs: Vec<RunSummary>,
+}
+
+impl RunHistory {
+    /// Parses the run history file, skipping lines that can not be read
+    pub fn parse(content: &str) -> Self {
+        let runs = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match ron::from_str(line) {
+                Ok(run) => Some(run),
+                Err(error) => {
+                    warn!("Skipping invalid run history line: {}", error);
+                    None
+                }
+            })
+            .collect();
+        Self { runs }
+    }
+
+    pub fn best_score_per_build(&self) -> Vec<(String, usize)> {
+        let mut best: BTreeMap<String, usize> = BTreeMap::new();
+        for run in self.runs.iter() {
+            let score = best.entry(run.build_name()).or_default();
+            *score = (*score).max(run.score);
+        }
+        let mut best: Vec<(String, usize)> = best.into_iter().collect();
+        best.sort_by_key(|(_, score)| Reverse(*score));
+        best
+    }
+
+    pub fn average_turns(&self) -> f32 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+        self.runs.iter().map(|run| run.turns).sum::<usize>() as f32 / self.runs.len() as f32
+    }
+
+    pub fn best_score(&self) -> usize {
+        self.runs.iter().map(|run| run.score).max().unwrap_or(0)
+    }
+}
+
+fn load_run_history(mut history: ResMut<RunHistory>) {
+    // a missing file only means no run was played yet
+    if let Ok(content) = read_file(RUN_HISTORY_FILE) {
+        *history = RunHistory::parse(&content);
+        debug!("Loaded {} runs from history", history.runs.len());
+    }
+}
+
+fn track_damage(
+    health_query: Query<(&Health, &Team), Changed<Health>>,
+    mut stats: ResMut<RunStats>,
+) {
+    for (health, team) in health_query.iter() {
+        for change in health.changes.iter().filter(|change| **change < 0.0) {
+            let damage = -change;
+            match team {
+                Team::Enemy => {
+                    stats.damage_dealt += damage;
+                    stats.biggest_hit = stats.biggest_hit.max(damage);
+                }
+                Team::Player => stats.damage_taken += damage,
+            }
+        }
+    }
+}
+
+fn track_kills(
+    mut death_events: EventReader<PieceDeathEvent>,
+    pieces: Query<(&Name, &Team)>,
+    mut stats: ResMut<RunStats>,
+) {
+    for event in death_events.read() {
+        if let Ok((name, Team::Enemy)) = pieces.get(event.entity) {
+            *stats.kills.entry(name.to_string()).or_default() += 1;
+        }
+    }
+}
+
+fn track_gold(gold: Res<Gold>, mut stats: ResMut<RunStats>) {
+    if let Some(last_gold) = stats.last_gold {
+        if gold.amount > last_gold {
+            stats.gold_earned += gold.amount - last_gold;
+        } else {
+            stats.gold_spent += last_gold - gold.amount;
+        }
+    }
+    stats.last_gold = Some(gold.amount);
+}
+
+fn track_side_effects(mut side_effects: EventReader<SideEffect>, mut stats: ResMut<RunStats>) {
+    for side_effect in side_effects.read() {
+        let name = match side_effect {
+            SideEffect::AttackRandomTarget { .. } => "Random attack",
+            SideEffect::Chain { .. } => "Chain",
+            SideEffect::Pierce { .. } => "Pierce",
+            SideEffect::ConvertPiece { .. } => "Convert",
+            SideEffect::Block { .. } => "Block",
+            SideEffect::Nothing => continue,
+        };
+        *stats.side_effects.entry(name.to_string()).or_default() += 1;
+    }
+}
+
+/// Keeps the build around, the player is despawned before the defeat screen
+fn track_build(
+    player: Query<&Upgrades, (With<Player>, Changed<Upgrades>)>,
+    mut stats: ResMut<RunStats>,
+) {
+    if let Ok(upgrades) = player.get_single() {
+        stats.build = upgrades
+            .get_movement_types_count()
+            .into_iter()
+            .map(|(movement_type, count)| (format!("{:?}", movement_type), count))
+            .collect();
+    }
+}
+
+/// Adds the finished run to the history, once per run, on defeat or victory
+pub fn record_run(
+    mut stats: ResMut<RunStats>,
+    score: Res<GameScore>,
+    config: Res<RunConfig>,
+    turn_info: Res<TurnInfo>,
+    state: Res<State<GameState>>,
+    mut history: ResMut<RunHistory>,
+    mut run_finished: EventWriter<RunFinished>,
+) {
+    // puzzles, the tutorial, versus matches and watched replays are not runs
+    if stats.recorded || config.is_scripted() || config.versus || config.replay.is_some() {
+        return;
+    }
+    stats.recorded = true;
+    let summary = RunSummary {
+        score: config.ascension().score(score.0),
+        turns: turn_info.number,
+        kills: stats.kills.clone(),
+        damage_dealt: stats.damage_dealt,
+        damage_taken: stats.damage_taken,
+        biggest_hit: stats.biggest_hit,
+        gold_earned: stats.gold_earned,
+        gold_spent: stats.gold_spent,
+        side_effects: stats.side_effects.clone(),
+        build: stats.build.clone(),
+        victory: *state.get() == GameState::Victory,
+    };
+    match ron::to_string(&summary) {
+        Ok(line) => {
+            if let Err(error) = append_line(RUN_HISTORY_FILE, &line) {
+                warn!("Could not save run history: {}", error);
+            }
+        }
+        Err(error) => warn!("Could not serialize run summary: {}", error),
+    }
+    run_finished.send(RunFinished(summary.clone()));
+    history.runs.push(summary);
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    debug!("Resetting run stats");
+    *stats = RunStats::default();
+}
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .init_resource::<RunHistory>()
+            .add_event::<RunFinished>()
+            .add_systems(Startup, load_run_history)
+            .add_systems(
+                Update,
+                (
+                    track_damage.before(spawn_health_change_text),
+                    track_kills,
+                    track_gold,
+                    track_side_effects,
+                    track_build,
+                )
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(OnEnter(GameState::Game), reset_run_stats)
+            .add_systems(OnEnter(GameState::Defeat), record_run)
+            .add_systems(OnEnter(GameState::Victory), record_run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(score: usize, turns: usize, build: &[(&str, usize)]) -> RunSummary {
+        RunSummary {
+            score,
+            turns,
+            build: build
+                .iter()
+                .map(|(movement_type, count)| (movement_type.to_string(), *count))
+                .collect(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_history_round_trip_and_aggregates() {
+        let runs = [
+            run(10, 4, &[("Rook", 2), ("Knight", 1)]),
+            run(30, 8, &[("Rook", 1)]),
+            run(20, 6, &[("Bishop", 3)]),
+        ];
+        let content = runs
+            .iter()
+            .map(|run| ron::to_string(run).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let history = RunHistory::parse(&format!("{}\nnot a run\n", content));
+
+        assert_eq!(history.runs, runs.to_vec());
+        assert_eq!(history.average_turns(), 6.0);
+        assert_eq!(history.best_score(), 30);
+        assert_eq!(
+            history.best_score_per_build(),
+            vec![("Rook".to_string(), 30), ("Bishop".to_string(), 20)]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use super::combat_log::{CombatLogEvent, LogCategory};
 use crate::{
     pieces::{common::Team, health::PieceDeathEvent, player::experience::PieceValue},
     states::game_state::GameState,
@@ -18,6 +19,7 @@ fn score_system(
     mut death_events: EventReader<PieceDeathEvent>,
     mut score: ResMut<GameScore>,
     value_query: Query<(&PieceValue, &Team)>,
+    mut log_writer: EventWriter<CombatLogEvent>,
 ) {
     for event in death_events.read() {
         let (value, team) = value_query.get(event.entity).unwrap();
@@ -26,6 +28,10 @@ fn score_system(
         }
         debug!("Adding to score: {}", value.value);
         score.0 += value.value;
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::Reward,
+            format!("+{} score", value.value),
+        ));
     }
 }
 
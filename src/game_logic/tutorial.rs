@@ -0,0 +1,362 @@
+use bevy::prelude::*;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::{
+    board::{highlight::HighlightCache, position::BoardPosition},
+    globals::UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER,
+    graphics::spritesheet::SpriteSheetAtlas,
+    pieces::{
+        attack::AttackPieceEvent,
+        enemies::{
+            roster::EnemyRoster,
+            spawn::{spawn_enemy, AIControlled},
+        },
+        movement::MovePieceEvent,
+        movement_type::MovementType,
+        player::{
+            experience::PlayerLevelUpEvent,
+            gold::Gold,
+            spawn::Player,
+            upgrades::{
+                data::{UpgradeCatalog, Upgrades},
+                unique_upgrades::SideEffect,
+            },
+        },
+    },
+    states::{game_state::GameState, turn_state::TurnState},
+    ui::{
+        messages::{ClearMessages, MessageEvent},
+        shop::{ApplyUpgrades, ShopState},
+    },
+};
+
+use super::{
+    balance::Balance,
+    profile::Profile,
+    puzzle::{InvalidPuzzle, PuzzleEnemy, PuzzlePlayer},
+    run_config::RunConfig,
+};
+
+/// Embedded so the tutorial also works on the web
+const TUTORIAL_FILE: &str = include_str!("../../assets/tutorial.ron");
+
+/// Action the player has to perform to finish a step
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum TutorialTrigger {
+    Move,
+    /// Attack without moving
+    Attack,
+    AttackAfterMove,
+    /// Attack using this movement type
+    AttackWith(MovementType),
+    /// King attack from the tile the player moved away from
+    KingDoubleAttack,
+    OpenShop,
+    BuyUpgrade,
+    LevelUp,
+    Chain,
+    Pierce,
+    Convert,
+    Block,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TutorialStep {
+    /// `{unique_unlock}` and `{slots}` are replaced by the game rules
+    pub message: String,
+    pub trigger: TutorialTrigger,
+    /// Replace the enemies on the board when not empty
+    #[serde(default)]
+    pub enemies: Vec<PuzzleEnemy>,
+    #[serde(default)]
+    pub player_position: Option<(i32, i32)>,
+    /// Movement upgrades given at the start of the step
+    #[serde(default)]
+    pub movement_types: Vec<MovementType>,
+    #[serde(default)]
+    pub gold: usize,
+}
+
+impl TutorialStep {
+    pub fn text(&self, balance: &Balance) -> String {
+        let slots = balance
+            .movement_type_limits
+            .iter()
+            .map(|(upgrades, limit)| format!("{} types from {} upgrades", limit, upgrades))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.message
+            .replace(
+                "{unique_unlock}",
+                &UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER.to_string(),
+            )
+            .replace("{slots}", &slots)
+    }
+}
+
+/// Scripted scenario teaching the rules one step at a time
+#[derive(Deserialize, Clone, Debug)]
+pub struct Tutorial {
+    pub player: PuzzlePlayer,
+    pub steps: Vec<TutorialStep>,
+}
+
+impl Tutorial {
+    pub fn parse(content: &str) -> Result<Self, InvalidPuzzle> {
+        let tutorial: Tutorial =
+            ron::from_str(content).map_err(|error| InvalidPuzzle::Parse(error.to_string()))?;
+        tutorial.validate()?;
+        Ok(tutorial)
+    }
+
+    fn validate(&self) -> Result<(), InvalidPuzzle> {
+        let positions =
+            std::iter::once(self.player.position).chain(self.steps.iter().flat_map(|step| {
+                step.player_position
+                    .into_iter()
+                    .chain(step.enemies.iter().map(|enemy| enemy.position))
+            }));
+        for position in positions {
+            if BoardPosition::new(position.0, position.1).is_none() {
+                return Err(InvalidPuzzle::OutOfBoard(position));
+            }
+        }
+        // against the embedded roster, the file reloaded later has the same pieces
+        let (roster, balance) = (EnemyRoster::default(), Balance::default());
+        for step in self.steps.iter() {
+            let mut occupied: Vec<(i32, i32)> = step.player_position.into_iter().collect();
+            for enemy in step.enemies.iter() {
+                if occupied.contains(&enemy.position) {
+                    return Err(InvalidPuzzle::Overlap(enemy.position));
+                }
+                occupied.push(enemy.position);
+                if enemy.piece_info(&roster, &balance).is_none() {
+                    return Err(InvalidPuzzle::UnknownEnemy(enemy.movement_type.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub static TUTORIAL: Lazy<Tutorial> =
+    Lazy::new(|| Tutorial::parse(TUTORIAL_FILE).expect("Tutorial file is valid"));
+
+#[derive(Resource, Default)]
+pub struct TutorialProgress {
+    pub step: usize,
+    step_started: bool,
+    moved_this_turn: bool,
+}
+
+pub fn is_tutorial(config: Res<RunConfig>) -> bool {
+    config.tutorial
+}
+
+/// Sets up the board of the current step, or ends the tutorial after the last one
+fn start_tutorial_step(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    mut progress: ResMut<TutorialProgress>,
+    mut player: Query<(&mut BoardPosition, &mut Transform, &mut Upgrades), With<Player>>,
+    enemies: Query<Entity, With<AIControlled>>,
+    mut gold: ResMut<Gold>,
+    mut highlight_cache: ResMut<HighlightCache>,
+    mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    mut clear_messages_writer: EventWriter<ClearMessages>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    mut profile: ResMut<Profile>,
+    mut game_state: ResMut<NextState<GameState>>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    catalog: Res<UpgradeCatalog>,
+) {
+    if progress.step_started {
+        return;
+    }
+    clear_messages_writer.send(ClearMessages);
+    let Some(step) = TUTORIAL.steps.get(progress.step) else {
+        debug!("Tutorial done");
+        profile.tutorial_done = true;
+        profile.save();
+        message_event_writer.send(MessageEvent {
+            message: "Tutorial complete, good luck in your runs!".to_string(),
+            timer: Some(Timer::from_seconds(5.0, TimerMode::Once)),
+        });
+        game_state.set(GameState::MainMenu);
+        return;
+    };
+    debug!("Starting tutorial step {}", progress.step + 1);
+    progress.step_started = true;
+    message_event_writer.send(MessageEvent {
+        message: format!(
+            "{}/{}: {}",
+            progress.step + 1,
+            TUTORIAL.steps.len(),
+            step.text(&balance)
+        ),
+        ..default()
+    });
+
+    let (mut board_position, mut transform, mut upgrades) = player.single_mut();
+    if let Some(tile_pos) = step
+        .player_position
+        .and_then(|(x, y)| BoardPosition::new(x, y))
+    {
+        *board_position = tile_pos;
+        let global_position = tile_pos.as_global_position();
+        transform.translation.x = global_position.x;
+        transform.translation.y = global_position.y;
+        highlight_cache.invalidate();
+    }
+    for movement_type in step.movement_types.iter() {
+        let upgrade = catalog.get_movement_upgrade(movement_type);
+        upgrades.0.push(upgrade.clone());
+        apply_upgrades_event_writer.send(ApplyUpgrades(upgrade));
+    }
+    gold.amount += step.gold;
+
+    if step.enemies.is_empty() {
+        return;
+    }
+    for entity in enemies.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for enemy in step.enemies.iter() {
+        let (Some(piece_info), Some(tile_pos)) = (
+            enemy.piece_info(&roster, &balance),
+            BoardPosition::new(enemy.position.0, enemy.position.1),
+        ) else {
+            continue;
+        };
+        spawn_enemy(
+            &mut commands,
+            &asset_server,
+            &atlas_layout,
+            &catalog,
+            &piece_info,
+            tile_pos,
+            Color::WHITE,
+        );
+    }
+    highlight_cache.invalidate();
+}
+
+/// Waits for the player to perform the action of the current step
+fn check_tutorial_step(
+    mut progress: ResMut<TutorialProgress>,
+    player: Query<(Entity, &BoardPosition), With<Player>>,
+    shop_state: Res<State<ShopState>>,
+    mut move_events: EventReader<MovePieceEvent>,
+    mut attack_events: EventReader<AttackPieceEvent>,
+    mut level_up_events: EventReader<PlayerLevelUpEvent>,
+    mut side_effect_events: EventReader<SideEffect>,
+    mut apply_upgrades_events: EventReader<ApplyUpgrades>,
+) {
+    let Ok((player, player_position)) = player.get_single() else {
+        return;
+    };
+    let mut done = Vec::new();
+    for event in move_events.read() {
+        if event.entity == player {
+            progress.moved_this_turn = true;
+            done.push(TutorialTrigger::Move);
+        }
+    }
+    for event in attack_events.read() {
+        if event.attacker != player {
+            continue;
+        }
+        done.push(if progress.moved_this_turn {
+            TutorialTrigger::AttackAfterMove
+        } else {
+            TutorialTrigger::Attack
+        });
+        done.push(TutorialTrigger::AttackWith(event.movement_type.clone()));
+        if progress.moved_this_turn
+            && event.movement_type == MovementType::King
+            && event.origin != *player_position
+        {
+            done.push(TutorialTrigger::KingDoubleAttack);
+        }
+    }
+    if level_up_events.read().count() > 0 {
+        done.push(TutorialTrigger::LevelUp);
+    }
+    for event in side_effect_events.read() {
+        match event {
+            SideEffect::Chain { .. } => done.push(TutorialTrigger::Chain),
+            SideEffect::Pierce { .. } => done.push(TutorialTrigger::Pierce),
+            SideEffect::ConvertPiece { .. } => done.push(TutorialTrigger::Convert),
+            SideEffect::Block { .. } => done.push(TutorialTrigger::Block),
+            _ => {}
+        }
+    }
+    let shop_open = *shop_state.get() == ShopState::Open;
+    if shop_open {
+        done.push(TutorialTrigger::OpenShop);
+    }
+    // upgrades given by the tutorial are applied while the shop is closed
+    if apply_upgrades_events.read().count() > 0 && shop_open {
+        done.push(TutorialTrigger::BuyUpgrade);
+    }
+
+    let Some(step) = TUTORIAL.steps.get(progress.step) else {
+        return;
+    };
+    if progress.step_started && done.contains(&step.trigger) {
+        debug!("Tutorial step {} done", progress.step + 1);
+        progress.step += 1;
+        progress.step_started = false;
+    }
+}
+
+fn reset_moved_this_turn(mut progress: ResMut<TutorialProgress>) {
+    progress.moved_this_turn = false;
+}
+
+fn reset_tutorial_progress(mut progress: ResMut<TutorialProgress>) {
+    *progress = TutorialProgress::default();
+}
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TutorialProgress>()
+            .add_systems(OnEnter(GameState::Game), reset_tutorial_progress)
+            .add_systems(OnEnter(TurnState::PlayerInput), reset_moved_this_turn)
+            .add_systems(
+                Update,
+                (
+                    check_tutorial_step,
+                    // the next step waits for the enemies to finish their turn
+                    start_tutorial_step.run_if(in_state(TurnState::PlayerInput)),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game).and(is_tutorial)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorial_file_is_valid() {
+        let tutorial = Tutorial::parse(TUTORIAL_FILE).unwrap();
+        assert!(!tutorial.steps.is_empty());
+        for step in tutorial.steps.iter() {
+            assert!(!step.text(&Balance::default()).contains('{'));
+        }
+
+        let out_of_board = TUTORIAL_FILE.replace("(5, 3)", "(5, 9)");
+        assert_eq!(
+            Tutorial::parse(&out_of_board).unwrap_err(),
+            InvalidPuzzle::OutOfBoard((5, 9))
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    pieces::{
+        common::Team,
+        health::Health,
+        movement_type::MovementType,
+        player::{
+            character::CharacterRoster,
+            experience::PlayerLevel,
+            gold::Gold,
+            spawn::{Hero, Player},
+            upgrades::data::UpgradeCatalog,
+        },
+    },
+    states::{game_state::GameState, turn_state::TurnState},
+    ui::{
+        draft::{LevelUpDraft, LevelUpDrafts},
+        messages::{ClearMessages, MessageEvent},
+        shop::{ApplyUpgrades, ShopUpgrades},
+    },
+};
+
+use super::{defeat::check_defeat, profile::Profile, run_config::RunConfig};
+
+/// Gold, level, shop and level up drafts of the hero waiting for its turn
+#[derive(Default)]
+struct HeroContext {
+    gold: usize,
+    level: usize,
+    experience: usize,
+    shop: ShopUpgrades,
+    drafts: VecDeque<LevelUpDraft>,
+}
+
+#[derive(Resource, Default)]
+pub struct Versus {
+    /// Side of the hero playing the current turn
+    pub active: usize,
+    /// None on a draw
+    pub winner: Option<usize>,
+    turn_played: bool,
+    waiting: HeroContext,
+}
+
+impl Versus {
+    /// Side of the hero that wins when the other heroes are dead
+    pub fn survivor(dead: [bool; 2]) -> Option<usize> {
+        match dead {
+            [true, false] => Some(1),
+            [false, true] => Some(0),
+            _ => None,
+        }
+    }
+}
+
+pub fn is_versus(config: Res<RunConfig>) -> bool {
+    config.versus
+}
+
+/// Hands the turn, the gold, the level, the shop and the drafts over to the other hero
+fn pass_turn(
+    mut commands: Commands,
+    mut versus: ResMut<Versus>,
+    mut heroes: Query<(Entity, &Hero, &mut Team)>,
+    mut gold: ResMut<Gold>,
+    mut player_level: ResMut<PlayerLevel>,
+    mut shop_upgrades: ResMut<ShopUpgrades>,
+    mut drafts: ResMut<LevelUpDrafts>,
+    mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    catalog: Res<UpgradeCatalog>,
+    mut clear_messages_writer: EventWriter<ClearMessages>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    // the first turn starts without a hand over
+    if !versus.turn_played {
+        return;
+    }
+    versus.turn_played = false;
+    let current = HeroContext {
+        gold: gold.amount,
+        level: player_level.level,
+        experience: player_level.experience,
+        shop: std::mem::take(&mut *shop_upgrades),
+        drafts: std::mem::take(&mut drafts.0),
+    };
+    let next = std::mem::replace(&mut versus.waiting, current);
+    gold.amount = next.gold;
+    player_level.level = next.level;
+    player_level.experience = next.experience;
+    *shop_upgrades = next.shop;
+    drafts.0 = next.drafts;
+    versus.active = 1 - versus.active;
+
+    for (entity, hero, mut team) in heroes.iter_mut() {
+        if hero.side == versus.active {
+            *team = Team::Player;
+            commands.entity(entity).insert(Player);
+        } else {
+            *team = Team::Enemy;
+            commands.entity(entity).remove::<Player>();
+        }
+    }
+    // refreshes the movement limit, highlights and character info of the new hero
+    apply_upgrades_event_writer.send(ApplyUpgrades(
+        catalog.get_movement_upgrade(&MovementType::King),
+    ));
+    clear_messages_writer.send(ClearMessages);
+    message_event_writer.send(MessageEvent {
+        message: format!("Player {}'s turn", versus.active + 1),
+        timer: Some(Timer::from_seconds(2.0, TimerMode::Once)),
+    });
+}
+
+/// Neutral pieces go after whichever hero is nearest
+fn expose_heroes(mut heroes: Query<&mut Team, With<Hero>>) {
+    for mut team in heroes.iter_mut() {
+        *team = Team::Player;
+    }
+}
+
+fn check_versus_winner(
+    heroes: Query<(&Hero, &Health)>,
+    mut versus: ResMut<Versus>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let mut dead = [false; 2];
+    for (hero, health) in heroes.iter() {
+        dead[hero.side] = health.is_dead();
+    }
+    if dead.contains(&true) {
+        versus.winner = Versus::survivor(dead);
+        debug!("Versus over, winner {:?}", versus.winner);
+        game_state.set(GameState::Victory);
+    }
+}
+
+fn end_hero_turn(mut versus: ResMut<Versus>) {
+    versus.turn_played = true;
+}
+
+/// The second hero waits with the same starting gold and level as the first one
+fn reset_versus(
+    mut versus: ResMut<Versus>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    characters: Res<CharacterRoster>,
+) {
+    *versus = Versus {
+        waiting: HeroContext {
+            gold: config.character(&profile, &characters).starting_gold(),
+            level: PlayerLevel::new().level,
+            ..default()
+        },
+        ..default()
+    };
+}
+
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Versus>()
+            .add_systems(OnEnter(GameState::Game), reset_versus)
+            .add_systems(
+                OnEnter(TurnState::PlayerInput),
+                pass_turn.run_if(in_state(GameState::Game).and(is_versus)),
+            )
+            .add_systems(
+                OnEnter(TurnState::PlayerAnimation),
+                end_hero_turn.run_if(in_state(GameState::Game).and(is_versus)),
+            )
+            .add_systems(
+                OnEnter(TurnState::EnemyAI),
+                expose_heroes.run_if(in_state(GameState::Game).and(is_versus)),
+            )
+            .add_systems(
+                Update,
+                check_versus_winner
+                    .before(check_defeat)
+                    .run_if(in_state(GameState::Game).and(is_versus)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versus_survivor() {
+        assert_eq!(Versus::survivor([true, false]), Some(1));
+        assert_eq!(Versus::survivor([false, true]), Some(0));
+        assert_eq!(Versus::survivor([true, true]), None);
+    }
+}
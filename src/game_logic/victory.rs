@@ -0,0 +1,188 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    globals::{BOSS_SPAWN_TURN, VICTORY_BOARD_CLEARS, VICTORY_SCORE, VICTORY_SURVIVE_TURNS},
+    pieces::{
+        common::{Piece, Team},
+        enemies::spawn::Boss,
+        health::{DeathAnimation, PieceDeathEvent},
+    },
+    states::{game_state::GameState, turn_state::TurnInfo},
+};
+
+use super::{
+    defeat::check_defeat,
+    run_config::{GameMode, RunConfig},
+    score::GameScore,
+};
+
+/// What ends a run with a victory, endless runs only end on defeat
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VictoryCondition {
+    SurviveTurns(usize),
+    KillBossKing,
+    ReachScore(usize),
+    ClearBoard(usize),
+    Endless,
+}
+
+impl VictoryCondition {
+    /// Conditions selectable before a run
+    pub const OPTIONS: [VictoryCondition; 5] = [
+        VictoryCondition::SurviveTurns(VICTORY_SURVIVE_TURNS),
+        VictoryCondition::KillBossKing,
+        VictoryCondition::ReachScore(VICTORY_SCORE),
+        VictoryCondition::ClearBoard(VICTORY_BOARD_CLEARS),
+        VictoryCondition::Endless,
+    ];
+
+    /// Leaderboard mode of runs played with this condition
+    pub fn mode(&self) -> GameMode {
+        match self {
+            VictoryCondition::Endless => GameMode::Endless,
+            _ => GameMode::Standard,
+        }
+    }
+
+    /// `turn` is the current turn, the turns survived are the ones before it
+    pub fn is_met(&self, turn: usize, score: usize, progress: &VictoryProgress) -> bool {
+        match *self {
+            VictoryCondition::SurviveTurns(turns) => turn > turns,
+            VictoryCondition::KillBossKing => progress.boss_killed,
+            VictoryCondition::ReachScore(target) => score >= target,
+            VictoryCondition::ClearBoard(times) => progress.boards_cleared >= times,
+            VictoryCondition::Endless => false,
+        }
+    }
+
+    pub fn progress(&self, turn: usize, score: usize, progress: &VictoryProgress) -> String {
+        match *self {
+            VictoryCondition::SurviveTurns(turns) => {
+                format!("Goal: turn {}/{}", turn.saturating_sub(1).min(turns), turns)
+            }
+            VictoryCondition::KillBossKing if progress.boss_spawned => {
+                "Goal: kill the boss king".to_string()
+            }
+            VictoryCondition::KillBossKing => {
+                format!("Goal: boss king on turn {}", BOSS_SPAWN_TURN)
+            }
+            VictoryCondition::ReachScore(target) => {
+                format!("Goal: score {}/{}", score.min(target), target)
+            }
+            VictoryCondition::ClearBoard(times) => {
+                format!("Goal: clears {}/{}", progress.boards_cleared, times)
+            }
+            VictoryCondition::Endless => "Endless".to_string(),
+        }
+    }
+}
+
+impl Default for VictoryCondition {
+    fn default() -> Self {
+        VictoryCondition::SurviveTurns(VICTORY_SURVIVE_TURNS)
+    }
+}
+
+impl fmt::Display for VictoryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VictoryCondition::SurviveTurns(turns) => write!(f, "Survive {} turns", turns),
+            VictoryCondition::KillBossKing => write!(f, "Kill the boss king"),
+            VictoryCondition::ReachScore(score) => write!(f, "Reach a score of {}", score),
+            VictoryCondition::ClearBoard(times) => write!(f, "Clear the board {} times", times),
+            VictoryCondition::Endless => write!(f, "Endless"),
+        }
+    }
+}
+
+/// Progress of the current run towards its victory condition
+#[derive(Resource, Default)]
+pub struct VictoryProgress {
+    pub boss_spawned: bool,
+    pub boss_killed: bool,
+    pub boards_cleared: usize,
+    board_has_enemies: bool,
+}
+
+fn track_boss_kill(
+    mut death_events: EventReader<PieceDeathEvent>,
+    bosses: Query<(), With<Boss>>,
+    mut progress: ResMut<VictoryProgress>,
+) {
+    for event in death_events.read() {
+        if bosses.contains(event.entity) {
+            progress.boss_killed = true;
+        }
+    }
+}
+
+/// Counts the times the last enemy on the board dies
+fn track_board_clears(
+    pieces: Query<&Team, (With<Piece>, Without<DeathAnimation>)>,
+    mut progress: ResMut<VictoryProgress>,
+) {
+    let has_enemies = pieces.iter().any(|team| *team == Team::Enemy);
+    if progress.board_has_enemies && !has_enemies {
+        progress.boards_cleared += 1;
+        debug!("Board cleared {} times", progress.boards_cleared);
+    }
+    progress.board_has_enemies = has_enemies;
+}
+
+fn check_victory(
+    config: Res<RunConfig>,
+    turn_info: Res<TurnInfo>,
+    score: Res<GameScore>,
+    progress: Res<VictoryProgress>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let score = config.ascension().score(score.0);
+    if config.victory.is_met(turn_info.number, score, &progress) {
+        debug!("Victory: {}", config.victory);
+        game_state.set(GameState::Victory);
+    }
+}
+
+fn reset_victory_progress(mut progress: ResMut<VictoryProgress>) {
+    *progress = VictoryProgress::default();
+}
+
+pub struct VictoryPlugin;
+
+impl Plugin for VictoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VictoryProgress>()
+            .add_systems(OnEnter(GameState::Game), reset_victory_progress)
+            .add_systems(
+                Update,
+                // a defeat in the same frame wins over the victory
+                (track_boss_kill, track_board_clears, check_victory)
+                    .chain()
+                    .before(check_defeat)
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_victory_conditions() {
+        let progress = VictoryProgress {
+            boards_cleared: 2,
+            ..default()
+        };
+        assert!(!VictoryCondition::SurviveTurns(10).is_met(10, 0, &progress));
+        assert!(VictoryCondition::SurviveTurns(10).is_met(11, 0, &progress));
+        assert!(!VictoryCondition::KillBossKing.is_met(100, 0, &progress));
+        assert!(VictoryCondition::ReachScore(50).is_met(1, 50, &progress));
+        assert!(VictoryCondition::ClearBoard(2).is_met(1, 0, &progress));
+        assert!(!VictoryCondition::ClearBoard(3).is_met(1, 0, &progress));
+        assert!(!VictoryCondition::Endless.is_met(1000, 1000, &progress));
+    }
+}
@@ -38,7 +38,6 @@ pub const GOLD_ANIMATION_DURATION: f32 = 1.5; // Duration of gold animation
 pub const GOLD_UI_COLOR_DURATION: f32 = 0.5; // Duration of gold UI color
 
 // Player settings
-pub const PLAYER_HEALTH: f32 = 5.0; // Health of the player
 pub const PLAYER_DAMAGE: f32 = 1.0; // Damage of the player
 pub const PRIMARY_COLOR: Color = Color::srgba(94.0 / 255.0, 205.0 / 255.0, 228.0 / 255.0, 1.0);
 pub const SECONDARY_COLOR: Color = Color::srgba(0.674, 0.192, 0.192, 1.0);
@@ -51,23 +50,49 @@ pub const DARKER_PRIMARY_COLOR: Color = Color::srgba(
     228.0 * 0.9 / 255.0,
     1.0,
 );
-// Enemy settings
-pub const ENEMY_BASE_HEALTH: f32 = 3.0; // Health of the enemy
-pub const ENEMY_BASE_DAMAGE: f32 = 1.0; // Damage of the enemy
-
-// Enemy spawn settings
-pub const KING_SPAWN_TURN: usize = 15; // Turn number to spawn kings
-pub const KNIGHT_SPAWN_TURN: usize = 30; // Turn number to spawn knights
-pub const BISHOP_SPAWN_TURN: usize = 45; // Turn number to spawn bishops
-pub const ROOK_SPAWN_TURN: usize = 60; // Turn number to spawn rooks
-pub const QUEEN_SPAWN_TURN: usize = 75; // Turn number to spawn queens
-
-pub const KING_SPAWN_WEIGHT: f32 = 1.0; // Weight of the king spawn
-pub const PAWN_SPAWN_WEIGHT: f32 = 1.0; // Weight of the pawn spawn
-pub const KNIGHT_SPAWN_WEIGHT: f32 = 1.0; // Weight of the knight spawn
-pub const BISHOP_SPAWN_WEIGHT: f32 = 1.0; // Weight of the bishop spawn
-pub const ROOK_SPAWN_WEIGHT: f32 = 1.0; // Weight of the rook spawn
-pub const QUEEN_SPAWN_WEIGHT: f32 = 1.0; // Weight of the queen spawn
+
+// Elite enemy settings
+pub const ELITE_SPAWN_TURN: usize = 40; // Turn number from which elites can spawn
+pub const ELITE_SPAWN_CHANCE: f32 = 0.1; // Chance for a spawned enemy to be an elite
+pub const ELITE_HEALTH_MULTIPLIER: f32 = 2.0; // Health multiplier of elites
+pub const ELITE_DAMAGE_MULTIPLIER: f32 = 2.0; // Damage multiplier of elites
+pub const ELITE_COLOR: Color = Color::srgb(1.0, 0.6, 0.6); // Tint of elite sprites
+
+// Ascension settings
+pub const ASCENSION_SPAWN_TURN_SHIFT: usize = 10; // Turns earlier enemies spawn
+pub const ASCENSION_EXTRA_ENEMIES: usize = 2; // Enemies added to the target number of enemies
+pub const ASCENSION_ENEMY_STAT_MULTIPLIER: f32 = 1.5; // Health and damage multiplier of enemies
+pub const ASCENSION_SHOP_PRICE_PERCENT: usize = 150; // Shop prices in percent of the normal price
+pub const ASCENSION_GOLD_PERCENT: usize = 50; // Gold per kill in percent of the normal gold
+pub const ASCENSION_SCORE_PERCENT_STEP: usize = 25; // Score bonus in percent per ascension level
+pub const ASCENSION_UNLOCK_TURN: usize = 50; // Turn to reach to unlock the next ascension level
+
+// Daily challenge settings
+pub const DAILY_MAX_MUTATORS: usize = 3; // Max number of mutators of a daily challenge
+pub const GLASS_CANNON_MULTIPLIER: f32 = 2.0; // Attack multiplier and health divisor of the glass cannon mutator
+
+// Victory settings
+pub const VICTORY_SURVIVE_TURNS: usize = 60; // Turns to survive to win
+pub const VICTORY_SCORE: usize = 150; // Score to reach to win
+pub const VICTORY_BOARD_CLEARS: usize = 3; // Times the board has to be cleared to win
+pub const VICTORY_META_CURRENCY_BONUS: usize = 10; // Meta-currency awarded on top of the score for a victory
+pub const BOSS_SPAWN_TURN: usize = 30; // Turn number the boss king spawns
+pub const BOSS_HEALTH_MULTIPLIER: f32 = 5.0; // Health multiplier of the boss king
+pub const BOSS_DAMAGE_MULTIPLIER: f32 = 2.0; // Damage multiplier of the boss king
+pub const BOSS_COLOR: Color = Color::srgb(1.0, 0.85, 0.2); // Tint of the boss king sprite
+
+// Two hero settings
+pub const VERSUS_HERO_POSITIONS: [(i32, i32); 2] = [(1, 1), (6, 6)]; // Starting tiles of the versus heroes
+pub const COOP_HERO_POSITIONS: [(i32, i32); 2] = [(3, 4), (4, 4)]; // Starting tiles of the co-op heroes
+pub const COOP_REVIVE_HEALTH_FRACTION: f32 = 0.5; // Fraction of max health a revived co-op hero comes back with
+pub const DOWNED_HERO_COLOR: Color = Color::srgb(0.3, 0.3, 0.3); // Tint of a downed co-op hero
+pub const HERO_COLORS: [Color; 2] = [Color::srgb(0.6, 0.8, 1.0), Color::srgb(1.0, 0.6, 0.6)]; // Tints telling the heroes apart
+
+// Online settings
+pub const ONLINE_RELAY_ADDRESS: &str = "127.0.0.1:7878"; // Relay server used when BCH_RELAY is not set
+pub const ONLINE_RELAY_ENV_VAR: &str = "BCH_RELAY"; // Environment variable overriding the relay address
+pub const ONLINE_ROOM: &str = "default"; // Room joined when BCH_ROOM is not set
+pub const ONLINE_ROOM_ENV_VAR: &str = "BCH_ROOM"; // Environment variable overriding the room, so several pairs can share a relay
 
 // Spritesheet settings
 pub const HIGHLIGHT_ATLAS_INDEX: usize = 3; // Index of the highlight sprite in the spritesheet
@@ -76,13 +101,18 @@ pub const FULL_HEALTHBAR_ATLAS_INDEX: usize = 7; // Index of the full healthbar
 pub const EMPTY_HEALTHBAR_ATLAS_INDEX: usize = 8; // Index of the empty healthbar sprite in the spritesheet
 pub const PLAYER_ATLAS_INDEX: usize = 20; // Index of the player sprite in the spritesheet
                                           // Spawner settings
-pub const TARGET_NUM_ENEMIES: usize = 10; // Max number of enemies on the board
 pub const PER_TURN_ENEMY_SPAWN_COUNT: usize = 2; // Number of enemies to spawn per turn
 pub const SPRITESHEET_WIDTH: usize = 20; // Width of the spritesheet
 pub const SPRITESHEET_HEIGHT: usize = 20; // Height of the spritesheet
 pub const WIP_SPRITE_INDEX: usize = 19; // Index of the wip sprite in the spritesheet
 pub const BLANK_SPRITE_INDEX: usize = SPRITESHEET_WIDTH * SPRITESHEET_HEIGHT - 1; // Index of the blank sprite in the spritesheet
 
+// Data file settings
+pub const UPGRADES_ASSET_PATH: &str = "shop.upgrades.ron"; // Upgrades sold in the shop, hot-reloaded in debug builds
+pub const ENEMY_ROSTER_ASSET_PATH: &str = "enemies.roster.ron"; // Enemies that can spawn, hot-reloaded in debug builds
+pub const BALANCE_ASSET_PATH: &str = "game.balance.ron"; // Gameplay tuning values, hot-reloaded in debug builds
+pub const CHARACTERS_ASSET_PATH: &str = "heroes.characters.ron"; // Starting characters, hot-reloaded in debug builds
+
 // UI Settings
 const UI_FONT_SCALE: f32 = 3.0;
 pub const UI_FONT_SIZE: f32 = 10.0 * UI_FONT_SCALE; // Font size for UI elements
@@ -100,24 +130,59 @@ pub const GOLD_FONT_SIZE: f32 = 12.0; // Font size for gold text
 // UI Shop
 pub const SHOP_FONT_SIZE: f32 = 32.0 * 1.5; // Font size for shop text
 pub const SHOP_HEADER_FONT_SIZE: f32 = 64.0 * 1.5; // Font size for shop header text
-pub const SHOP_PIECE_VALUE_GOLD_MULTIPLIER: f32 = 10.0; // Multiplier for the value of pieces in the shop
 pub const SHOP_UPGRADES_COUNT_MOVEMENT: usize = 1;
 pub const SHOP_UPGRADES_COUNT_STATS: usize = 3;
 pub const STARTING_GOLD: usize = 10000; // Starting gold
-pub const REFRESH_SHOP_COST: usize = 1; // Cost of refreshing the shop
+
+// Consumable settings
+pub const INVENTORY_SIZE: usize = 3; // Consumables a hero can carry
+
+// Combat log settings
+pub const COMBAT_LOG_DISPLAY_LIMIT: usize = 100; // Max number of combat log entries shown in the UI
+pub const COMBAT_LOG_EXPORT_FILE: &str = "combat_log.txt"; // File the combat log is exported to
+pub const COMBAT_LOG_EXPORT_MESSAGE_DURATION: f32 = 3.0; // Duration of the export result message
+pub const COMBAT_LOG_FONT_SIZE: f32 = 8.0 * UI_FONT_SCALE; // Font size for combat log entries
+pub const COMBAT_LOG_SCROLL_SPEED: f32 = 20.0; // Pixels scrolled per mouse wheel line
+pub const COMBAT_LOG_HEIGHT: f32 = 240.0; // Height of the scrollable combat log area
+
+// Run history settings
+pub const RUN_HISTORY_FILE: &str = "run_history.ron"; // File every finished run is appended to
+pub const STATS_CHART_WIDTH: f32 = 400.0; // Width of the longest bar in the stats charts
+pub const STATS_RECENT_RUNS: usize = 10; // Number of recent runs charted on the stats screen
+
+// Leaderboard settings
+pub const LEADERBOARD_FILE: &str = "leaderboard.ron"; // File the local leaderboard is saved to
+pub const LEADERBOARD_SIZE: usize = 10; // Entries kept per mode, difficulty and seed
+pub const REPLAY_FILE_PREFIX: &str = "replay_"; // Replays are saved to this prefix followed by the date, seed and score
+
+// Meta progression settings
+pub const PROFILE_FILE: &str = "profile.ron"; // File the meta progression profile is saved to
+pub const META_CURRENCY_SCORE_DIVISOR: usize = 10; // Score needed to earn one meta-currency
+pub const META_HEALTH_BONUS: f32 = 1.0; // Max health gained per health bonus unlock
+pub const META_ATTACK_BONUS: f32 = 0.5; // Attack gained per attack bonus unlock
 
 // Keyboard settings
 pub const SHOP_KEY: KeyCode = KeyCode::KeyS; // Key to toggle the shop
 pub const REFRESH_SHOP_KEY: KeyCode = KeyCode::KeyR; // Key to refresh the shop
+pub const CONSUMABLE_KEYS: [KeyCode; INVENTORY_SIZE] =
+    [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3]; // Keys to use the consumables of the hotbar
+
+// Touch settings
+pub const TOUCH_HOLD_DURATION: f32 = 0.4; // Seconds a touch must be held to show piece info
+pub const TOUCH_TAP_MAX_DISTANCE: f32 = 12.0; // Max finger travel (logical pixels) for a touch to count as a tap
+pub const TOUCH_CONFIRM_MESSAGE_DURATION: f32 = 2.0; // Duration of the "tap again to attack" message
+
+// Portrait layout settings
+pub const PORTRAIT_PANEL_HEIGHT: f32 = 25.0; // Height of each side panel (in percent) on portrait screens
+pub const PORTRAIT_VIEWPORT_WIDTH: f32 = BOARD_SIZE as f32 * TILE_SIZE as f32 * 1.1; // World units visible horizontally on portrait screens
 
 // Upgrade settings
 pub const UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER: usize = 2;
-pub const UNIQUE_UPGRADE_DAMAGE_MULTIPLIER: f32 = 0.1;
-pub const CONVERT_ENEMY_TURNS_TO_CONVERT: usize = 3;
-
-pub const MOVEMENT_TYPE_LIMITS: [(usize, usize); 3] = [
-    (0, 2),
-    (UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER * 2, 3),
-    (UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER * 5, 4),
+pub const ADVANCED_ABILITY_UNLOCK_UPGRADE_NUMBER: usize = 4; // Upgrades of a movement type for the second tier of its unique ability
+pub const ABILITY_TIER_UNLOCKS: [usize; 2] = [
+    UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER,
+    ADVANCED_ABILITY_UNLOCK_UPGRADE_NUMBER,
 ];
-pub const QUEEN_UNIQUE_CHANCE: f32 = 0.35;
+pub const UNIQUE_UPGRADE_DAMAGE_MULTIPLIER: f32 = 0.1;
+pub const SYNERGY_UPGRADE_NUMBER: usize = 2; // Upgrades of each movement type a synergy needs
+pub const SYNERGY_PIERCE_COUNT: usize = 1; // Pieces a chained attack pierces with the Knight and Bishop synergy
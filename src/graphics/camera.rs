@@ -1,7 +1,17 @@
-use bevy::{prelude::*, render::camera::ScalingMode};
+use bevy::{
+    prelude::*,
+    render::camera::ScalingMode,
+    window::{PrimaryWindow, WindowResized},
+};
 
 use crate::globals;
 
+const LANDSCAPE_SCALING_MODE: ScalingMode = ScalingMode::AutoMax {
+    max_width: 2560.0,
+    max_height: 1440.0,
+};
+const LANDSCAPE_SCALE: f32 = 1.0 / 5.0;
+
 pub fn setup_camera(mut commands: Commands) {
     debug!("Setting up camera");
     commands.spawn((
@@ -14,11 +24,8 @@ pub fn setup_camera(mut commands: Commands) {
         Projection::Orthographic(OrthographicProjection {
             near: -1000.0,
             far: 1000.0,
-            scale: 1.0 / 5.0,
-            scaling_mode: ScalingMode::AutoMax {
-                max_width: 2560.0,
-                max_height: 1440.0,
-            },
+            scale: LANDSCAPE_SCALE,
+            scaling_mode: LANDSCAPE_SCALING_MODE,
             viewport_origin: Vec2::new(0.5, 0.5),
             area: Rect::new(0.0, 0.0, 2560.0, 1440.0),
         }),
@@ -27,3 +34,45 @@ pub fn setup_camera(mut commands: Commands) {
         Msaa::Off,
     ));
 }
+
+/// Keeps the whole board visible on portrait screens, so it never needs pinching
+pub fn fit_camera_to_window(
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&mut Projection, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    let portrait = window.height() > window.width();
+    // Camera2d also requires an OrthographicProjection, keep both in sync
+    for (mut projection, mut orthographic) in cameras.iter_mut() {
+        if let Projection::Orthographic(projection) = projection.as_mut() {
+            fit_projection(projection, portrait);
+        }
+        fit_projection(&mut orthographic, portrait);
+    }
+}
+
+fn fit_projection(projection: &mut OrthographicProjection, portrait: bool) {
+    if portrait {
+        projection.scaling_mode = ScalingMode::FixedHorizontal {
+            viewport_width: globals::PORTRAIT_VIEWPORT_WIDTH,
+        };
+        projection.scale = 1.0;
+    } else {
+        projection.scaling_mode = LANDSCAPE_SCALING_MODE;
+        projection.scale = LANDSCAPE_SCALE;
+    }
+}
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (setup_camera, fit_camera_to_window).chain())
+            .add_systems(
+                Update,
+                fit_camera_to_window.run_if(on_event::<WindowResized>),
+            );
+    }
+}
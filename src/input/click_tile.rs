@@ -1,19 +1,26 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{prelude::*, utils::HashSet, window::PrimaryWindow};
 
 use crate::{
     board::position::BoardPosition,
     globals::SPRITESHEET_WIDTH,
     pieces::{
-        attack::{attack_from_tile, AttackPieceEvent},
+        attack::{attack_from_tile, AttackPieceEvent, AttackSource},
         common::{Piece, Team},
         damage::Attack,
+        enemies::roster::EnemyRoster,
         movement::MovePieceEvent,
         movement_type::MovementType,
-        player::{spawn::Player, upgrades::data::Upgrades},
+        player::{
+            consumables::{hero_movement_types, Promoted},
+            spawn::Player,
+            upgrades::data::Upgrades,
+        },
     },
     states::turn_state::TurnState,
 };
 
+use super::touch::TouchControls;
+
 #[derive(Resource)]
 pub struct HoveredTile(pub Option<BoardPosition>);
 
@@ -21,7 +28,12 @@ pub fn update_hovered_tile(
     mut resource: ResMut<HoveredTile>,
     window: Query<&Window, With<PrimaryWindow>>,
     camera: Query<(&Camera, &GlobalTransform)>,
+    touch_controls: Res<TouchControls>,
 ) {
+    // on touch screens the hovered tile is driven by taps and holds instead
+    if touch_controls.enabled {
+        return;
+    }
     let (camera, camera_transform) = camera.single();
     if let Some(tile_position) =
         mouse_position_to_tile_position(window.single(), camera, camera_transform)
@@ -41,7 +53,17 @@ fn mouse_position_to_tile_position(
 ) -> Option<BoardPosition> {
     window
         .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        .and_then(|cursor| viewport_to_tile_position(cursor, camera, camera_transform))
+}
+
+pub fn viewport_to_tile_position(
+    viewport_position: Vec2,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<BoardPosition> {
+    camera
+        .viewport_to_world_2d(camera_transform, viewport_position)
+        .ok()
         .and_then(BoardPosition::from_world_position)
 }
 
@@ -55,63 +77,93 @@ pub fn click_tile_update_player_position(
     windows: Query<&Window, With<PrimaryWindow>>,
     camera: Query<(&Camera, &GlobalTransform)>,
     mouse: Res<ButtonInput<MouseButton>>,
-    player: Query<(Entity, &BoardPosition, &Attack, &Upgrades), With<Player>>,
-    touches: Res<Touches>,
+    player: Query<
+        (
+            Entity,
+            &BoardPosition,
+            &Attack,
+            &Upgrades,
+            Option<&Promoted>,
+        ),
+        With<Player>,
+    >,
     pieces_query: Query<(Entity, &BoardPosition, &Team), (With<Piece>, Without<Player>)>,
     mut next_state: ResMut<NextState<TurnState>>,
+    roster: Res<EnemyRoster>,
 ) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
     let window = windows.single();
     let (camera, camera_transform) = camera.single();
-    let (player_entity, player_position, damage, player_upgrades) = player.single();
+    let Some(tile_position) = mouse_position_to_tile_position(window, camera, camera_transform)
+    else {
+        return;
+    };
+    let (player_entity, player_position, damage, player_upgrades, promoted) = player.single();
     let all_pieces_positions = pieces_query.iter().map(|(_, pos, _)| *pos).collect();
     let enemy_pieces_positions = pieces_query
         .iter()
         .filter(|(_, _, &team)| team == Team::Enemy)
         .map(|(_, pos, _)| *pos)
         .collect();
+    let movement_types = hero_movement_types(player_upgrades, promoted);
 
-    if mouse.just_pressed(MouseButton::Left) {
-        if let Some(tile_position) =
-            mouse_position_to_tile_position(window, camera, camera_transform)
-        {
-            let mut current_tile_position = player_position;
-            let mut moved = false;
-            let movement_types = player_upgrades.get_movement_types_set();
-            // First, move the player to the tile if possible
-            for movement_type in movement_types.clone() {
-                let response = movement_type.get_valid_moves(
-                    player_position,
-                    &all_pieces_positions,
-                    &enemy_pieces_positions,
-                );
-                if response.valid_moves.contains(&tile_position) {
-                    send_move_event(&mut move_event_writer, tile_position, player_entity);
-                    current_tile_position = &tile_position;
-                    moved = true;
-                }
-            }
+    // First, move the player to the tile if possible
+    if try_move_player(
+        &mut move_event_writer,
+        tile_position,
+        player_entity,
+        player_position,
+        &movement_types,
+        &all_pieces_positions,
+        &enemy_pieces_positions,
+    ) {
+        next_state.set(TurnState::PlayerAnimation);
+        return;
+    }
 
-            if moved {
-                next_state.set(TurnState::PlayerAnimation);
-                return;
-            }
+    // else, try attacking from current tile
+    attack_from_tile(
+        &movement_types,
+        player_position,
+        &all_pieces_positions,
+        &enemy_pieces_positions,
+        &pieces_query,
+        &mut attack_event_writer,
+        player_entity,
+        damage,
+        &mut next_state,
+        &roster,
+    );
+}
 
-            // else, try attacking from current tile
-            attack_from_tile(
-                &movement_types,
-                current_tile_position,
-                &all_pieces_positions,
-                &enemy_pieces_positions,
-                &pieces_query,
-                &mut attack_event_writer,
-                player_entity,
-                damage,
-                &mut next_state,
-            );
-        }
-    } else {
-        for _ in touches.iter_just_pressed() {}
+/// Moves the player to `tile_position` if any of its movement types can reach it
+///
+/// Returns whether a move event was sent
+pub fn try_move_player(
+    move_event_writer: &mut EventWriter<MovePieceEvent>,
+    tile_position: BoardPosition,
+    player_entity: Entity,
+    player_position: &BoardPosition,
+    movement_types: &HashSet<MovementType>,
+    all_pieces_positions: &HashSet<BoardPosition>,
+    enemy_pieces_positions: &HashSet<BoardPosition>,
+) -> bool {
+    let can_move = movement_types.iter().any(|movement_type| {
+        movement_type
+            .get_valid_moves(
+                player_position,
+                all_pieces_positions,
+                enemy_pieces_positions,
+            )
+            .valid_moves
+            .contains(&tile_position)
+    });
+    if can_move {
+        send_move_event(move_event_writer, tile_position, player_entity);
     }
+    can_move
 }
 
 fn send_move_event(
@@ -135,6 +187,7 @@ pub fn send_attack_event(
     movement_type: &MovementType,
     delay: Option<f32>,
     player_position: &BoardPosition,
+    roster: &EnemyRoster,
 ) {
     debug!(
         "Clicked tile: {:?}, attacking target: {:?}, with damage: {}",
@@ -146,9 +199,9 @@ pub fn send_attack_event(
         attacker: player_entity,
         target: target_entity,
         damage,
-        sprite_index: Some(movement_type.sprite_index() + SPRITESHEET_WIDTH),
+        sprite_index: Some(movement_type.sprite_index(roster) + SPRITESHEET_WIDTH),
         delay,
         movement_type: movement_type.clone(),
-        upgrades_applied: false,
+        source: AttackSource::Direct,
     });
 }
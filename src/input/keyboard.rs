@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
 use crate::{
-    globals::{REFRESH_SHOP_COST, REFRESH_SHOP_KEY, SHOP_KEY},
-    ui::shop::RefreshShop,
+    game_logic::balance::Balance,
+    globals::{CONSUMABLE_KEYS, REFRESH_SHOP_KEY, SHOP_KEY},
+    network::is_local_turn,
+    pieces::player::consumables::UseConsumable,
+    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+    ui::shop::{RefreshShop, ShopState},
 };
 
 #[derive(Event)]
@@ -20,21 +24,41 @@ pub fn toggle_shop(
 pub fn refresh_shop(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut event_writer: EventWriter<RefreshShop>,
+    balance: Res<Balance>,
 ) {
     if keyboard_input.just_pressed(REFRESH_SHOP_KEY) {
         event_writer.send(RefreshShop {
-            cost: REFRESH_SHOP_COST,
+            cost: balance.refresh_shop_cost,
         });
     }
 }
 
+pub fn use_consumable(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut event_writer: EventWriter<UseConsumable>,
+) {
+    for (slot, key) in CONSUMABLE_KEYS.iter().enumerate() {
+        if keyboard_input.just_pressed(*key) {
+            event_writer.send(UseConsumable(slot));
+        }
+    }
+}
+
 pub struct KeyboardPlugin;
 
 impl Plugin for KeyboardPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, toggle_shop);
         app.add_event::<ToggleShop>();
-        app.add_systems(Update, refresh_shop);
+        app.add_systems(Update, refresh_shop.run_if(in_state(ShopState::Open)));
         app.add_event::<RefreshShop>();
+        app.add_systems(
+            Update,
+            use_consumable
+                .run_if(in_state(GameState::Game))
+                .run_if(in_state(TurnState::PlayerInput))
+                .run_if(in_state(GamePauseState::Playing))
+                .run_if(is_local_turn),
+        );
     }
 }
@@ -1,2 +1,3 @@
 pub mod click_tile;
 pub mod keyboard;
+pub mod touch;
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+
+use crate::{
+    board::{highlight::HighlightCache, position::BoardPosition},
+    globals::{TOUCH_CONFIRM_MESSAGE_DURATION, TOUCH_HOLD_DURATION, TOUCH_TAP_MAX_DISTANCE},
+    pieces::{
+        attack::{attack_from_tile, AttackPieceEvent},
+        common::{Piece, Team},
+        damage::Attack,
+        enemies::roster::EnemyRoster,
+        movement::MovePieceEvent,
+        player::{
+            consumables::{hero_movement_types, Promoted},
+            spawn::Player,
+            upgrades::data::Upgrades,
+        },
+    },
+    states::turn_state::TurnState,
+    ui::messages::MessageEvent,
+};
+
+use super::click_tile::{try_move_player, viewport_to_tile_position, HoveredTile};
+
+/// State of the touch controls
+///
+/// A short tap moves the player, a tap on an attack tile has to be
+/// repeated to confirm the attack, and holding a tile shows its info.
+#[derive(Resource, Default)]
+pub struct TouchControls {
+    /// Whether the last input came from a touch screen
+    pub enabled: bool,
+    press: Option<TouchPress>,
+    pending_attack: Option<BoardPosition>,
+}
+
+struct TouchPress {
+    id: u64,
+    tile: Option<BoardPosition>,
+    timer: Timer,
+    held: bool,
+}
+
+/// Tracks the current touch and shows the hover info of a held tile
+pub fn track_touch_press(
+    touches: Res<Touches>,
+    time: Res<Time>,
+    mut touch_controls: ResMut<TouchControls>,
+    mut hovered_tile: ResMut<HoveredTile>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+) {
+    // a mouse moving again means we are back on a desktop
+    if cursor_moved.read().count() > 0 && touches.iter().next().is_none() {
+        touch_controls.enabled = false;
+    }
+
+    if let Some(touch) = touches.iter_just_pressed().next() {
+        let (camera, camera_transform) = camera.single();
+        touch_controls.enabled = true;
+        touch_controls.press = Some(TouchPress {
+            id: touch.id(),
+            tile: viewport_to_tile_position(touch.position(), camera, camera_transform),
+            timer: Timer::from_seconds(TOUCH_HOLD_DURATION, TimerMode::Once),
+            held: false,
+        });
+    }
+
+    let Some(press) = touch_controls.press.as_mut() else {
+        return;
+    };
+    if touches.get_pressed(press.id).is_none() || press.held {
+        return;
+    }
+    if press.timer.tick(time.delta()).just_finished() {
+        debug!("Touch held on tile: {:?}", press.tile);
+        press.held = true;
+        hovered_tile.0 = press.tile;
+    }
+}
+
+/// Handles tap events
+///
+/// Tapping a valid tile moves the player there, tapping an attack tile
+/// asks for a second tap on the same tile before attacking.
+pub fn tap_tile_update_player_position(
+    mut move_event_writer: EventWriter<MovePieceEvent>,
+    mut attack_event_writer: EventWriter<AttackPieceEvent>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    touches: Res<Touches>,
+    mut touch_controls: ResMut<TouchControls>,
+    mut hovered_tile: ResMut<HoveredTile>,
+    highlight_cache: Res<HighlightCache>,
+    player: Query<
+        (
+            Entity,
+            &BoardPosition,
+            &Attack,
+            &Upgrades,
+            Option<&Promoted>,
+        ),
+        With<Player>,
+    >,
+    pieces_query: Query<(Entity, &BoardPosition, &Team), (With<Piece>, Without<Player>)>,
+    mut next_state: ResMut<NextState<TurnState>>,
+    roster: Res<EnemyRoster>,
+) {
+    let Some(press_id) = touch_controls.press.as_ref().map(|press| press.id) else {
+        return;
+    };
+    let Some(touch) = touches.get_released(press_id) else {
+        return;
+    };
+    let press = touch_controls.press.take().unwrap();
+    // holds only show information, and drags are not taps
+    if press.held || touch.distance().length() > TOUCH_TAP_MAX_DISTANCE {
+        return;
+    }
+    let Some(tile_position) = press.tile else {
+        touch_controls.pending_attack = None;
+        return;
+    };
+    hovered_tile.0 = Some(tile_position);
+
+    let (player_entity, player_position, damage, player_upgrades, promoted) = player.single();
+    let all_pieces_positions = pieces_query.iter().map(|(_, pos, _)| *pos).collect();
+    let enemy_pieces_positions = pieces_query
+        .iter()
+        .filter(|(_, _, &team)| team == Team::Enemy)
+        .map(|(_, pos, _)| *pos)
+        .collect();
+    let movement_types = hero_movement_types(player_upgrades, promoted);
+
+    if try_move_player(
+        &mut move_event_writer,
+        tile_position,
+        player_entity,
+        player_position,
+        &movement_types,
+        &all_pieces_positions,
+        &enemy_pieces_positions,
+    ) {
+        touch_controls.pending_attack = None;
+        next_state.set(TurnState::PlayerAnimation);
+        return;
+    }
+
+    if !highlight_cache.player_attacks.contains(&tile_position) {
+        touch_controls.pending_attack = None;
+        return;
+    }
+
+    if touch_controls.pending_attack != Some(tile_position) {
+        touch_controls.pending_attack = Some(tile_position);
+        message_event_writer.send(MessageEvent {
+            message: "Tap again to attack.".to_string(),
+            timer: Some(Timer::from_seconds(
+                TOUCH_CONFIRM_MESSAGE_DURATION,
+                TimerMode::Once,
+            )),
+        });
+        return;
+    }
+
+    touch_controls.pending_attack = None;
+    attack_from_tile(
+        &movement_types,
+        player_position,
+        &all_pieces_positions,
+        &enemy_pieces_positions,
+        &pieces_query,
+        &mut attack_event_writer,
+        player_entity,
+        damage,
+        &mut next_state,
+        &roster,
+    );
+}
+
+pub fn reset_pending_attack(mut touch_controls: ResMut<TouchControls>) {
+    touch_controls.pending_attack = None;
+    touch_controls.press = None;
+}
@@ -5,6 +5,7 @@ mod game_logic;
 mod globals;
 mod graphics;
 mod input;
+mod network;
 mod pieces;
 mod plugins;
 mod states;
@@ -17,6 +18,11 @@ fn main() {
         .add_plugins(
             DefaultPlugins
                 .set(ImagePlugin::default_nearest())
+                // data files only reload in debug builds, release builds keep what they loaded
+                .set(AssetPlugin {
+                    watch_for_changes_override: Some(cfg!(debug_assertions)),
+                    ..default()
+                })
                 .set(WindowPlugin {
                     primary_window: Some(Window {
                         title: "Bullet Chess Heaven".to_string(),
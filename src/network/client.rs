@@ -0,0 +1,123 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::log::warn;
+
+use super::protocol::{NetMessage, RelayLine};
+
+/// What happened to a connection since the last poll
+pub enum NetEvent {
+    Connected,
+    Failed(String),
+    Relay(RelayLine),
+    Closed,
+}
+
+enum ThreadEvent {
+    /// Writing side of the socket
+    Connected(TcpStream),
+    Event(NetEvent),
+}
+
+/// Connection to the relay server, read on a background thread
+pub struct Connection {
+    events: Mutex<Receiver<ThreadEvent>>,
+    stream: Option<TcpStream>,
+}
+
+impl Connection {
+    /// Connects in the background and joins `room` once connected
+    pub fn open(address: String, room: String) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let stream = match TcpStream::connect(&address).and_then(|stream| {
+                writeln!(&stream, "JOIN {}", room)?;
+                Ok(stream)
+            }) {
+                Ok(stream) => stream,
+                Err(error) => {
+                    let _ = sender.send(ThreadEvent::Event(NetEvent::Failed(error.to_string())));
+                    return;
+                }
+            };
+            let Ok(writer) = stream.try_clone() else {
+                let _ = sender.send(ThreadEvent::Event(NetEvent::Failed(
+                    "Could not share the socket".to_string(),
+                )));
+                return;
+            };
+            if sender.send(ThreadEvent::Connected(writer)).is_err() {
+                return;
+            }
+            let Ok(reader) = stream.try_clone() else {
+                let _ = sender.send(ThreadEvent::Event(NetEvent::Closed));
+                return;
+            };
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                match RelayLine::parse(&line) {
+                    Some(relay_line) => {
+                        // the relay only pairs players who acknowledge the start
+                        if relay_line == RelayLine::Start && writeln!(&stream, "READY").is_err() {
+                            break;
+                        }
+                        if sender
+                            .send(ThreadEvent::Event(NetEvent::Relay(relay_line)))
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    None => warn!("Ignoring unknown relay line {:?}", line),
+                }
+            }
+            let _ = sender.send(ThreadEvent::Event(NetEvent::Closed));
+        });
+        Connection {
+            events: Mutex::new(receiver),
+            stream: None,
+        }
+    }
+
+    /// Events received since the last poll
+    pub fn poll(&mut self) -> Vec<NetEvent> {
+        let mut events = Vec::new();
+        let receiver = self.events.get_mut().expect("Network thread never panics");
+        loop {
+            match receiver.try_recv() {
+                Ok(ThreadEvent::Connected(stream)) => {
+                    self.stream = Some(stream);
+                    events.push(NetEvent::Connected);
+                }
+                Ok(ThreadEvent::Event(event)) => events.push(event),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+        events
+    }
+
+    pub fn send(&self, message: &NetMessage) -> io::Result<()> {
+        let stream = self
+            .stream
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "not connected yet"))?;
+        writeln!(&*stream, "{}", message.encode())
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        if let Some(stream) = self.stream.as_ref() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
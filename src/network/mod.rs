@@ -0,0 +1,560 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use client::{Connection, NetEvent};
+use protocol::{board_checksum, NetMessage, OnlineMode, PieceSnapshot, PlayerAction, RelayLine};
+
+use crate::{
+    board::position::BoardPosition,
+    game_logic::{
+        balance::Balance, coop::Coop, profile::Profile, run_config::RunConfig, versus::Versus,
+        victory::VictoryCondition,
+    },
+    globals::{ONLINE_RELAY_ADDRESS, ONLINE_RELAY_ENV_VAR, ONLINE_ROOM, ONLINE_ROOM_ENV_VAR},
+    input::{
+        click_tile::{click_tile_update_player_position, try_move_player},
+        keyboard,
+        touch::tap_tile_update_player_position,
+    },
+    pieces::{
+        attack::{attack_from_tile, AttackPieceEvent},
+        common::{Piece, Team},
+        damage::Attack,
+        enemies::roster::EnemyRoster,
+        health::Health,
+        movement::MovePieceEvent,
+        player::{
+            consumables::{hero_movement_types, Promoted, UseConsumable},
+            spawn::Player,
+            upgrades::data::Upgrades,
+        },
+    },
+    states::{
+        game_state::GameState,
+        pause_state::GamePauseState,
+        turn_state::{TurnInfo, TurnState},
+    },
+    ui::{
+        draft::DraftChoice,
+        hotbar::press_hotbar_buttons,
+        messages::MessageEvent,
+        shop::{BuyShopUpgrade, RefreshShop, ShopAction},
+    },
+    utils::rng::random_seed,
+};
+
+pub mod client;
+pub mod protocol;
+
+/// Online match in lockstep: both players run the same seeded game
+/// and only exchange the actions of their hero
+#[derive(Resource, Default)]
+pub struct NetSession {
+    connection: Option<Connection>,
+    /// Game picked in the lobby, the host's choice is played
+    mode: Option<OnlineMode>,
+    /// Hero controlled on this machine, the host plays the first one
+    pub side: usize,
+    /// Lobby progress shown to the player
+    pub status: String,
+    /// Player inputs entered since the start of the game
+    step: usize,
+    pending: VecDeque<(usize, PlayerAction)>,
+    local_checksums: HashMap<usize, u64>,
+    remote_checksums: HashMap<usize, u64>,
+    desync: bool,
+}
+
+impl NetSession {
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Joins the room of the relay, both can be overridden from the environment
+    pub fn connect(&mut self, mode: OnlineMode) {
+        let address = std::env::var(ONLINE_RELAY_ENV_VAR)
+            .unwrap_or_else(|_| ONLINE_RELAY_ADDRESS.to_string());
+        let room = std::env::var(ONLINE_ROOM_ENV_VAR).unwrap_or_else(|_| ONLINE_ROOM.to_string());
+        debug!("Joining room {} on relay {}", room, address);
+        *self = NetSession {
+            connection: Some(Connection::open(address.clone(), room)),
+            mode: Some(mode),
+            status: format!("Connecting to {}...", address),
+            ..default()
+        };
+    }
+
+    pub fn disconnect(&mut self) {
+        self.connection = None;
+    }
+
+    /// Tells which hero this machine plays, in online games only
+    pub fn side_label(&self) -> String {
+        if self.is_connected() {
+            format!(" (you are Player {})", self.side + 1)
+        } else {
+            String::new()
+        }
+    }
+
+    fn send(&self, message: &NetMessage) {
+        let Some(connection) = self.connection.as_ref() else {
+            return;
+        };
+        if let Err(error) = connection.send(message) {
+            warn!("Could not send {:?}: {}", message, error);
+        }
+    }
+
+    fn poll(&mut self) -> Vec<NetEvent> {
+        self.connection
+            .as_mut()
+            .map(Connection::poll)
+            .unwrap_or_default()
+    }
+
+    /// Returns true the first time the players disagree on the board
+    fn record_checksum(&mut self, step: usize, checksum: u64, local: bool) -> bool {
+        if local {
+            self.local_checksums.insert(step, checksum);
+        } else {
+            self.remote_checksums.insert(step, checksum);
+        }
+        let (Some(local), Some(remote)) = (
+            self.local_checksums.get(&step),
+            self.remote_checksums.get(&step),
+        ) else {
+            return false;
+        };
+        if local == remote || self.desync {
+            return false;
+        }
+        self.desync = true;
+        true
+    }
+
+    /// Queues the actions of the other player, returns true on a new desync
+    fn receive(&mut self, message: NetMessage) -> bool {
+        match message {
+            NetMessage::Action { step, action } => {
+                self.pending.push_back((step, action));
+                false
+            }
+            NetMessage::Checksum { step, checksum } => self.record_checksum(step, checksum, false),
+            NetMessage::Hello { .. } => false,
+        }
+    }
+}
+
+fn desync_message(step: usize) -> MessageEvent {
+    warn!("Desync with the other player at step {}", step);
+    MessageEvent {
+        message: format!(
+            "Desync with the other player at step {}, your games may differ from now on.",
+            step
+        ),
+        ..default()
+    }
+}
+
+pub fn is_online(session: Res<NetSession>) -> bool {
+    session.is_connected()
+}
+
+/// Offline, every turn is played on this machine
+pub fn is_local_turn(
+    session: Res<NetSession>,
+    config: Res<RunConfig>,
+    versus: Res<Versus>,
+    coop: Res<Coop>,
+) -> bool {
+    if !session.is_connected() {
+        return config.replay.is_none();
+    }
+    let active = if config.versus {
+        versus.active
+    } else {
+        coop.active
+    };
+    active == session.side
+}
+
+fn configure_online_run(config: &mut RunConfig, seed: u64, mode: OnlineMode, profile: Profile) {
+    *config = RunConfig {
+        versus: mode == OnlineMode::Versus,
+        coop: match mode {
+            OnlineMode::Coop(gold_sharing) => Some(gold_sharing),
+            OnlineMode::Versus => None,
+        },
+        victory: match mode {
+            OnlineMode::Versus => VictoryCondition::Endless,
+            OnlineMode::Coop(_) => VictoryCondition::default(),
+        },
+        fixed_seed: Some(seed),
+        shared_profile: Some(profile),
+        ..default()
+    };
+}
+
+/// Waits for the other player, then starts the game picked by the host
+fn poll_lobby(
+    mut session: ResMut<NetSession>,
+    profile: Res<Profile>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for event in session.poll() {
+        match event {
+            NetEvent::Connected => {
+                session.status = "Waiting for the other player...".to_string();
+            }
+            NetEvent::Failed(error) => {
+                session.status = format!("Could not reach the relay: {}", error);
+                session.disconnect();
+            }
+            NetEvent::Relay(RelayLine::Side(side)) => session.side = side,
+            NetEvent::Relay(RelayLine::Start) if session.side == 0 => {
+                let seed = random_seed();
+                let mode = session.mode.unwrap_or(OnlineMode::Versus);
+                debug!("Hosting online {} game with seed {}", mode, seed);
+                session.send(&NetMessage::Hello {
+                    seed,
+                    mode,
+                    profile: profile.clone(),
+                });
+                configure_online_run(&mut config, seed, mode, profile.clone());
+                game_state.set(GameState::Game);
+            }
+            NetEvent::Relay(RelayLine::Start) => {
+                session.status = "Waiting for the host to pick the game...".to_string();
+            }
+            NetEvent::Relay(RelayLine::Message(NetMessage::Hello {
+                seed,
+                mode,
+                profile,
+            })) => {
+                debug!("Joining online {} game with seed {}", mode, seed);
+                configure_online_run(&mut config, seed, mode, profile);
+                game_state.set(GameState::Game);
+            }
+            // the host may act before this machine enters the game
+            NetEvent::Relay(RelayLine::Message(message)) => {
+                session.receive(message);
+            }
+            NetEvent::Relay(RelayLine::Left) | NetEvent::Closed => {
+                session.status = "The other player left.".to_string();
+                session.disconnect();
+            }
+        }
+    }
+}
+
+fn receive_game_messages(
+    mut session: ResMut<NetSession>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    for event in session.poll() {
+        match event {
+            NetEvent::Relay(RelayLine::Message(message)) => {
+                let step = match message {
+                    NetMessage::Checksum { step, .. } => step,
+                    _ => session.step,
+                };
+                if session.receive(message) {
+                    message_event_writer.send(desync_message(step));
+                }
+            }
+            NetEvent::Relay(RelayLine::Left) | NetEvent::Closed => {
+                session.disconnect();
+                message_event_writer.send(MessageEvent {
+                    message: "The other player left, both heroes are yours now.".to_string(),
+                    ..default()
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Events the input sends for the local hero
+#[derive(SystemParam)]
+pub struct LocalActionEvents<'w, 's> {
+    move_events: EventReader<'w, 's, MovePieceEvent>,
+    attack_events: EventReader<'w, 's, AttackPieceEvent>,
+    buy_events: EventReader<'w, 's, BuyShopUpgrade>,
+    refresh_events: EventReader<'w, 's, RefreshShop>,
+    shop_actions: EventReader<'w, 's, ShopAction>,
+    draft_choices: EventReader<'w, 's, DraftChoice>,
+    use_events: EventReader<'w, 's, UseConsumable>,
+}
+
+impl LocalActionEvents<'_, '_> {
+    /// Actions of the hero sent since the last read
+    pub fn read(&mut self, player: Entity) -> Vec<PlayerAction> {
+        // the drafts are answered before anything else on the turn
+        let mut actions: Vec<PlayerAction> = self
+            .draft_choices
+            .read()
+            .map(|choice| PlayerAction::Draft(*choice))
+            .collect();
+        actions.extend(
+            self.buy_events
+                .read()
+                .map(|event| PlayerAction::Buy(event.0)),
+        );
+        actions.extend(
+            self.shop_actions
+                .read()
+                .map(|action| PlayerAction::Shop(*action)),
+        );
+        actions.extend(
+            self.use_events
+                .read()
+                .map(|event| PlayerAction::Use(event.0)),
+        );
+        // buying refreshes the shop for free
+        actions.extend(
+            self.refresh_events
+                .read()
+                .filter(|event| event.cost > 0)
+                .map(|_| PlayerAction::Refresh),
+        );
+        let destination = self
+            .move_events
+            .read()
+            .filter(|event| event.entity == player)
+            .map(|event| event.destination)
+            .last();
+        let attacked = self
+            .attack_events
+            .read()
+            .filter(|event| event.attacker == player)
+            .count()
+            > 0;
+        if let Some(destination) = destination {
+            actions.push(PlayerAction::Move {
+                x: destination.x,
+                y: destination.y,
+            });
+        } else if attacked {
+            actions.push(PlayerAction::Attack);
+        }
+        actions
+    }
+}
+
+/// Sends the actions of the local hero, read from the events the input sent
+fn send_local_actions(
+    session: Res<NetSession>,
+    player: Query<Entity, With<Player>>,
+    mut local_actions: LocalActionEvents,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    for action in local_actions.read(player) {
+        session.send(&NetMessage::Action {
+            step: session.step,
+            action,
+        });
+    }
+}
+
+/// Events an action of the hero playing is sent through, the same as the local input
+#[derive(SystemParam)]
+pub struct ActionEventWriters<'w, 's> {
+    move_event_writer: EventWriter<'w, MovePieceEvent>,
+    attack_event_writer: EventWriter<'w, AttackPieceEvent>,
+    buy_event_writer: EventWriter<'w, BuyShopUpgrade>,
+    refresh_event_writer: EventWriter<'w, RefreshShop>,
+    shop_action_writer: EventWriter<'w, ShopAction>,
+    draft_choice_writer: EventWriter<'w, DraftChoice>,
+    use_event_writer: EventWriter<'w, UseConsumable>,
+    player: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static BoardPosition,
+            &'static Attack,
+            &'static Upgrades,
+            Option<&'static Promoted>,
+        ),
+        With<Player>,
+    >,
+    pieces_query: Query<
+        'w,
+        's,
+        (Entity, &'static BoardPosition, &'static Team),
+        (With<Piece>, Without<Player>),
+    >,
+    next_state: ResMut<'w, NextState<TurnState>>,
+    balance: Res<'w, Balance>,
+    roster: Res<'w, EnemyRoster>,
+}
+
+impl ActionEventWriters<'_, '_> {
+    /// Plays an action of the hero as if it was entered on this machine
+    pub fn play(&mut self, action: PlayerAction) {
+        let Ok((player_entity, player_position, damage, player_upgrades, promoted)) =
+            self.player.get_single()
+        else {
+            return;
+        };
+        let tile_position = match action {
+            PlayerAction::Buy(slot) => {
+                self.buy_event_writer.send(BuyShopUpgrade(slot));
+                return;
+            }
+            PlayerAction::Refresh => {
+                self.refresh_event_writer.send(RefreshShop {
+                    cost: self.balance.refresh_shop_cost,
+                });
+                return;
+            }
+            PlayerAction::Shop(shop_action) => {
+                self.shop_action_writer.send(shop_action);
+                return;
+            }
+            PlayerAction::Draft(choice) => {
+                self.draft_choice_writer.send(choice);
+                return;
+            }
+            PlayerAction::Use(slot) => {
+                self.use_event_writer.send(UseConsumable(slot));
+                return;
+            }
+            PlayerAction::Move { x, y } => BoardPosition::new(x, y),
+            PlayerAction::Attack => None,
+        };
+        let all_pieces_positions = self.pieces_query.iter().map(|(_, pos, _)| *pos).collect();
+        let enemy_pieces_positions = self
+            .pieces_query
+            .iter()
+            .filter(|(_, _, &team)| team == Team::Enemy)
+            .map(|(_, pos, _)| *pos)
+            .collect();
+        let movement_types = hero_movement_types(player_upgrades, promoted);
+
+        if let Some(tile_position) = tile_position {
+            if try_move_player(
+                &mut self.move_event_writer,
+                tile_position,
+                player_entity,
+                player_position,
+                &movement_types,
+                &all_pieces_positions,
+                &enemy_pieces_positions,
+            ) {
+                self.next_state.set(TurnState::PlayerAnimation);
+                return;
+            }
+        }
+        attack_from_tile(
+            &movement_types,
+            player_position,
+            &all_pieces_positions,
+            &enemy_pieces_positions,
+            &self.pieces_query,
+            &mut self.attack_event_writer,
+            player_entity,
+            damage,
+            &mut self.next_state,
+            &self.roster,
+        );
+    }
+}
+
+/// Plays one action of the other player through the same events as the local input
+fn replay_remote_action(
+    mut session: ResMut<NetSession>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    mut action_writers: ActionEventWriters,
+) {
+    let Some(&(step, action)) = session.pending.front() else {
+        return;
+    };
+    // the action waits for this machine to reach the same player input
+    if step > session.step {
+        return;
+    }
+    session.pending.pop_front();
+    if step < session.step && !session.desync {
+        session.desync = true;
+        message_event_writer.send(desync_message(step));
+    }
+    debug!("Replaying {:?} of the other player", action);
+    action_writers.play(action);
+}
+
+/// Both players compare the board each time a hero gets to act
+fn exchange_checksum(
+    mut session: ResMut<NetSession>,
+    pieces: Query<(&BoardPosition, &Health), With<Piece>>,
+    turn_info: Res<TurnInfo>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    session.step += 1;
+    let step = session.step;
+    let checksum = board_checksum(
+        turn_info.number,
+        pieces
+            .iter()
+            .map(|(position, health)| PieceSnapshot {
+                x: position.x,
+                y: position.y,
+                health: (health.value * 100.0).round() as i64,
+            })
+            .collect(),
+    );
+    session.send(&NetMessage::Checksum { step, checksum });
+    if session.record_checksum(step, checksum, true) {
+        message_event_writer.send(desync_message(step));
+    }
+}
+
+fn disconnect(mut session: ResMut<NetSession>) {
+    session.disconnect();
+}
+
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetSession>()
+            .add_systems(Update, poll_lobby.run_if(in_state(GameState::Lobby)))
+            .add_systems(
+                OnEnter(TurnState::PlayerInput),
+                exchange_checksum.run_if(in_state(GameState::Game).and(is_online)),
+            )
+            .add_systems(
+                Update,
+                receive_game_messages
+                    .run_if(in_state(GameState::Game))
+                    .run_if(is_online),
+            )
+            .add_systems(
+                Update,
+                (
+                    // reads the events of the input in the frame they are sent
+                    send_local_actions
+                        .after(click_tile_update_player_position)
+                        .after(tap_tile_update_player_position)
+                        // consumables taking a turn end the player input in the frame they are used
+                        .after(keyboard::use_consumable)
+                        .after(press_hotbar_buttons)
+                        .run_if(is_local_turn),
+                    replay_remote_action
+                        .run_if(not(is_local_turn))
+                        .run_if(in_state(GamePauseState::Playing)),
+                )
+                    .after(receive_game_messages)
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(is_online),
+            )
+            // a finished game or a rematch is played offline
+            .add_systems(OnExit(GameState::Game), disconnect)
+            .add_systems(OnEnter(GameState::MainMenu), disconnect);
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_logic::{coop::GoldSharing, profile::Profile},
+    ui::{draft::DraftChoice, shop::ShopAction},
+};
+
+/// Game the host of an online match picked
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OnlineMode {
+    Versus,
+    Coop(GoldSharing),
+}
+
+impl fmt::Display for OnlineMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OnlineMode::Versus => write!(f, "Versus"),
+            OnlineMode::Coop(gold_sharing) => write!(f, "Co-op ({})", gold_sharing),
+        }
+    }
+}
+
+/// Action of a player, replayed by the other one through the same events
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PlayerAction {
+    Move {
+        x: i32,
+        y: i32,
+    },
+    /// Attack from the current tile
+    Attack,
+    /// Buy the upgrade in this slot of the shop
+    Buy(usize),
+    Refresh,
+    Shop(ShopAction),
+    Draft(DraftChoice),
+    /// Use the consumable in this inventory slot
+    Use(usize),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum NetMessage {
+    /// Sent by the host, everything else follows from the seed and its profile
+    Hello {
+        seed: u64,
+        mode: OnlineMode,
+        profile: Profile,
+    },
+    /// Action taken during the player input of this step
+    Action { step: usize, action: PlayerAction },
+    /// State of the board when entering the player input of this step
+    Checksum { step: usize, checksum: u64 },
+}
+
+impl NetMessage {
+    /// Single line of RON
+    pub fn encode(&self) -> String {
+        ron::to_string(self).expect("Messages serialize")
+    }
+
+    pub fn decode(line: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(line)
+    }
+}
+
+/// Line received from the relay server
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelayLine {
+    Side(usize),
+    Start,
+    /// The other player disconnected
+    Left,
+    Message(NetMessage),
+}
+
+impl RelayLine {
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        match line {
+            "START" => Some(RelayLine::Start),
+            "LEFT" => Some(RelayLine::Left),
+            _ => match line.strip_prefix("SIDE ") {
+                Some(side) => side.parse().ok().map(RelayLine::Side),
+                None => NetMessage::decode(line).ok().map(RelayLine::Message),
+            },
+        }
+    }
+}
+
+/// Piece state compared between the players
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PieceSnapshot {
+    pub x: i32,
+    pub y: i32,
+    /// Hundredths of health points
+    pub health: i64,
+}
+
+/// Hash of the board, independent of the order the pieces are listed in
+pub fn board_checksum(turn: usize, mut pieces: Vec<PieceSnapshot>) -> u64 {
+    pieces.sort_unstable();
+    let mut hasher = DefaultHasher::new();
+    turn.hash(&mut hasher);
+    pieces.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_round_trip() {
+        let messages = [
+            NetMessage::Hello {
+                seed: 42,
+                mode: OnlineMode::Coop(GoldSharing::Split),
+                profile: Profile::default(),
+            },
+            NetMessage::Action {
+                step: 3,
+                action: PlayerAction::Move { x: 2, y: -1 },
+            },
+            NetMessage::Action {
+                step: 4,
+                action: PlayerAction::Buy(1),
+            },
+            NetMessage::Checksum {
+                step: 4,
+                checksum: u64::MAX,
+            },
+        ];
+        for message in messages {
+            let line = message.encode();
+            assert!(!line.contains('\n'));
+            assert_eq!(
+                RelayLine::parse(&format!("{}\n", line)),
+                Some(RelayLine::Message(message))
+            );
+        }
+        assert_eq!(RelayLine::parse("SIDE 1"), Some(RelayLine::Side(1)));
+        assert_eq!(RelayLine::parse("START"), Some(RelayLine::Start));
+        assert_eq!(RelayLine::parse("garbage"), None);
+    }
+
+    #[test]
+    fn test_board_checksum() {
+        let a = PieceSnapshot {
+            x: 1,
+            y: 2,
+            health: 300,
+        };
+        let b = PieceSnapshot {
+            x: 4,
+            y: 4,
+            health: 1000,
+        };
+        assert_eq!(board_checksum(5, vec![a, b]), board_checksum(5, vec![b, a]));
+        assert_ne!(board_checksum(5, vec![a, b]), board_checksum(6, vec![a, b]));
+        let hurt = PieceSnapshot { health: 250, ..a };
+        assert_ne!(
+            board_checksum(5, vec![a, b]),
+            board_checksum(5, vec![hurt, b])
+        );
+    }
+}
@@ -4,23 +4,32 @@ use bevy::{prelude::*, utils::HashSet};
 
 use crate::{
     board::position::BoardPosition,
-    globals::{ATTACK_ANIMATION_DURATION, TILE_SIZE, UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER},
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+    },
+    globals::{ATTACK_ANIMATION_DURATION, TILE_SIZE},
     graphics::spritesheet::SpriteSheetAtlas,
     input::click_tile::send_attack_event,
     states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+    utils::rng::GameRng,
 };
 
 use super::{
     common::{Piece, PieceState, Team},
     damage::Attack,
+    enemies::roster::EnemyRoster,
     health::{Health, PieceHealthChangeEvent},
     movement::MovePieceAnimationEndEvent,
     movement_type::MovementType,
     player::{
+        consumables::{hero_movement_types, Promoted},
         spawn::Player,
         upgrades::{
             data::Upgrades,
-            unique_upgrades::{apply_unique_upgrades, SideEffect},
+            unique_upgrades::{
+                ability_tier, apply_unique_upgrades, convert_enemy::Converted, SideEffect,
+            },
         },
     },
 };
@@ -35,7 +44,20 @@ pub struct AttackPieceEvent {
     pub sprite_index: Option<usize>,
     pub movement_type: MovementType,
     pub delay: Option<f32>,
-    pub upgrades_applied: bool,
+    pub source: AttackSource,
+}
+
+/// What started an attack, the unique upgrade damage bonus is only added to direct attacks
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AttackSource {
+    Direct,
+    /// Queen repeat of another attack
+    Repeat,
+    /// Chain, pierce or random target of another attack
+    ///
+    /// It triggers its own tier effects like a direct attack, so chained attacks chain
+    /// again, except for pierce follow-ups of bishop attacks, which pierce on their own.
+    SideEffect,
 }
 
 #[derive(Eq, PartialEq, Clone)]
@@ -64,18 +86,31 @@ pub fn attack_piece_system(
     mut pieces: Query<(&BoardPosition, &mut PieceState, &Upgrades, &Health), With<Piece>>,
     mut commands: Commands,
     mut side_effect_event_writer: EventWriter<SideEffect>,
+    names: Query<&Name>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    mut rng: ResMut<GameRng>,
+    converted: Query<&Converted>,
+    balance: Res<Balance>,
 ) {
     for event in attack_event_reader.read() {
         let (attacker_pos, mut attacker_state, upgrades, _) =
             pieces.get_mut(event.attacker).unwrap();
         let mut event = (*event).clone();
+        let base_damage = event.damage;
+        let inherited_repeat_chance = converted
+            .get(event.attacker)
+            .map_or(0.0, |converted| converted.repeat_chance);
         // handle unique upgrades
         apply_unique_upgrades(
             &mut event,
             upgrades,
+            &balance,
+            inherited_repeat_chance,
             &mut side_effect_event_writer,
             &mut commands,
+            &mut rng.0,
         );
+        log_attack(&names, &mut log_writer, &event, base_damage);
 
         if let Some(sprite_index) = event.sprite_index {
             *attacker_state = PieceState::AttackingWithNewSprite;
@@ -111,6 +146,30 @@ pub fn attack_piece_system(
     }
 }
 
+fn log_attack(
+    names: &Query<&Name>,
+    log_writer: &mut EventWriter<CombatLogEvent>,
+    event: &AttackPieceEvent,
+    base_damage: f32,
+) {
+    let (Ok(attacker), Ok(target)) = (names.get(event.attacker), names.get(event.target)) else {
+        return;
+    };
+    let bonus = event.damage - base_damage;
+    let modifiers = if bonus > 0.0 {
+        format!(" ({} + {} unique)", base_damage, bonus)
+    } else {
+        String::new()
+    };
+    log_writer.send(CombatLogEvent::new(
+        LogCategory::Attack,
+        format!(
+            "{} hits {} with {:?} for {}{}",
+            attacker, target, event.movement_type, event.damage, modifiers
+        ),
+    ));
+}
+
 pub fn attack_piece_animation_system(
     mut query: Query<(&mut Transform, &mut PieceState), (With<Piece>, Without<Player>)>,
     time: Res<Time>,
@@ -356,6 +415,7 @@ pub fn attack_from_tile(
     player_entity: Entity,
     damage: &Attack,
     next_state: &mut ResMut<NextState<TurnState>>,
+    roster: &EnemyRoster,
 ) -> bool {
     let mut attack = false;
     let mut delay = 0.0;
@@ -385,6 +445,7 @@ pub fn attack_from_tile(
                 movement_type,
                 Some(delay),
                 current_tile_position,
+                roster,
             );
             next_state.set(TurnState::PlayerAnimation);
         }
@@ -398,13 +459,21 @@ pub fn attack_from_tile(
 pub fn on_move_animation_end_attack_system(
     mut attack_event_writer: EventWriter<AttackPieceEvent>,
     mut pieces_with_attack: Query<
-        (Entity, &BoardPosition, &Attack, &Upgrades, &mut PieceState),
+        (
+            Entity,
+            &BoardPosition,
+            &Attack,
+            &Upgrades,
+            Option<&Promoted>,
+            &mut PieceState,
+        ),
         With<AttackAfterMove>,
     >,
     mut pieces_without_attack: Query<&mut PieceState, (With<Piece>, Without<AttackAfterMove>)>,
     pieces_query: Query<(Entity, &BoardPosition, &Team), (With<Piece>, Without<Player>)>,
     mut next_state: ResMut<NextState<TurnState>>,
     mut move_piece_animation_end_events: EventReader<MovePieceAnimationEndEvent>,
+    roster: Res<EnemyRoster>,
 ) {
     let all_pieces_positions = pieces_query.iter().map(|(_, pos, _)| *pos).collect();
     let enemy_pieces_positions = pieces_query
@@ -414,7 +483,7 @@ pub fn on_move_animation_end_attack_system(
         .collect();
     for event in move_piece_animation_end_events.read() {
         let mut to_attack = false;
-        for (piece_entity, piece_position, damage, upgrades, mut piece_state) in
+        for (piece_entity, piece_position, damage, upgrades, promoted, mut piece_state) in
             pieces_with_attack.iter_mut()
         {
             if event.entity != piece_entity {
@@ -422,7 +491,7 @@ pub fn on_move_animation_end_attack_system(
             }
 
             to_attack = true;
-            let movement_types = upgrades.get_movement_types_set();
+            let movement_types = hero_movement_types(upgrades, promoted);
             let mut attacked = attack_from_tile(
                 &movement_types,
                 piece_position,
@@ -433,13 +502,23 @@ pub fn on_move_animation_end_attack_system(
                 piece_entity,
                 damage,
                 &mut next_state,
+                &roster,
             );
-            if let Some(count) = upgrades.get_movement_types_count().get(&MovementType::King) {
-                if *count >= UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER {
-                    // king unique ability, attack from original position
-                    let attacked_2 = attack_from_tile(
+            let king_tier = upgrades
+                .get_movement_types_count()
+                .get(&MovementType::King)
+                .map_or(0, |count| ability_tier(*count));
+            if king_tier > 0 {
+                // king unique ability, attack from original position,
+                // and from every square on the way from the second tier
+                let mut tiles = vec![event.origin];
+                if king_tier >= 2 {
+                    tiles.extend(event.origin.squares_between(piece_position));
+                }
+                for tile in tiles {
+                    let attacked_again = attack_from_tile(
                         &HashSet::from([MovementType::King]),
-                        &event.origin,
+                        &tile,
                         &all_pieces_positions,
                         &enemy_pieces_positions,
                         &pieces_query,
@@ -447,8 +526,9 @@ pub fn on_move_animation_end_attack_system(
                         piece_entity,
                         damage,
                         &mut next_state,
+                        &roster,
                     );
-                    attacked = attacked || attacked_2;
+                    attacked = attacked || attacked_again;
                 }
             }
             if !attacked {
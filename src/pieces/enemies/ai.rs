@@ -3,11 +3,12 @@ use bevy::{prelude::*, utils::HashSet};
 use crate::{
     board::position::BoardPosition,
     pieces::{
-        attack::AttackPieceEvent,
+        attack::{AttackPieceEvent, AttackSource},
         common::{Piece, PieceState, Team},
         damage::Attack,
         health::DeathAnimation,
         movement::MovePieceEvent,
+        movement_type::MovementType,
         player::upgrades::data::Upgrades,
     },
     states::turn_state::TurnState,
@@ -91,6 +92,25 @@ pub fn ai_system_player(
     turn_state.set(TurnState::PlayerAnimationAI);
 }
 
+/// Every attack an AI piece makes from its position, with the movement type it attacks with
+pub fn ai_attacks(
+    position: &BoardPosition,
+    movement_types: &HashSet<MovementType>,
+    all_pieces_positions: &HashSet<BoardPosition>,
+    enemy_pieces_positions: &HashSet<BoardPosition>,
+) -> Vec<(MovementType, BoardPosition)> {
+    movement_types
+        .iter()
+        .flat_map(|movement_type| {
+            movement_type
+                .get_valid_moves(position, all_pieces_positions, enemy_pieces_positions)
+                .valid_attacks
+                .into_iter()
+                .map(move |attack| (movement_type.clone(), attack))
+        })
+        .collect()
+}
+
 fn execute_ai(
     mut ai_pieces: Query<
         (
@@ -144,30 +164,34 @@ fn execute_ai(
         // we make the assumption that there will be enemies with more than one movement type
         let mut moves = HashSet::new();
         for movement_type in enemy_movement_types.iter() {
-            let response = movement_type.get_valid_moves(
-                ally_pos,
-                &all_pieces_positions,
-                &enemy_pieces_positions,
+            moves.extend(
+                movement_type
+                    .get_valid_moves(ally_pos, &all_pieces_positions, &enemy_pieces_positions)
+                    .valid_moves,
             );
-            moves.extend(response.valid_moves);
-            for attack in response.valid_attacks {
-                attack_events.send(AttackPieceEvent {
-                    attacker: ally_entity,
-                    target: enemy_pieces_positions_and_entities
-                        .iter()
-                        .find(|(_, pos)| *pos == attack)
-                        .unwrap()
-                        .0,
-                    damage: ally_damage.0.upgraded_value,
-                    destination: attack,
-                    sprite_index: None,
-                    delay: None,
-                    movement_type: movement_type.clone(),
-                    upgrades_applied: false,
-                    origin: *ally_pos,
-                });
-                has_attacked = true;
-            }
+        }
+        for (movement_type, attack) in ai_attacks(
+            ally_pos,
+            &enemy_movement_types,
+            &all_pieces_positions,
+            &enemy_pieces_positions,
+        ) {
+            attack_events.send(AttackPieceEvent {
+                attacker: ally_entity,
+                target: enemy_pieces_positions_and_entities
+                    .iter()
+                    .find(|(_, pos)| *pos == attack)
+                    .unwrap()
+                    .0,
+                damage: ally_damage.0.upgraded_value,
+                destination: attack,
+                sprite_index: None,
+                delay: None,
+                movement_type,
+                source: AttackSource::Direct,
+                origin: *ally_pos,
+            });
+            has_attacked = true;
         }
 
         if !has_attacked {
@@ -194,12 +218,8 @@ fn execute_ai(
                             .iter()
                             .map(|player_pos| pos.distance(*player_pos))
                             .min();
-                        if let Some(min_distance) = min_distance {
-                            min_distance
-                        } else {
-                            // if there are no enemy pieces, disregard this logic
-                            i32::MAX
-                        }
+                        // if there are no enemy pieces, disregard this logic
+                        min_distance.unwrap_or(i32::MAX)
                     }
                 })
                 .expect("There should be at least one valid move");
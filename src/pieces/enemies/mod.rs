@@ -1,17 +1,21 @@
 use bevy::prelude::*;
 use pawn::promotion::promotion_system;
+use roster::{EnemyRoster, PieceColor};
 
-use crate::states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState};
+use crate::{
+    game_logic::balance::Balance,
+    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+    utils::rng::Weighted,
+};
 
-use super::movement_type::MovementType;
+use super::{
+    movement_type::MovementType,
+    player::consumables::{skip_enemy_turn, time_stopped},
+};
 
 pub mod ai;
-pub mod bishop;
-pub mod king;
-pub mod knight;
 pub mod pawn;
-pub mod queen;
-pub mod rook;
+pub mod roster;
 pub mod spawn;
 
 #[derive(Clone)]
@@ -20,26 +24,72 @@ pub struct PieceInfo {
     pub damage: f32,
     pub sprite_index: usize,
     pub movement_type: MovementType,
+    pub color: PieceColor,
     pub spawn_weight: f32,
     pub spawn_turn: usize,
     pub value: usize,
     pub name: String,
 }
 
+impl Weighted for PieceInfo {
+    fn weight(&self) -> f32 {
+        self.spawn_weight
+    }
+}
+
+impl EnemyRoster {
+    /// Every enemy that can spawn, with the values of the balance
+    pub fn pieces(&self, balance: &Balance) -> Vec<PieceInfo> {
+        self.enemies
+            .iter()
+            .map(|enemy| enemy.piece_info(balance))
+            .collect()
+    }
+
+    /// Piece moving like this in the color
+    pub fn find_piece(
+        &self,
+        movement_type: &MovementType,
+        color: PieceColor,
+        balance: &Balance,
+    ) -> Option<PieceInfo> {
+        self.find(movement_type, color)
+            .map(|enemy| enemy.piece_info(balance))
+    }
+
+    /// Score value of an enemy, by name
+    pub fn value(&self, name: &str) -> Option<usize> {
+        self.enemies
+            .iter()
+            .find(|enemy| enemy.name == name)
+            .map(|enemy| enemy.value)
+    }
+}
+
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugins(roster::EnemyRosterPlugin);
+        app.add_systems(OnEnter(GameState::Game), spawn::spawn_puzzle_enemies);
         app.add_systems(
             Update,
-            (spawn::spawn_enemies, promotion_system)
+            (
+                spawn::spawn_enemies,
+                spawn::spawn_boss.after(spawn::spawn_enemies),
+                promotion_system,
+            )
                 .run_if(in_state(GameState::Game))
                 .run_if(in_state(TurnState::EnemySpawn))
                 .run_if(in_state(GamePauseState::Playing)),
         );
         app.add_systems(
             Update,
-            ai::ai_system_enemy
+            (
+                ai::ai_system_enemy.run_if(not(time_stopped)),
+                skip_enemy_turn.run_if(time_stopped),
+            )
+                .chain()
                 .run_if(in_state(TurnState::EnemyAI))
                 .run_if(in_state(GamePauseState::Playing))
                 .run_if(in_state(GameState::Game)),
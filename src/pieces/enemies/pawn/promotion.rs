@@ -2,13 +2,19 @@ use bevy::prelude::*;
 
 use crate::{
     board::position::BoardPosition,
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+        daily::Mutator,
+        run_config::RunConfig,
+    },
     pieces::{
         common::Piece,
-        enemies::king::{BLACK_KING_INFO, WHITE_KING_INFO},
+        enemies::roster::{EnemyRoster, PieceColor},
         movement_type::MovementType,
         player::{
             experience::PieceValue,
-            upgrades::data::{get_movement_upgrade, Effect, Upgrades},
+            upgrades::data::{Effect, UpgradeCatalog, Upgrades},
         },
     },
 };
@@ -24,7 +30,24 @@ pub fn promotion_system(
         ),
         With<Piece>,
     >,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    catalog: Res<UpgradeCatalog>,
 ) {
+    // white pawns become black pieces and black pawns white ones
+    let promoted_type = if config.has_mutator(Mutator::PawnsPromoteToQueens) {
+        MovementType::Queen
+    } else {
+        MovementType::King
+    };
+    let black_info = roster
+        .find_piece(&promoted_type, PieceColor::Black, &balance)
+        .expect("Roster has a black piece of every movement type");
+    let white_info = roster
+        .find_piece(&promoted_type, PieceColor::White, &balance)
+        .expect("Roster has a white piece of every movement type");
     for (mut upgrades, pos, mut value, mut sprite, mut name) in pieces.iter_mut() {
         let movement_types = upgrades.get_movement_types_set();
         let is_white_pawn =
@@ -32,45 +55,53 @@ pub fn promotion_system(
         let is_black_pawn =
             movement_types.contains(&MovementType::BlackPawn) && movement_types.len() == 1;
         if pos.y == 7 && is_white_pawn {
-            debug!("Promoting white pawn to black king");
+            debug!("Promoting white pawn to black {:?}", promoted_type);
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Spawn,
+                format!("{} promotes to {} at {}", *name, black_info.name, pos),
+            ));
             *upgrades = Upgrades(
                 upgrades
                     .0
                     .iter()
                     .map(|u| {
                         if u.effect == Effect::MovementType(vec![MovementType::WhitePawn]) {
-                            get_movement_upgrade(&MovementType::King)
+                            catalog.get_movement_upgrade(&promoted_type)
                         } else {
                             u.clone()
                         }
                     })
                     .collect(),
             );
-            sprite.texture_atlas.as_mut().unwrap().index = BLACK_KING_INFO.sprite_index;
-            *name = Name::new("King".to_string());
+            sprite.texture_atlas.as_mut().unwrap().index = black_info.sprite_index;
+            *name = Name::new(black_info.name.clone());
 
             *value = PieceValue {
-                value: BLACK_KING_INFO.value,
+                value: black_info.value,
             };
         } else if pos.y == 0 && is_black_pawn {
-            debug!("Promoting black pawn to white king");
+            debug!("Promoting black pawn to white {:?}", promoted_type);
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Spawn,
+                format!("{} promotes to {} at {}", *name, white_info.name, pos),
+            ));
             *upgrades = Upgrades(
                 upgrades
                     .0
                     .iter()
                     .map(|u| {
                         if u.effect == Effect::MovementType(vec![MovementType::BlackPawn]) {
-                            get_movement_upgrade(&MovementType::King)
+                            catalog.get_movement_upgrade(&promoted_type)
                         } else {
                             u.clone()
                         }
                     })
                     .collect(),
             );
-            sprite.texture_atlas.as_mut().unwrap().index = WHITE_KING_INFO.sprite_index;
-            *name = Name::new("King".to_string());
+            sprite.texture_atlas.as_mut().unwrap().index = white_info.sprite_index;
+            *name = Name::new(white_info.name.clone());
             *value = PieceValue {
-                value: WHITE_KING_INFO.value,
+                value: white_info.value,
             };
         }
     }
@@ -0,0 +1,258 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    game_logic::balance::Balance, globals::ENEMY_ROSTER_ASSET_PATH,
+    pieces::movement_type::MovementType,
+};
+
+use super::PieceInfo;
+
+/// Embedded so the enemies exist before the asset server loads the file
+const ROSTER_FILE: &str = include_str!("../../../assets/enemies.roster.ron");
+
+/// Side of the board a piece belongs to in chess, pawns promote to the other one
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PieceColor {
+    White,
+    Black,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum SpawnTurn {
+    Turn(usize),
+    /// King spawn turn of the balance config
+    KingSpawnTurn,
+}
+
+/// Pieces promotions, the boss and the shop icons use
+const REQUIRED_PIECES: [(MovementType, PieceColor); 12] = [
+    (MovementType::WhitePawn, PieceColor::White),
+    (MovementType::BlackPawn, PieceColor::Black),
+    (MovementType::King, PieceColor::White),
+    (MovementType::King, PieceColor::Black),
+    (MovementType::Queen, PieceColor::White),
+    (MovementType::Queen, PieceColor::Black),
+    (MovementType::Rook, PieceColor::White),
+    (MovementType::Rook, PieceColor::Black),
+    (MovementType::Bishop, PieceColor::White),
+    (MovementType::Bishop, PieceColor::Black),
+    (MovementType::Knight, PieceColor::White),
+    (MovementType::Knight, PieceColor::Black),
+];
+
+/// Enemy as written in the roster file
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnemyDefinition {
+    pub name: String,
+    pub color: PieceColor,
+    pub movement_type: MovementType,
+    pub sprite_index: usize,
+    /// Enemy base health of the balance config if not set
+    #[serde(default)]
+    pub health: Option<f32>,
+    pub damage: f32,
+    pub spawn_weight: f32,
+    pub spawn_turn: SpawnTurn,
+    pub value: usize,
+}
+
+impl EnemyDefinition {
+    pub fn piece_info(&self, balance: &Balance) -> PieceInfo {
+        PieceInfo {
+            health: self.health.unwrap_or(balance.enemy_base_health),
+            damage: self.damage,
+            sprite_index: self.sprite_index,
+            movement_type: self.movement_type.clone(),
+            color: self.color,
+            spawn_weight: self.spawn_weight,
+            spawn_turn: match self.spawn_turn {
+                SpawnTurn::Turn(turn) => turn,
+                SpawnTurn::KingSpawnTurn => balance.king_spawn_turn,
+            },
+            value: self.value,
+            name: self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidRoster {
+    Parse(String),
+    Missing(MovementType, PieceColor),
+    NonPositiveWeight(String),
+}
+
+impl fmt::Display for InvalidRoster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidRoster::Parse(error) => write!(f, "invalid roster file: {}", error),
+            InvalidRoster::Missing(movement_type, color) => {
+                write!(f, "no {:?} piece moves like {:?}", color, movement_type)
+            }
+            InvalidRoster::NonPositiveWeight(name) => {
+                write!(
+                    f,
+                    "{} needs a positive spawn weight, remove it to stop its spawns",
+                    name
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidRoster {}
+
+/// Enemies that can spawn, loaded from a `.roster.ron` file
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct EnemyRoster {
+    pub enemies: Vec<EnemyDefinition>,
+}
+
+impl EnemyRoster {
+    pub fn parse(content: &str) -> Result<Self, InvalidRoster> {
+        let roster: Self =
+            ron::from_str(content).map_err(|error| InvalidRoster::Parse(error.to_string()))?;
+        roster.validate()?;
+        Ok(roster)
+    }
+
+    fn validate(&self) -> Result<(), InvalidRoster> {
+        if let Some(enemy) = self.enemies.iter().find(|enemy| enemy.spawn_weight <= 0.0) {
+            return Err(InvalidRoster::NonPositiveWeight(enemy.name.clone()));
+        }
+        for (movement_type, color) in REQUIRED_PIECES {
+            if self.find(&movement_type, color).is_none() {
+                return Err(InvalidRoster::Missing(movement_type, color));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn find(
+        &self,
+        movement_type: &MovementType,
+        color: PieceColor,
+    ) -> Option<&EnemyDefinition> {
+        self.enemies
+            .iter()
+            .find(|enemy| enemy.movement_type == *movement_type && enemy.color == color)
+    }
+}
+
+/// Embedded enemies, replaced once the asset server loads the file
+impl Default for EnemyRoster {
+    fn default() -> Self {
+        EnemyRoster::parse(ROSTER_FILE).expect("Roster file is valid")
+    }
+}
+
+#[derive(Default)]
+struct EnemyRosterLoader;
+
+impl AssetLoader for EnemyRosterLoader {
+    type Asset = EnemyRoster;
+    type Settings = ();
+    type Error = InvalidRoster;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<EnemyRoster, InvalidRoster> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| InvalidRoster::Parse(error.to_string()))?;
+        let content =
+            std::str::from_utf8(&bytes).map_err(|error| InvalidRoster::Parse(error.to_string()))?;
+        // rejected files keep the previous enemies
+        EnemyRoster::parse(content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["roster.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct EnemyRosterHandle(Handle<EnemyRoster>);
+
+fn load_enemy_roster(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(EnemyRosterHandle(
+        asset_server.load(ENEMY_ROSTER_ASSET_PATH),
+    ));
+}
+
+/// Swaps in the enemies of the file, also when it changes on disk in debug builds
+fn reload_roster(
+    mut events: EventReader<AssetEvent<EnemyRoster>>,
+    rosters: Res<Assets<EnemyRoster>>,
+    handle: Res<EnemyRosterHandle>,
+    mut enemy_roster: ResMut<EnemyRoster>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        if let Some(roster) = rosters.get(*id) {
+            *enemy_roster = roster.clone();
+            debug!("Enemies loaded from {}", ENEMY_ROSTER_ASSET_PATH);
+        }
+    }
+}
+
+pub struct EnemyRosterPlugin;
+
+impl Plugin for EnemyRosterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<EnemyRoster>()
+            .init_asset_loader::<EnemyRosterLoader>()
+            .init_resource::<EnemyRoster>()
+            .add_systems(Startup, load_enemy_roster)
+            .add_systems(
+                Update,
+                reload_roster.run_if(on_event::<AssetEvent<EnemyRoster>>),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roster_file_is_valid() {
+        let roster = EnemyRoster::parse(ROSTER_FILE).unwrap();
+        assert_eq!(roster.enemies.len(), 12);
+        let king = roster.find(&MovementType::King, PieceColor::Black).unwrap();
+        let balance = Balance::default();
+        let piece_info = king.piece_info(&balance);
+        assert_eq!(piece_info.spawn_turn, balance.king_spawn_turn);
+        assert_eq!(piece_info.health, balance.enemy_base_health);
+
+        let no_black_rook = ROSTER_FILE.replacen(
+            "color: Black,\n            movement_type: Rook",
+            "color: White,\n            movement_type: Rook",
+            1,
+        );
+        assert_eq!(
+            EnemyRoster::parse(&no_black_rook).err(),
+            Some(InvalidRoster::Missing(
+                MovementType::Rook,
+                PieceColor::Black
+            ))
+        );
+    }
+}
@@ -1,75 +1,78 @@
 use crate::{
     board::position::{BoardPosition, PositionAvailable},
-    globals::{ENEMY_Z_INDEX, PER_TURN_ENEMY_SPAWN_COUNT, TARGET_NUM_ENEMIES},
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+        daily::Mutator,
+        run_config::RunConfig,
+        victory::{VictoryCondition, VictoryProgress},
+    },
+    globals::{
+        BOSS_COLOR, BOSS_DAMAGE_MULTIPLIER, BOSS_HEALTH_MULTIPLIER, BOSS_SPAWN_TURN, ELITE_COLOR,
+        ELITE_DAMAGE_MULTIPLIER, ELITE_HEALTH_MULTIPLIER, ELITE_SPAWN_CHANCE, ENEMY_Z_INDEX,
+        PER_TURN_ENEMY_SPAWN_COUNT,
+    },
     graphics::spritesheet::SpriteSheetAtlas,
     pieces::{
         common::{Piece, Team},
         damage::Attack,
-        enemies::{
-            bishop::{BLACK_BISHOP_INFO, WHITE_BISHOP_INFO},
-            king::{BLACK_KING_INFO, WHITE_KING_INFO},
-            knight::{BLACK_KNIGHT_INFO, WHITE_KNIGHT_INFO},
-            queen::{BLACK_QUEEN_INFO, WHITE_QUEEN_INFO},
-            rook::{BLACK_ROOK_INFO, WHITE_ROOK_INFO},
-        },
         health::Health,
         healthbar::spawn_healthbar,
         movement_type::MovementType,
         player::{
             experience::PieceValue,
-            upgrades::data::{get_movement_upgrade, Upgrades},
+            upgrades::data::{UpgradeCatalog, Upgrades},
         },
     },
     states::{
         game_state::GameState,
         turn_state::{TurnInfo, TurnState},
     },
+    utils::rng::{sample_weighted, GameRng},
 };
 use bevy::{prelude::*, utils::HashSet};
 use rand::prelude::*;
 
 use super::{
-    pawn::{BLACK_PAWN_INFO, WHITE_PAWN_INFO},
+    roster::{EnemyRoster, PieceColor},
     PieceInfo,
 };
 
 #[derive(Component)]
 pub struct AIControlled;
 
-fn get_random_piece_info(turn_info: &Res<TurnInfo>) -> PieceInfo {
-    let pieces = [
-        WHITE_PAWN_INFO.clone(),
-        BLACK_PAWN_INFO.clone(),
-        WHITE_KING_INFO.clone(),
-        BLACK_KING_INFO.clone(),
-        WHITE_QUEEN_INFO.clone(),
-        BLACK_QUEEN_INFO.clone(),
-        WHITE_ROOK_INFO.clone(),
-        BLACK_ROOK_INFO.clone(),
-        WHITE_BISHOP_INFO.clone(),
-        BLACK_BISHOP_INFO.clone(),
-        WHITE_KNIGHT_INFO.clone(),
-        BLACK_KNIGHT_INFO.clone(),
-    ];
+/// Stronger version of an enemy, worth the same score
+#[derive(Component)]
+pub struct Elite;
+
+/// King to kill to win the run
+#[derive(Component)]
+pub struct Boss;
+
+fn get_random_piece_info(
+    rng: &mut impl Rng,
+    turn_info: &Res<TurnInfo>,
+    config: &RunConfig,
+    roster: &EnemyRoster,
+    balance: &Balance,
+) -> Option<PieceInfo> {
+    let pieces = roster.pieces(balance);
+    let ascension = config.ascension();
+    let all_knights = config.has_mutator(Mutator::AllKnights);
 
     let spawnable_pieces = pieces
         .iter()
-        .filter(|p| turn_info.number >= p.spawn_turn)
+        .filter(|p| {
+            if all_knights {
+                p.movement_type == MovementType::Knight
+            } else {
+                turn_info.number >= ascension.spawn_turn(p.spawn_turn)
+            }
+        })
+        .cloned()
         .collect::<Vec<_>>();
 
-    let total_weight = spawnable_pieces.iter().map(|p| p.spawn_weight).sum::<f32>();
-    let mut random_value = rand::thread_rng().gen_range(0.0..total_weight);
-
-    for &piece in spawnable_pieces.iter() {
-        if random_value < piece.spawn_weight {
-            return piece.clone();
-        }
-        random_value -= piece.spawn_weight;
-    }
-
-    // This should never happen if the weights are positive
-    warn!("Logic error: no piece selected randomly, defaulting to last piece");
-    pieces.last().unwrap().clone()
+    sample_weighted(rng, 1, &spawnable_pieces)?.pop()
 }
 
 pub fn spawn_enemies(
@@ -80,14 +83,27 @@ pub fn spawn_enemies(
     piece_position_query: Query<&BoardPosition, With<Piece>>,
     mut next_turn_state: ResMut<NextState<TurnState>>,
     turn_info: Res<TurnInfo>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    mut rng: ResMut<GameRng>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    catalog: Res<UpgradeCatalog>,
 ) {
+    // puzzles and the tutorial are played with their own pieces only
+    if config.is_scripted() {
+        next_turn_state.set(TurnState::PlayerInput);
+        return;
+    }
     debug!("Spawning enemies");
+    let ascension = config.ascension();
 
     let num_enemies = pieces
         .iter()
         .filter(|(_, &team)| team == Team::Enemy)
         .count();
-    let enemies_to_spawn = TARGET_NUM_ENEMIES
+    let enemies_to_spawn = ascension
+        .target_num_enemies(&balance)
         .saturating_sub(num_enemies)
         .clamp(0, PER_TURN_ENEMY_SPAWN_COUNT);
     let mut occupied_positions = HashSet::from_iter(piece_position_query.iter().copied());
@@ -98,53 +114,206 @@ pub fn spawn_enemies(
         PositionAvailable::Right,
     ];
     for _ in 0..enemies_to_spawn {
-        let piece_info = get_random_piece_info(&turn_info);
-        let tile_pos = get_spawn_position(&piece_info, &occupied_positions, &all_positions);
+        let Some(mut piece_info) =
+            get_random_piece_info(&mut rng.0, &turn_info, &config, &roster, &balance)
+        else {
+            warn!("No enemy can spawn on turn {}", turn_info.number);
+            break;
+        };
+        let elite = turn_info.number >= ascension.elite_spawn_turn()
+            && rng.0.gen::<f32>() < ELITE_SPAWN_CHANCE;
+        let (health_multiplier, damage_multiplier, color) = if elite {
+            (
+                ELITE_HEALTH_MULTIPLIER,
+                ELITE_DAMAGE_MULTIPLIER,
+                ELITE_COLOR,
+            )
+        } else {
+            (1.0, 1.0, Color::WHITE)
+        };
+        piece_info.health *= ascension.enemy_stat_multiplier() * health_multiplier;
+        piece_info.damage *= ascension.enemy_stat_multiplier() * damage_multiplier;
+        let tile_pos =
+            get_spawn_position(&mut rng.0, &piece_info, &occupied_positions, &all_positions);
 
         occupied_positions.insert(tile_pos);
-        let global_position = tile_pos.as_global_position().extend(ENEMY_Z_INDEX);
-        let enemy = commands
-            .spawn((
-                Piece,
-                Sprite {
-                    image: asset_server.load("custom/spritesheet.png"),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: atlas_layout.handle.clone(),
-                        index: piece_info.sprite_index,
-                    }),
-                    ..default()
-                },
-                Transform::from_translation(global_position),
-                tile_pos,
-                Health::new(piece_info.health),
-                Attack::new(piece_info.damage),
-                Upgrades(vec![get_movement_upgrade(&piece_info.movement_type)]),
-                Team::Enemy,
-                Name::new(piece_info.name),
-                StateScoped(GameState::Game),
-                AIControlled,
-                PieceValue {
-                    value: piece_info.value,
-                },
-            ))
-            .id();
-
-        let healthbars = spawn_healthbar(&mut commands, &asset_server, &atlas_layout.handle);
-        commands.entity(enemy).add_children(&healthbars);
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::Spawn,
+            format!(
+                "{}{} spawns at {}",
+                if elite { "Elite " } else { "" },
+                piece_info.name,
+                tile_pos
+            ),
+        ));
+        let enemy = spawn_enemy(
+            &mut commands,
+            &asset_server,
+            &atlas_layout,
+            &catalog,
+            &piece_info,
+            tile_pos,
+            color,
+        );
+        if elite {
+            commands.entity(enemy).insert(Elite);
+        }
     }
     next_turn_state.set(TurnState::PlayerInput);
 }
 
+/// Spawns an enemy with the stats of its piece info
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    atlas_layout: &SpriteSheetAtlas,
+    catalog: &UpgradeCatalog,
+    piece_info: &PieceInfo,
+    tile_pos: BoardPosition,
+    color: Color,
+) -> Entity {
+    let global_position = tile_pos.as_global_position().extend(ENEMY_Z_INDEX);
+    let enemy = commands
+        .spawn((
+            Piece,
+            Sprite {
+                image: asset_server.load("custom/spritesheet.png"),
+                texture_atlas: Some(TextureAtlas {
+                    layout: atlas_layout.handle.clone(),
+                    index: piece_info.sprite_index,
+                }),
+                color,
+                ..default()
+            },
+            Transform::from_translation(global_position),
+            tile_pos,
+            Health::new(piece_info.health),
+            Attack::new(piece_info.damage),
+            Upgrades(vec![catalog.get_movement_upgrade(&piece_info.movement_type)]),
+            Team::Enemy,
+            Name::new(piece_info.name.clone()),
+            StateScoped(GameState::Game),
+            AIControlled,
+            PieceValue {
+                value: piece_info.value,
+            },
+        ))
+        .id();
+
+    let healthbars = spawn_healthbar(commands, asset_server, &atlas_layout.handle);
+    commands.entity(enemy).add_children(&healthbars);
+    enemy
+}
+
+/// Spawns the boss king once when the run has to be won by killing it
+pub fn spawn_boss(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    piece_position_query: Query<&BoardPosition, With<Piece>>,
+    turn_info: Res<TurnInfo>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    mut rng: ResMut<GameRng>,
+    config: Res<RunConfig>,
+    mut progress: ResMut<VictoryProgress>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    catalog: Res<UpgradeCatalog>,
+) {
+    if config.victory != VictoryCondition::KillBossKing
+        || progress.boss_spawned
+        || turn_info.number < BOSS_SPAWN_TURN
+    {
+        return;
+    }
+    progress.boss_spawned = true;
+
+    let ascension = config.ascension();
+    let mut piece_info = roster
+        .find_piece(&MovementType::King, PieceColor::White, &balance)
+        .expect("Roster has a white king");
+    piece_info.health *= ascension.enemy_stat_multiplier() * BOSS_HEALTH_MULTIPLIER;
+    piece_info.damage *= ascension.enemy_stat_multiplier() * BOSS_DAMAGE_MULTIPLIER;
+    let occupied_positions = HashSet::from_iter(piece_position_query.iter().copied());
+    let tile_pos = get_spawn_position(
+        &mut rng.0,
+        &piece_info,
+        &occupied_positions,
+        &[
+            PositionAvailable::Top,
+            PositionAvailable::Bottom,
+            PositionAvailable::Left,
+            PositionAvailable::Right,
+        ],
+    );
+    log_writer.send(CombatLogEvent::new(
+        LogCategory::Spawn,
+        format!("Boss {} spawns at {}", piece_info.name, tile_pos),
+    ));
+    let boss = spawn_enemy(
+        &mut commands,
+        &asset_server,
+        &atlas_layout,
+        &catalog,
+        &piece_info,
+        tile_pos,
+        BOSS_COLOR,
+    );
+    commands.entity(boss).insert(Boss);
+}
+
+/// Places the enemies of the puzzle being played
+pub fn spawn_puzzle_enemies(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    catalog: Res<UpgradeCatalog>,
+) {
+    let Some(puzzle) = config.puzzle() else {
+        return;
+    };
+    debug!("Spawning enemies of puzzle {}", puzzle.name);
+    for enemy in puzzle.enemies.iter() {
+        let (Some(piece_info), Some(tile_pos)) = (
+            enemy.piece_info(&roster, &balance),
+            BoardPosition::new(enemy.position.0, enemy.position.1),
+        ) else {
+            continue;
+        };
+        spawn_enemy(
+            &mut commands,
+            &asset_server,
+            &atlas_layout,
+            &catalog,
+            &piece_info,
+            tile_pos,
+            Color::WHITE,
+        );
+    }
+}
+
 fn get_spawn_position(
+    rng: &mut impl Rng,
     piece_info: &PieceInfo,
     occupied_positions: &HashSet<BoardPosition>,
     all_positions: &[PositionAvailable],
 ) -> BoardPosition {
     if piece_info.movement_type == MovementType::WhitePawn {
-        BoardPosition::get_random_position_limited(occupied_positions, &[PositionAvailable::Bottom])
+        BoardPosition::get_random_position_limited(
+            rng,
+            occupied_positions,
+            &[PositionAvailable::Bottom],
+        )
     } else if piece_info.movement_type == MovementType::BlackPawn {
-        BoardPosition::get_random_position_limited(occupied_positions, &[PositionAvailable::Top])
+        BoardPosition::get_random_position_limited(
+            rng,
+            occupied_positions,
+            &[PositionAvailable::Top],
+        )
     } else {
-        BoardPosition::get_random_position_limited(occupied_positions, all_positions)
+        BoardPosition::get_random_position_limited(rng, occupied_positions, all_positions)
     }
 }
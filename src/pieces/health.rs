@@ -5,6 +5,7 @@ use rand::prelude::*;
 
 use crate::{
     board::highlight::HighlightCache,
+    game_logic::combat_log::{CombatLogEvent, LogCategory},
     globals::{
         DEATH_ANIMATION_DURATION, HEALTH_CHANGE_TEXT_ANIMATION_DURATION,
         HEALTH_CHANGE_TEXT_ANIMATION_SPEED, HEALTH_CHANGE_TEXT_FONT_SIZE,
@@ -16,7 +17,7 @@ use crate::{
 use super::{
     common::Team,
     player::{
-        spawn::Player,
+        spawn::{Hero, Player},
         upgrades::{
             stats::{Stat, StatVariant},
             unique_upgrades::{block::Block, immortal::Immortal},
@@ -75,6 +76,26 @@ impl Health {
     }
 }
 
+/// Damages the piece unless a block absorbs the whole hit, returns whether it did
+pub fn take_hit(health: &mut Health, block: &mut usize, damage: f32) -> bool {
+    if *block > 0 {
+        *block -= 1;
+        health.take_damage(0.0);
+        return true;
+    }
+    health.take_damage(damage);
+    false
+}
+
+/// Whether a piece dies from the hits it takes, in order
+pub fn killed_by(health: f32, mut block: usize, damages: &[f32]) -> bool {
+    let mut health = Health::new(health);
+    for damage in damages {
+        take_hit(&mut health, &mut block, *damage);
+    }
+    health.is_dead()
+}
+
 #[derive(Component)]
 pub struct TextAnimation {
     pub timer: Timer,
@@ -159,29 +180,36 @@ pub fn spawn_health_change_text(
 
 pub fn health_change_system(
     mut health_change_event_reader: EventReader<PieceHealthChangeEvent>,
-    mut health_query: Query<(&mut Health, &mut Block)>,
+    mut health_query: Query<(&mut Health, &mut Block, &Name)>,
+    mut log_writer: EventWriter<CombatLogEvent>,
 ) {
     for event in health_change_event_reader.read() {
         if event.change < 0.0 {
-            if let Ok((mut health, mut block)) = health_query.get_mut(event.entity) {
-                if block.amount > 0 {
-                    block.amount -= 1;
-                    health.take_damage(0.0);
+            if let Ok((mut health, mut block, name)) = health_query.get_mut(event.entity) {
+                if take_hit(&mut health, &mut block.amount, -event.change) {
+                    log_writer.send(CombatLogEvent::new(
+                        LogCategory::SideEffect,
+                        format!("{}'s Block absorbs {} damage", name, -event.change),
+                    ));
                     return;
                 }
-                health.take_damage(-event.change);
             }
-        } else if let Ok((mut health, _)) = health_query.get_mut(event.entity) {
+        } else if let Ok((mut health, _, _)) = health_query.get_mut(event.entity) {
             health.heal(event.change);
         }
     }
 }
 
-// death of player is handled by game logic
+// death of player and versus heroes is handled by game logic
 pub fn death_system(
     health_query: Query<
         (&Health, Entity, &Name),
-        (Without<DeathAnimation>, Without<Player>, Without<Immortal>),
+        (
+            Without<DeathAnimation>,
+            Without<Player>,
+            Without<Hero>,
+            Without<Immortal>,
+        ),
     >,
     mut commands: Commands,
     mut death_event_writer: EventWriter<PieceDeathEvent>,
@@ -238,3 +266,21 @@ pub fn health_change_text_animation(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_absorbs_whole_hits() {
+        assert!(killed_by(3.0, 0, &[1.0, 2.0]));
+        assert!(!killed_by(3.0, 1, &[5.0, 2.0]));
+        assert!(killed_by(3.0, 1, &[0.5, 2.0, 1.0]));
+
+        let mut health = Health::new(3.0);
+        let mut block = 1;
+        assert!(take_hit(&mut health, &mut block, 2.0));
+        assert!(!take_hit(&mut health, &mut block, 2.0));
+        assert_eq!((health.value, block), (1.0, 0));
+    }
+}
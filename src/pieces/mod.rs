@@ -9,3 +9,4 @@ pub mod movement;
 pub mod movement_type;
 pub mod player;
 pub mod plugin;
+pub mod preview;
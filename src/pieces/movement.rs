@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     board::{highlight::HighlightCache, position::BoardPosition},
+    game_logic::coop::Coop,
     globals::TWEEN_MOVE_ANIMATION_SPEED,
     states::turn_state::TurnState,
 };
@@ -123,12 +124,18 @@ pub fn all_player_ai_idle(
 pub fn is_player_idle(
     mut moves: Query<&mut PieceState, With<Player>>,
     mut turn_state: ResMut<NextState<TurnState>>,
+    coop: Res<Coop>,
 ) {
     if moves
         .iter()
         .all(|state| matches!(state, PieceState::AttackEnded))
     {
-        turn_state.set(TurnState::PlayerAI);
+        // the other co-op hero acts before the allies and enemies
+        if coop.next_hero.is_some() {
+            turn_state.set(TurnState::PlayerInput);
+        } else {
+            turn_state.set(TurnState::PlayerAI);
+        }
         moves.iter_mut().for_each(|mut state| {
             *state = PieceState::Idle;
         });
@@ -1,12 +1,10 @@
 use crate::board::position::BoardPosition;
 use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
 
-use super::enemies::{
-    bishop::WHITE_BISHOP_INFO, king::WHITE_KING_INFO, knight::WHITE_KNIGHT_INFO,
-    pawn::WHITE_PAWN_INFO, queen::WHITE_QUEEN_INFO, rook::WHITE_ROOK_INFO,
-};
+use super::enemies::roster::{EnemyRoster, PieceColor};
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum MovementType {
     WhitePawn,
     BlackPawn,
@@ -114,16 +112,16 @@ impl MovementType {
         }
     }
 
-    pub fn sprite_index(&self) -> usize {
-        match self {
-            MovementType::WhitePawn => WHITE_PAWN_INFO.sprite_index,
-            MovementType::BlackPawn => WHITE_PAWN_INFO.sprite_index,
-            MovementType::Knight => WHITE_KNIGHT_INFO.sprite_index,
-            MovementType::Bishop => WHITE_BISHOP_INFO.sprite_index,
-            MovementType::Rook => WHITE_ROOK_INFO.sprite_index,
-            MovementType::Queen => WHITE_QUEEN_INFO.sprite_index,
-            MovementType::King => WHITE_KING_INFO.sprite_index,
-        }
+    pub fn sprite_index(&self, roster: &EnemyRoster) -> usize {
+        // both pawns show the white one
+        let movement_type = match self {
+            MovementType::BlackPawn => &MovementType::WhitePawn,
+            movement_type => movement_type,
+        };
+        roster
+            .find(movement_type, PieceColor::White)
+            .expect("Roster has a white piece of every movement type")
+            .sprite_index
     }
     fn pawn_moves(
         &self,
@@ -279,7 +277,7 @@ impl MovementType {
 #[cfg(test)]
 mod tests {
 
-    use crate::pieces::player::upgrades::data::{get_movement_upgrade, Upgrades};
+    use crate::pieces::player::upgrades::data::{UpgradeCatalog, Upgrades};
 
     use super::*;
 
@@ -293,8 +291,10 @@ mod tests {
             BoardPosition::new(5, 5).unwrap(),
         ]);
         let enemies_positions = HashSet::from_iter(vec![BoardPosition::new(5, 5).unwrap()]);
-        let movement_types =
-            Upgrades(vec![get_movement_upgrade(&MovementType::King)]).get_movement_types_set();
+        let movement_types = Upgrades(vec![
+            UpgradeCatalog::default().get_movement_upgrade(&MovementType::King)
+        ])
+        .get_movement_types_set();
         let valid_moves = movement_types
             .get(&MovementType::King)
             .unwrap()
@@ -312,8 +312,10 @@ mod tests {
             BoardPosition::new(5, 5).unwrap(),
         ]);
         let enemies_positions = HashSet::from_iter(vec![BoardPosition::new(5, 5).unwrap()]);
-        let movement_types =
-            Upgrades(vec![get_movement_upgrade(&MovementType::WhitePawn)]).get_movement_types_set();
+        let movement_types = Upgrades(vec![
+            UpgradeCatalog::default().get_movement_upgrade(&MovementType::WhitePawn)
+        ])
+        .get_movement_types_set();
         let valid_moves = movement_types
             .get(&MovementType::WhitePawn)
             .unwrap()
@@ -0,0 +1,281 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+    utils::HashSet,
+};
+use serde::Deserialize;
+
+use crate::{
+    game_logic::balance::Balance,
+    globals::{CHARACTERS_ASSET_PATH, PLAYER_ATLAS_INDEX, PLAYER_DAMAGE, STARTING_GOLD},
+    pieces::{movement_type::MovementType, player::upgrades::unique_upgrades::block::Block},
+    states::{game_state::GameState, turn_state::TurnState},
+};
+
+use super::spawn::Player;
+
+/// Embedded so the characters exist before the asset server loads the file
+const CHARACTERS_FILE: &str = include_str!("../../../assets/heroes.characters.ron");
+
+/// Unique ability of a character, kept for the whole run
+#[derive(Component, Deserialize, Clone, Debug, PartialEq)]
+pub enum Passive {
+    None,
+    /// Block refilled to this amount at the start of every player turn
+    PermanentBlock(usize),
+    /// Chained attacks hit their targets twice
+    DoubleChain,
+}
+
+impl Passive {
+    pub fn description(&self) -> String {
+        match self {
+            Passive::None => "No passive".to_string(),
+            Passive::PermanentBlock(amount) => {
+                format!("Starts every turn with Block({})", amount)
+            }
+            Passive::DoubleChain => "Chained attacks hit twice".to_string(),
+        }
+    }
+}
+
+fn default_attack() -> f32 {
+    PLAYER_DAMAGE
+}
+
+fn default_atlas_index() -> usize {
+    PLAYER_ATLAS_INDEX
+}
+
+/// Starting setup of the player, as written in the characters file
+#[derive(Deserialize, Clone, Debug)]
+pub struct Character {
+    pub name: String,
+    pub description: String,
+    pub movement_types: Vec<MovementType>,
+    /// Health above the player health of the balance config
+    #[serde(default)]
+    pub health_offset: f32,
+    #[serde(default = "default_attack")]
+    pub attack: f32,
+    pub movement_type_limit: usize,
+    #[serde(default = "default_atlas_index")]
+    pub atlas_index: usize,
+    /// Tint of the sprite, in red, green and blue
+    pub color: (f32, f32, f32),
+    /// Gold on top of the starting gold
+    #[serde(default)]
+    pub extra_gold: usize,
+    pub passive: Passive,
+}
+
+impl Character {
+    pub fn health(&self, balance: &Balance) -> f32 {
+        balance.player_health + self.health_offset
+    }
+
+    pub fn starting_gold(&self) -> usize {
+        STARTING_GOLD + self.extra_gold
+    }
+
+    pub fn sprite_color(&self) -> Color {
+        let (red, green, blue) = self.color;
+        Color::srgb(red, green, blue)
+    }
+}
+
+pub const DEFAULT_CHARACTER: &str = "Classic";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidCharacters {
+    Parse(String),
+    MissingDefault,
+    Duplicate(String),
+    NoMovement(String),
+    /// Starts with more movement types than it can own
+    OverLimit(String),
+}
+
+impl fmt::Display for InvalidCharacters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidCharacters::Parse(error) => write!(f, "invalid characters file: {}", error),
+            InvalidCharacters::MissingDefault => {
+                write!(f, "the {} character is missing", DEFAULT_CHARACTER)
+            }
+            InvalidCharacters::Duplicate(name) => write!(f, "{} is defined twice", name),
+            InvalidCharacters::NoMovement(name) => write!(f, "{} starts without moves", name),
+            InvalidCharacters::OverLimit(name) => {
+                write!(f, "{} starts with more movement types than its limit", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidCharacters {}
+
+/// Characters the player can start a run with, loaded from a `.characters.ron` file
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct CharacterRoster {
+    pub characters: Vec<Character>,
+}
+
+impl CharacterRoster {
+    pub fn parse(content: &str) -> Result<Self, InvalidCharacters> {
+        let roster: Self =
+            ron::from_str(content).map_err(|error| InvalidCharacters::Parse(error.to_string()))?;
+        roster.validate()?;
+        Ok(roster)
+    }
+
+    fn validate(&self) -> Result<(), InvalidCharacters> {
+        if self.get(DEFAULT_CHARACTER).is_none() {
+            return Err(InvalidCharacters::MissingDefault);
+        }
+        let mut names = HashSet::new();
+        for character in self.characters.iter() {
+            if !names.insert(&character.name) {
+                return Err(InvalidCharacters::Duplicate(character.name.clone()));
+            }
+            if character.movement_types.is_empty() {
+                return Err(InvalidCharacters::NoMovement(character.name.clone()));
+            }
+            let distinct_types: HashSet<&MovementType> = character.movement_types.iter().collect();
+            if distinct_types.len() > character.movement_type_limit {
+                return Err(InvalidCharacters::OverLimit(character.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Character> {
+        self.characters
+            .iter()
+            .find(|character| character.name == name)
+    }
+
+    /// Character played when the selected one is locked or missing
+    pub fn default_character(&self) -> &Character {
+        self.get(DEFAULT_CHARACTER)
+            .expect("Characters are validated")
+    }
+}
+
+/// Embedded characters, replaced once the asset server loads the file
+impl Default for CharacterRoster {
+    fn default() -> Self {
+        CharacterRoster::parse(CHARACTERS_FILE).expect("Characters file is valid")
+    }
+}
+
+#[derive(Default)]
+struct CharacterRosterLoader;
+
+impl AssetLoader for CharacterRosterLoader {
+    type Asset = CharacterRoster;
+    type Settings = ();
+    type Error = InvalidCharacters;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<CharacterRoster, InvalidCharacters> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| InvalidCharacters::Parse(error.to_string()))?;
+        let content = std::str::from_utf8(&bytes)
+            .map_err(|error| InvalidCharacters::Parse(error.to_string()))?;
+        // rejected files keep the previous characters
+        CharacterRoster::parse(content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["characters.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct CharacterRosterHandle(Handle<CharacterRoster>);
+
+fn load_characters(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CharacterRosterHandle(
+        asset_server.load(CHARACTERS_ASSET_PATH),
+    ));
+}
+
+/// Swaps in the characters of the file, also when it changes on disk in debug builds
+fn reload_characters(
+    mut events: EventReader<AssetEvent<CharacterRoster>>,
+    rosters: Res<Assets<CharacterRoster>>,
+    handle: Res<CharacterRosterHandle>,
+    mut characters: ResMut<CharacterRoster>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        if let Some(roster) = rosters.get(*id) {
+            *characters = roster.clone();
+            debug!("Characters loaded from {}", CHARACTERS_ASSET_PATH);
+        }
+    }
+}
+
+fn refill_permanent_block(mut player: Query<(&Passive, &mut Block), With<Player>>) {
+    for (passive, mut block) in player.iter_mut() {
+        if let Passive::PermanentBlock(amount) = passive {
+            block.amount = block.amount.max(*amount);
+        }
+    }
+}
+
+pub struct CharacterPlugin;
+
+impl Plugin for CharacterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CharacterRoster>()
+            .init_asset_loader::<CharacterRosterLoader>()
+            .init_resource::<CharacterRoster>()
+            .add_systems(Startup, load_characters)
+            .add_systems(
+                Update,
+                reload_characters.run_if(on_event::<AssetEvent<CharacterRoster>>),
+            )
+            .add_systems(
+                OnEnter(TurnState::PlayerInput),
+                refill_permanent_block.run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_characters_file_is_valid() {
+        let roster = CharacterRoster::parse(CHARACTERS_FILE).unwrap();
+        assert_eq!(roster.characters.len(), 4);
+        assert_eq!(roster.default_character().name, DEFAULT_CHARACTER);
+        let commander = roster.get("Pawn Commander").unwrap();
+        assert_eq!(commander.starting_gold(), STARTING_GOLD + 20);
+        assert_eq!(commander.attack, PLAYER_DAMAGE);
+
+        let over_limit =
+            CHARACTERS_FILE.replacen("movement_type_limit: 2", "movement_type_limit: 1", 1);
+        assert_eq!(
+            CharacterRoster::parse(&over_limit).err(),
+            Some(InvalidCharacters::OverLimit(DEFAULT_CHARACTER.to_string()))
+        );
+    }
+}
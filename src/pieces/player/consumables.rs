@@ -0,0 +1,444 @@
+use std::fmt;
+
+use bevy::{prelude::*, utils::HashSet};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::{highlight::HighlightCache, position::BoardPosition},
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+    },
+    globals::{BOARD_SIZE, INVENTORY_SIZE},
+    pieces::{
+        common::{Piece, PieceState, Team},
+        health::{Health, PieceDeathEvent, PieceHealthChangeEvent},
+        movement_type::MovementType,
+    },
+    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+    ui::shop::RefreshShop,
+    utils::rng::GameRng,
+};
+
+use super::{spawn::Player, upgrades::data::Upgrades};
+
+/// Movement type granted by the scroll of promotion, like a pawn reaching the last rank
+const PROMOTION_MOVEMENT_TYPE: MovementType = MovementType::Queen;
+
+/// One-shot item, bought in the shop or dropped by enemies
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Consumable {
+    HealingPotion,
+    /// Hits every enemy around the hero
+    Bomb,
+    /// Moves the hero to a random tile away from the enemies
+    TeleportScroll,
+    /// Skips the next enemy turn
+    TimeStop,
+    /// Refreshes the shop for free
+    RerollToken,
+    /// Moves like a queen for a few turns
+    PromotionScroll,
+}
+
+impl Consumable {
+    pub const ALL: [Consumable; 6] = [
+        Consumable::HealingPotion,
+        Consumable::Bomb,
+        Consumable::TeleportScroll,
+        Consumable::TimeStop,
+        Consumable::RerollToken,
+        Consumable::PromotionScroll,
+    ];
+
+    pub fn description(&self, balance: &Balance) -> String {
+        match self {
+            Consumable::HealingPotion => {
+                format!("Heals {} health", balance.healing_potion_amount)
+            }
+            Consumable::Bomb => format!(
+                "Deals {} damage to every adjacent enemy",
+                balance.bomb_damage
+            ),
+            Consumable::TeleportScroll => "Teleports to a tile away from the enemies".to_string(),
+            Consumable::TimeStop => format!("Enemies skip {} turn", balance.time_stop_turns),
+            Consumable::RerollToken => "Refreshes the shop for free".to_string(),
+            Consumable::PromotionScroll => format!(
+                "Move like a {:?} for {} turns",
+                PROMOTION_MOVEMENT_TYPE, balance.promotion_turns
+            ),
+        }
+    }
+
+    /// Whether using it ends the turn like a move, the others are free
+    pub fn takes_turn(&self) -> bool {
+        matches!(self, Consumable::Bomb | Consumable::TeleportScroll)
+    }
+}
+
+impl fmt::Display for Consumable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Consumable::HealingPotion => write!(f, "Healing Potion"),
+            Consumable::Bomb => write!(f, "Bomb"),
+            Consumable::TeleportScroll => write!(f, "Teleport Scroll"),
+            Consumable::TimeStop => write!(f, "Time Stop"),
+            Consumable::RerollToken => write!(f, "Reroll Token"),
+            Consumable::PromotionScroll => write!(f, "Scroll of Promotion"),
+        }
+    }
+}
+
+/// Consumables carried by a hero, shown in the hotbar
+#[derive(Component, Default, Clone, Debug)]
+pub struct Inventory(pub Vec<Consumable>);
+
+impl Inventory {
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= INVENTORY_SIZE
+    }
+
+    /// Returns whether there was room for the consumable
+    pub fn add(&mut self, consumable: Consumable) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.0.push(consumable);
+        true
+    }
+}
+
+/// Uses the consumable in this inventory slot, from the hotbar or the other online player
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct UseConsumable(pub usize);
+
+/// Enemy turns left to skip
+#[derive(Resource, Default)]
+pub struct TimeStop {
+    pub turns: usize,
+}
+
+pub fn time_stopped(time_stop: Res<TimeStop>) -> bool {
+    time_stop.turns > 0
+}
+
+/// Hero moving like a piece it has no upgrade of until the turns run out
+///
+/// It is not an upgrade, so it counts for neither the movement limit nor the ability tiers.
+#[derive(Component)]
+pub struct Promoted {
+    pub turns_left: usize,
+    pub movement_type: MovementType,
+}
+
+/// Movement types a hero moves and attacks with, its upgrades and its promotion
+pub fn hero_movement_types(
+    upgrades: &Upgrades,
+    promoted: Option<&Promoted>,
+) -> HashSet<MovementType> {
+    let mut movement_types = upgrades.get_movement_types_set();
+    movement_types.extend(promoted.map(|promoted| promoted.movement_type.clone()));
+    movement_types
+}
+
+/// Tiles of the 3x3 square around the hero
+pub fn bomb_area(center: &BoardPosition) -> Vec<BoardPosition> {
+    let mut area = Vec::new();
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            if let Some(position) = BoardPosition::new(center.x + dx, center.y + dy) {
+                area.push(position);
+            }
+        }
+    }
+    area
+}
+
+/// Empty tile away from the enemies if there is one, any empty tile otherwise
+fn teleport_destination(
+    rng: &mut impl Rng,
+    occupied: &HashSet<BoardPosition>,
+    enemies: &[BoardPosition],
+    safe_distance: i32,
+) -> Option<BoardPosition> {
+    let empty: Vec<BoardPosition> = (0..BOARD_SIZE)
+        .flat_map(|x| (0..BOARD_SIZE).filter_map(move |y| BoardPosition::new(x, y)))
+        .filter(|position| !occupied.contains(position))
+        .collect();
+    let safe: Vec<BoardPosition> = empty
+        .iter()
+        .filter(|position| {
+            enemies
+                .iter()
+                .all(|enemy| position.distance(*enemy) >= safe_distance)
+        })
+        .copied()
+        .collect();
+    if safe.is_empty() {
+        empty.choose(rng).copied()
+    } else {
+        safe.choose(rng).copied()
+    }
+}
+
+fn use_consumable(
+    mut events: EventReader<UseConsumable>,
+    mut commands: Commands,
+    mut player: Query<
+        (
+            Entity,
+            &mut Inventory,
+            &mut Health,
+            &mut PieceState,
+            &mut BoardPosition,
+            &mut Transform,
+            Option<&mut Promoted>,
+        ),
+        With<Player>,
+    >,
+    pieces: Query<(Entity, &BoardPosition, &Team), (With<Piece>, Without<Player>)>,
+    mut health_change_writer: EventWriter<PieceHealthChangeEvent>,
+    mut refresh_shop_writer: EventWriter<RefreshShop>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    mut time_stop: ResMut<TimeStop>,
+    mut rng: ResMut<GameRng>,
+    mut highlight_cache: ResMut<HighlightCache>,
+    mut next_state: ResMut<NextState<TurnState>>,
+    balance: Res<Balance>,
+) {
+    let Ok((
+        entity,
+        mut inventory,
+        mut health,
+        mut piece_state,
+        mut position,
+        mut transform,
+        mut promoted,
+    )) = player.get_single_mut()
+    else {
+        return;
+    };
+    for event in events.read() {
+        if event.0 >= inventory.0.len() {
+            continue;
+        }
+        let consumable = inventory.0.remove(event.0);
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::SideEffect,
+            format!("Used {}", consumable),
+        ));
+        match consumable {
+            Consumable::HealingPotion => health.heal(balance.healing_potion_amount),
+            Consumable::Bomb => {
+                let area = bomb_area(&position);
+                for (target, _, _) in pieces
+                    .iter()
+                    .filter(|(_, pos, &team)| team == Team::Enemy && area.contains(pos))
+                {
+                    health_change_writer.send(PieceHealthChangeEvent {
+                        entity: target,
+                        change: -balance.bomb_damage,
+                    });
+                }
+            }
+            Consumable::TeleportScroll => {
+                let occupied = pieces.iter().map(|(_, pos, _)| *pos).collect();
+                let enemies: Vec<BoardPosition> = pieces
+                    .iter()
+                    .filter(|(_, _, &team)| team == Team::Enemy)
+                    .map(|(_, pos, _)| *pos)
+                    .collect();
+                if let Some(destination) = teleport_destination(
+                    &mut rng.0,
+                    &occupied,
+                    &enemies,
+                    balance.teleport_safe_distance,
+                ) {
+                    *position = destination;
+                    transform.translation = destination
+                        .as_global_position()
+                        .extend(transform.translation.z);
+                    highlight_cache.invalidate();
+                }
+            }
+            Consumable::TimeStop => time_stop.turns += balance.time_stop_turns,
+            Consumable::RerollToken => {
+                refresh_shop_writer.send(RefreshShop { cost: 0 });
+            }
+            Consumable::PromotionScroll => {
+                if let Some(promoted) = promoted.as_mut() {
+                    promoted.turns_left += balance.promotion_turns;
+                } else {
+                    commands.entity(entity).insert(Promoted {
+                        turns_left: balance.promotion_turns,
+                        movement_type: PROMOTION_MOVEMENT_TYPE,
+                    });
+                    highlight_cache.invalidate();
+                }
+            }
+        }
+        if consumable.takes_turn() {
+            *piece_state = PieceState::AttackEnded;
+            next_state.set(TurnState::PlayerAnimation);
+            // the rest of the turn is lost
+            break;
+        }
+    }
+}
+
+/// Takes the promotion movement type back once its turns are played
+fn expire_promotion(
+    mut commands: Commands,
+    mut player: Query<(Entity, &mut Promoted), With<Player>>,
+    mut highlight_cache: ResMut<HighlightCache>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    for (entity, mut promoted) in player.iter_mut() {
+        promoted.turns_left = promoted.turns_left.saturating_sub(1);
+        if promoted.turns_left > 0 {
+            continue;
+        }
+        commands.entity(entity).remove::<Promoted>();
+        highlight_cache.invalidate();
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::SideEffect,
+            "The promotion wears off".to_string(),
+        ));
+    }
+}
+
+/// Moves on to the spawns while the time is stopped, instead of the enemy AI
+pub fn skip_enemy_turn(
+    mut time_stop: ResMut<TimeStop>,
+    mut turn_state: ResMut<NextState<TurnState>>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    time_stop.turns -= 1;
+    turn_state.set(TurnState::EnemySpawn);
+    log_writer.send(CombatLogEvent::new(
+        LogCategory::SideEffect,
+        "Time is stopped, the enemies skip their turn".to_string(),
+    ));
+}
+
+/// Killed enemies may drop a consumable into the inventory of the hero playing
+fn drop_consumables(
+    mut death_events: EventReader<PieceDeathEvent>,
+    teams: Query<&Team>,
+    mut player: Query<&mut Inventory, With<Player>>,
+    mut rng: ResMut<GameRng>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    balance: Res<Balance>,
+) {
+    let Ok(mut inventory) = player.get_single_mut() else {
+        return;
+    };
+    for event in death_events.read() {
+        if !matches!(teams.get(event.entity), Ok(Team::Enemy)) {
+            continue;
+        }
+        if !rng.0.gen_bool(balance.consumable_drop_chance) {
+            continue;
+        }
+        let consumable = *Consumable::ALL
+            .choose(&mut rng.0)
+            .expect("There are consumables");
+        if inventory.add(consumable) {
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Reward,
+                format!("Found a {}", consumable),
+            ));
+        }
+    }
+}
+
+fn reset_time_stop(mut time_stop: ResMut<TimeStop>) {
+    time_stop.turns = 0;
+}
+
+pub struct ConsumablesPlugin;
+
+impl Plugin for ConsumablesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeStop>()
+            .add_event::<UseConsumable>()
+            .add_systems(OnEnter(GameState::Game), reset_time_stop)
+            .add_systems(
+                Update,
+                use_consumable
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(in_state(GamePauseState::Playing))
+                    .run_if(on_event::<UseConsumable>),
+            )
+            .add_systems(
+                Update,
+                drop_consumables
+                    .run_if(in_state(GameState::Game))
+                    .run_if(on_event::<PieceDeathEvent>),
+            )
+            .add_systems(
+                OnExit(TurnState::PlayerAnimation),
+                expire_promotion.run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::player::upgrades::data::UpgradeCatalog;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_bomb_area_and_inventory() {
+        let center = BoardPosition::new(3, 3).unwrap();
+        assert_eq!(bomb_area(&center).len(), 8);
+        assert!(!bomb_area(&center).contains(&center));
+        let corner = BoardPosition::new(0, 0).unwrap();
+        assert_eq!(bomb_area(&corner).len(), 3);
+
+        let mut inventory = Inventory::default();
+        for _ in 0..INVENTORY_SIZE {
+            assert!(inventory.add(Consumable::Bomb));
+        }
+        assert!(!inventory.add(Consumable::HealingPotion));
+        assert_eq!(inventory.0.len(), INVENTORY_SIZE);
+    }
+
+    #[test]
+    fn test_teleport_avoids_enemies() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let enemy = BoardPosition::new(0, 0).unwrap();
+        let occupied = HashSet::from([enemy]);
+        let safe_distance = Balance::default().teleport_safe_distance;
+        for _ in 0..20 {
+            let destination =
+                teleport_destination(&mut rng, &occupied, &[enemy], safe_distance).unwrap();
+            assert!(destination.distance(enemy) >= safe_distance);
+        }
+    }
+
+    #[test]
+    fn test_promotion_adds_a_movement_type() {
+        let upgrades = Upgrades(vec![
+            UpgradeCatalog::default().get_movement_upgrade(&MovementType::King)
+        ]);
+        let promoted = Promoted {
+            turns_left: Balance::default().promotion_turns,
+            movement_type: PROMOTION_MOVEMENT_TYPE,
+        };
+        assert_eq!(
+            hero_movement_types(&upgrades, Some(&promoted)),
+            HashSet::from([MovementType::King, MovementType::Queen])
+        );
+        assert_eq!(
+            hero_movement_types(&upgrades, None),
+            HashSet::from([MovementType::King])
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
+    game_logic::combat_log::{CombatLogEvent, LogCategory},
     pieces::{common::Team, health::PieceDeathEvent},
     states::game_state::GameState,
 };
@@ -61,6 +62,7 @@ pub fn add_experience_on_death(
     piece_value_query: Query<(&PieceValue, &Team)>,
     mut player_level: ResMut<PlayerLevel>,
     mut player_level_up_events: EventWriter<PlayerLevelUpEvent>,
+    mut log_writer: EventWriter<CombatLogEvent>,
 ) {
     for event in piece_death_events.read() {
         let (piece_value, team) = piece_value_query.get(event.entity).unwrap();
@@ -68,7 +70,15 @@ pub fn add_experience_on_death(
             return;
         }
         player_level.add_experience(piece_value.value);
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::Reward,
+            format!("+{} XP", piece_value.value),
+        ));
         if player_level.level_up() {
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Reward,
+                format!("Level up! Reached level {}", player_level.level),
+            ));
             player_level_up_events.send(PlayerLevelUpEvent {
                 level: player_level.level,
             });
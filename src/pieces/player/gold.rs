@@ -2,6 +2,11 @@ use bevy::prelude::*;
 
 use crate::{
     board::position::BoardPosition,
+    game_logic::{
+        combat_log::{CombatLogEvent, LogCategory},
+        profile::Profile,
+        run_config::RunConfig,
+    },
     globals::{
         GOLD_ANIMATION_DURATION, GOLD_ANIMATION_SPEED, GOLD_FONT_SIZE, GOLD_Z_INDEX, STARTING_GOLD,
         UI_FONT,
@@ -10,7 +15,7 @@ use crate::{
     states::game_state::GameState,
 };
 
-use super::experience::PieceValue;
+use super::{character::CharacterRoster, experience::PieceValue};
 
 #[derive(Resource)]
 pub struct Gold {
@@ -34,22 +39,23 @@ pub fn spawn_gold(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut gold: ResMut<Gold>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    config: Res<RunConfig>,
 ) {
     death_event.read().for_each(|event| {
         let (enemy_value, enemy_position) = enemies.get(event.entity).unwrap();
+        let amount = config.ascension().gold_per_kill(enemy_value.value);
         let gold_position = enemy_position.as_global_position().extend(GOLD_Z_INDEX);
         commands.spawn((
             Name::new("PickedUpGold"),
             StateScoped(GameState::Game),
-            PickedUpGold {
-                amount: enemy_value.value,
-            },
+            PickedUpGold { amount },
             TextAnimation {
                 speed: GOLD_ANIMATION_SPEED,
                 timer: Timer::from_seconds(GOLD_ANIMATION_DURATION, TimerMode::Once),
                 ..default()
             },
-            Text2d(format!("+{}$", enemy_value.value)),
+            Text2d(format!("+{}$", amount)),
             TextFont {
                 font: asset_server.load(UI_FONT),
                 font_size: GOLD_FONT_SIZE,
@@ -58,13 +64,22 @@ pub fn spawn_gold(
             Transform::from_translation(gold_position),
             TextColor(Color::linear_rgb(0.0, 1.0, 0.0)),
         ));
-        gold.amount += enemy_value.value;
+        gold.amount += amount;
+        log_writer.send(CombatLogEvent::new(
+            LogCategory::Reward,
+            format!("+{} gold", amount),
+        ));
     });
 }
 
-pub fn reset_gold(mut gold: ResMut<Gold>) {
+pub fn reset_gold(
+    mut gold: ResMut<Gold>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    characters: Res<CharacterRoster>,
+) {
     debug!("Resetting gold");
-    gold.amount = STARTING_GOLD;
+    gold.amount = config.character(&profile, &characters).starting_gold();
 }
 
 pub struct GoldPlugin;
@@ -3,8 +3,11 @@ use spawn::spawn_player;
 
 use crate::states::game_state::GameState;
 
+pub mod character;
+pub mod consumables;
 pub mod experience;
 pub mod gold;
+pub mod relics;
 pub mod spawn;
 pub mod upgrades;
 
@@ -14,9 +17,13 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Game), spawn_player);
         app.add_plugins((
+            character::CharacterPlugin,
+            consumables::ConsumablesPlugin,
             experience::ExperiencePlugin,
             gold::GoldPlugin,
+            relics::RelicsPlugin,
             upgrades::unique_upgrades::UniqueUpgradesPlugin,
+            upgrades::data::UpgradeDataPlugin,
         ));
     }
 }
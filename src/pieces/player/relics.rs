@@ -0,0 +1,390 @@
+use std::fmt;
+
+use bevy::{ecs::event::EventCursor, prelude::*};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::position::BoardPosition,
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+    },
+    pieces::{
+        attack::{AttackPieceEvent, AttackSource},
+        common::{Piece, Team},
+        enemies::spawn::Boss,
+        health::{Health, PieceDeathEvent, PieceHealthChangeEvent},
+        movement::MovePieceEvent,
+    },
+    states::{game_state::GameState, turn_state::TurnState},
+    utils::rng::GameRng,
+};
+
+use super::{
+    consumables::bomb_area, experience::PlayerLevelUpEvent, gold::Gold, spawn::Player,
+    upgrades::unique_upgrades::convert_enemy::ConversionEndedEvent,
+};
+
+/// Game event a relic hooks
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RelicTrigger {
+    Attack,
+    HealthChange,
+    Death,
+    Move,
+    LevelUp,
+    ConversionEnded,
+}
+
+/// Passive item kept for the whole run, granted by boss kills and the relic slot of the shop
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Relic {
+    VampireFang,
+    MerchantsPurse,
+    AmbushDagger,
+    VolatileBanner,
+    PilgrimSandals,
+    ThornedCrown,
+}
+
+impl Relic {
+    pub const ALL: [Relic; 6] = [
+        Relic::VampireFang,
+        Relic::MerchantsPurse,
+        Relic::AmbushDagger,
+        Relic::VolatileBanner,
+        Relic::PilgrimSandals,
+        Relic::ThornedCrown,
+    ];
+
+    pub fn trigger(&self) -> RelicTrigger {
+        match self {
+            Relic::VampireFang => RelicTrigger::Death,
+            Relic::MerchantsPurse => RelicTrigger::LevelUp,
+            Relic::AmbushDagger => RelicTrigger::Attack,
+            Relic::VolatileBanner => RelicTrigger::ConversionEnded,
+            Relic::PilgrimSandals => RelicTrigger::Move,
+            Relic::ThornedCrown => RelicTrigger::HealthChange,
+        }
+    }
+
+    pub fn description(&self, balance: &Balance) -> String {
+        match self {
+            Relic::VampireFang => {
+                format!("Heal {} for each enemy killed", balance.relic_kill_heal)
+            }
+            Relic::MerchantsPurse => {
+                format!("Gain {} gold on each level up", balance.relic_level_up_gold)
+            }
+            Relic::AmbushDagger => format!(
+                "The first hit of each turn deals x{} damage",
+                balance.relic_first_hit_multiplier
+            ),
+            Relic::VolatileBanner => format!(
+                "Converted allies explode when they revert, dealing {} damage around them",
+                balance.relic_explosion_damage
+            ),
+            Relic::PilgrimSandals => format!("Gain {} gold for each move", balance.relic_move_gold),
+            Relic::ThornedCrown => format!(
+                "Getting hurt deals {} damage to the adjacent enemies",
+                balance.relic_thorns_damage
+            ),
+        }
+    }
+
+    /// Random relic the hero does not own yet
+    pub fn draw(rng: &mut impl Rng, owned: &[Relic]) -> Option<Relic> {
+        let missing: Vec<Relic> = Relic::ALL
+            .into_iter()
+            .filter(|relic| !owned.contains(relic))
+            .collect();
+        missing.choose(rng).copied()
+    }
+}
+
+impl fmt::Display for Relic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Relic::VampireFang => write!(f, "Vampire Fang"),
+            Relic::MerchantsPurse => write!(f, "Merchant's Purse"),
+            Relic::AmbushDagger => write!(f, "Ambush Dagger"),
+            Relic::VolatileBanner => write!(f, "Volatile Banner"),
+            Relic::PilgrimSandals => write!(f, "Pilgrim's Sandals"),
+            Relic::ThornedCrown => write!(f, "Thorned Crown"),
+        }
+    }
+}
+
+/// Relics of a hero, each one at most once
+#[derive(Component, Default, Clone, Debug)]
+pub struct Relics(pub Vec<Relic>);
+
+impl Relics {
+    /// Returns whether the relic was not owned yet
+    pub fn add(&mut self, relic: Relic) -> bool {
+        if self.0.contains(&relic) {
+            return false;
+        }
+        self.0.push(relic);
+        true
+    }
+
+    /// Relics hooking this event
+    pub fn triggered_by(&self, trigger: RelicTrigger) -> impl Iterator<Item = Relic> + '_ {
+        self.0
+            .iter()
+            .copied()
+            .filter(move |relic| relic.trigger() == trigger)
+    }
+}
+
+/// Relic effects limited to once per turn
+#[derive(Resource, Default)]
+struct RelicTurn {
+    first_hit_done: bool,
+    /// Target of the first hit, its next damage is multiplied
+    ambushed: Option<Entity>,
+}
+
+fn reset_relic_turn(mut relic_turn: ResMut<RelicTurn>) {
+    *relic_turn = RelicTurn::default();
+}
+
+fn trigger_attack_relics(
+    mut events: EventReader<AttackPieceEvent>,
+    player: Query<(Entity, &Relics), With<Player>>,
+    mut relic_turn: ResMut<RelicTurn>,
+) {
+    let Ok((player_entity, relics)) = player.get_single() else {
+        return;
+    };
+    for event in events.read() {
+        if event.attacker != player_entity || event.source != AttackSource::Direct {
+            continue;
+        }
+        for relic in relics.triggered_by(RelicTrigger::Attack) {
+            if relic == Relic::AmbushDagger && !relic_turn.first_hit_done {
+                relic_turn.first_hit_done = true;
+                relic_turn.ambushed = Some(event.target);
+            }
+        }
+    }
+}
+
+/// Reads and sends health changes, the ones it sends hit enemies and trigger nothing
+fn trigger_health_change_relics(
+    mut events: ResMut<Events<PieceHealthChangeEvent>>,
+    mut cursor: Local<EventCursor<PieceHealthChangeEvent>>,
+    player: Query<(Entity, &Relics, &BoardPosition), With<Player>>,
+    pieces: Query<(Entity, &BoardPosition, &Team), (With<Piece>, Without<Player>)>,
+    mut relic_turn: ResMut<RelicTurn>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    balance: Res<Balance>,
+) {
+    let changes: Vec<PieceHealthChangeEvent> = cursor.read(&events).copied().collect();
+    let Ok((player_entity, relics, player_position)) = player.get_single() else {
+        return;
+    };
+    for change in changes.iter().filter(|change| change.change < 0.0) {
+        if relic_turn.ambushed == Some(change.entity) {
+            relic_turn.ambushed = None;
+            events.send(PieceHealthChangeEvent {
+                entity: change.entity,
+                change: change.change * (balance.relic_first_hit_multiplier - 1.0),
+            });
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::SideEffect,
+                format!("{} multiplies the first hit", Relic::AmbushDagger),
+            ));
+        }
+        if change.entity != player_entity {
+            continue;
+        }
+        for relic in relics.triggered_by(RelicTrigger::HealthChange) {
+            if relic == Relic::ThornedCrown {
+                let area = bomb_area(player_position);
+                for (entity, _, _) in pieces
+                    .iter()
+                    .filter(|(_, pos, &team)| team == Team::Enemy && area.contains(pos))
+                {
+                    events.send(PieceHealthChangeEvent {
+                        entity,
+                        change: -balance.relic_thorns_damage,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn trigger_death_relics(
+    mut events: EventReader<PieceDeathEvent>,
+    mut player: Query<(&Relics, &mut Health), With<Player>>,
+    teams: Query<&Team>,
+    balance: Res<Balance>,
+) {
+    let Ok((relics, mut health)) = player.get_single_mut() else {
+        return;
+    };
+    for event in events.read() {
+        if !matches!(teams.get(event.entity), Ok(Team::Enemy)) {
+            continue;
+        }
+        for relic in relics.triggered_by(RelicTrigger::Death) {
+            if relic == Relic::VampireFang {
+                health.heal(balance.relic_kill_heal);
+            }
+        }
+    }
+}
+
+fn trigger_move_relics(
+    mut events: EventReader<MovePieceEvent>,
+    player: Query<(Entity, &Relics), With<Player>>,
+    mut gold: ResMut<Gold>,
+    balance: Res<Balance>,
+) {
+    let Ok((player_entity, relics)) = player.get_single() else {
+        return;
+    };
+    for _ in events.read().filter(|event| event.entity == player_entity) {
+        for relic in relics.triggered_by(RelicTrigger::Move) {
+            if relic == Relic::PilgrimSandals {
+                gold.amount += balance.relic_move_gold;
+            }
+        }
+    }
+}
+
+fn trigger_level_up_relics(
+    mut events: EventReader<PlayerLevelUpEvent>,
+    player: Query<&Relics, With<Player>>,
+    mut gold: ResMut<Gold>,
+    balance: Res<Balance>,
+) {
+    let Ok(relics) = player.get_single() else {
+        return;
+    };
+    for _ in events.read() {
+        for relic in relics.triggered_by(RelicTrigger::LevelUp) {
+            if relic == Relic::MerchantsPurse {
+                gold.amount += balance.relic_level_up_gold;
+            }
+        }
+    }
+}
+
+fn trigger_conversion_ended_relics(
+    mut events: EventReader<ConversionEndedEvent>,
+    player: Query<&Relics, With<Player>>,
+    pieces: Query<(Entity, &BoardPosition, &Team, &Health), (With<Piece>, Without<Player>)>,
+    mut health_change_writer: EventWriter<PieceHealthChangeEvent>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    balance: Res<Balance>,
+) {
+    let Ok(relics) = player.get_single() else {
+        return;
+    };
+    for event in events.read() {
+        let Ok((_, position, _, health)) = pieces.get(event.entity) else {
+            continue;
+        };
+        for relic in relics.triggered_by(RelicTrigger::ConversionEnded) {
+            if relic != Relic::VolatileBanner {
+                continue;
+            }
+            health_change_writer.send(PieceHealthChangeEvent {
+                entity: event.entity,
+                change: -health.value,
+            });
+            let area = bomb_area(position);
+            for (entity, _, _, _) in pieces
+                .iter()
+                .filter(|(_, pos, &team, _)| team == Team::Enemy && area.contains(pos))
+            {
+                health_change_writer.send(PieceHealthChangeEvent {
+                    entity,
+                    change: -balance.relic_explosion_damage,
+                });
+            }
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::SideEffect,
+                format!("A reverting ally explodes at {}", position),
+            ));
+        }
+    }
+}
+
+/// Killing a boss grants a relic the hero does not own yet
+fn grant_boss_relic(
+    mut death_events: EventReader<PieceDeathEvent>,
+    bosses: Query<(), With<Boss>>,
+    mut player: Query<&mut Relics, With<Player>>,
+    mut rng: ResMut<GameRng>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+) {
+    let Ok(mut relics) = player.get_single_mut() else {
+        return;
+    };
+    for event in death_events.read() {
+        if bosses.get(event.entity).is_err() {
+            continue;
+        }
+        if let Some(relic) = Relic::draw(&mut rng.0, &relics.0) {
+            relics.add(relic);
+            log_writer.send(CombatLogEvent::new(
+                LogCategory::Reward,
+                format!("The boss leaves the {}", relic),
+            ));
+        }
+    }
+}
+
+pub struct RelicsPlugin;
+
+impl Plugin for RelicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RelicTurn>()
+            .add_systems(OnEnter(TurnState::PlayerInput), reset_relic_turn)
+            .add_systems(
+                Update,
+                (
+                    trigger_attack_relics.run_if(on_event::<AttackPieceEvent>),
+                    trigger_health_change_relics,
+                    trigger_death_relics.run_if(on_event::<PieceDeathEvent>),
+                    trigger_move_relics.run_if(on_event::<MovePieceEvent>),
+                    trigger_level_up_relics.run_if(on_event::<PlayerLevelUpEvent>),
+                    trigger_conversion_ended_relics.run_if(on_event::<ConversionEndedEvent>),
+                    grant_boss_relic.run_if(on_event::<PieceDeathEvent>),
+                )
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_relic_triggers_and_draws() {
+        let mut relics = Relics::default();
+        assert!(relics.add(Relic::VampireFang));
+        assert!(relics.add(Relic::MerchantsPurse));
+        assert!(!relics.add(Relic::VampireFang));
+        assert_eq!(
+            relics.triggered_by(RelicTrigger::Death).collect::<Vec<_>>(),
+            vec![Relic::VampireFang]
+        );
+        assert_eq!(relics.triggered_by(RelicTrigger::Move).count(), 0);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let relic = Relic::draw(&mut rng, &relics.0).unwrap();
+            assert!(!relics.0.contains(&relic));
+        }
+        assert_eq!(Relic::draw(&mut rng, &Relic::ALL), None);
+    }
+}
@@ -1,8 +1,11 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 use crate::{
     board::position::BoardPosition,
-    globals::{self, PLAYER_ATLAS_INDEX},
+    game_logic::{balance::Balance, daily::Mutator, profile::Profile, run_config::RunConfig},
+    globals::{
+        self, COOP_HERO_POSITIONS, GLASS_CANNON_MULTIPLIER, HERO_COLORS, VERSUS_HERO_POSITIONS,
+    },
     graphics::spritesheet::SpriteSheetAtlas,
     pieces::{
         attack::AttackAfterMove,
@@ -11,13 +14,18 @@ use crate::{
         health::Health,
         healthbar::spawn_healthbar,
         movement_type::MovementType,
-        player::upgrades::unique_upgrades::limit::MovementTypeLimit,
+        player::upgrades::unique_upgrades::{block::Block, limit::MovementTypeLimit},
     },
     states::game_state::GameState,
     ui::shop::ApplyUpgrades,
 };
 
-use super::upgrades::data::{get_movement_upgrade, Upgrades};
+use super::{
+    character::{CharacterRoster, Passive},
+    consumables::Inventory,
+    relics::Relics,
+    upgrades::data::{UpgradeCatalog, Upgrades},
+};
 
 #[derive(Component)]
 pub struct PulseSize {
@@ -30,42 +38,84 @@ pub struct PulseSize {
 #[derive(Component)]
 pub struct Player;
 
+/// One of the two heroes of a versus or co-op game, the one playing also has `Player`
+#[derive(Component)]
+pub struct Hero {
+    pub side: usize,
+}
+
 pub fn spawn_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     atlas_layout: Res<SpriteSheetAtlas>,
     mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    catalog: Res<UpgradeCatalog>,
+    characters: Res<CharacterRoster>,
 ) {
     debug!("Spawning player");
-    let tile_pos = BoardPosition::new(4, 4).unwrap();
-    let global_position = tile_pos
-        .as_global_position()
-        .extend(globals::PLAYER_Z_INDEX);
-
-    let player_id = commands
-        .spawn((
+    let character = config.character(&profile, &characters);
+    let profile = config.run_profile(&profile);
+    let mut health = character.health(&balance) + profile.health_bonus();
+    let mut attack = character.attack + profile.attack_bonus();
+    if config.has_mutator(Mutator::GlassCannon) {
+        health = (health / GLASS_CANNON_MULTIPLIER).ceil();
+        attack *= GLASS_CANNON_MULTIPLIER;
+    }
+    let mut tile_pos = BoardPosition::new(4, 4).unwrap();
+    let mut movement_types = character.movement_types.clone();
+    let mut movement_type_limit = character.movement_type_limit;
+    let mut passive = character.passive.clone();
+    let mut block = match passive {
+        Passive::PermanentBlock(amount) => amount,
+        _ => 0,
+    };
+    // puzzles and the tutorial replace the character setup with their own
+    let scenario_player = config.scenario_player();
+    if let Some(scenario_player) = scenario_player {
+        let (x, y) = scenario_player.position;
+        tile_pos = BoardPosition::new(x, y).expect("Scenario positions are validated");
+        health = scenario_player.health;
+        attack = scenario_player.attack;
+        block = scenario_player.block;
+        movement_types = scenario_player.movement_types.clone();
+        let distinct_types: HashSet<&MovementType> = movement_types.iter().collect();
+        movement_type_limit = distinct_types.len();
+        passive = Passive::None;
+    }
+    let hero_bundle = |tile_pos: BoardPosition, color: Color| {
+        let global_position = tile_pos
+            .as_global_position()
+            .extend(globals::PLAYER_Z_INDEX);
+        (
             Piece,
             Sprite {
                 texture_atlas: Some(TextureAtlas {
                     layout: atlas_layout.handle.clone(),
-                    index: PLAYER_ATLAS_INDEX,
+                    index: character.atlas_index,
                 }),
                 image: asset_server.load("custom/spritesheet.png"),
+                color,
                 ..default()
             },
             Transform::from_translation(global_position),
             tile_pos,
-            Health::new(globals::PLAYER_HEALTH),
-            Attack::new(globals::PLAYER_DAMAGE),
-            Upgrades(vec![
-                get_movement_upgrade(&MovementType::Queen),
-                get_movement_upgrade(&MovementType::Queen),
-                get_movement_upgrade(&MovementType::Knight),
-                get_movement_upgrade(&MovementType::Knight),
-            ]),
+            Health::new(health),
+            Attack::new(attack),
+            Upgrades(
+                movement_types
+                    .iter()
+                    .map(|movement_type| {
+                        // the starting upgrades were not paid for, they sell for nothing
+                        let mut upgrade = catalog.get_movement_upgrade(movement_type);
+                        upgrade.cost = 0;
+                        upgrade
+                    })
+                    .collect(),
+            ),
             Team::Player,
-            Player,
-            Name::new("Player"),
             StateScoped(GameState::Game),
             PulseSize {
                 start_size: 1.0,
@@ -73,12 +123,59 @@ pub fn spawn_player(
                 progress: 0.0,
                 speed: globals::PULSE_ANIMATION_SPEED,
             },
-            AttackAfterMove,
-            MovementTypeLimit { limit: 2 },
-        ))
-        .id();
+            (
+                AttackAfterMove,
+                MovementTypeLimit {
+                    limit: movement_type_limit,
+                },
+                Block { amount: block },
+                passive.clone(),
+                Inventory::default(),
+                Relics::default(),
+            ),
+        )
+    };
 
-    let healthbars = spawn_healthbar(&mut commands, &asset_server, &atlas_layout.handle);
-    commands.entity(player_id).add_children(&healthbars);
-    apply_upgrades_event_writer.send(ApplyUpgrades(get_movement_upgrade(&MovementType::King)));
+    let hero_positions = if config.versus {
+        Some(VERSUS_HERO_POSITIONS)
+    } else if config.coop.is_some() {
+        Some(COOP_HERO_POSITIONS)
+    } else {
+        None
+    };
+    if let Some(hero_positions) = hero_positions {
+        // both heroes play the same character, the second one waits for its turn
+        for (side, (x, y)) in hero_positions.into_iter().enumerate() {
+            let tile_pos = BoardPosition::new(x, y).expect("Hero positions are on the board");
+            let hero_id = commands
+                .spawn((
+                    hero_bundle(tile_pos, HERO_COLORS[side]),
+                    Hero { side },
+                    Name::new(format!("Player {}", side + 1)),
+                ))
+                .id();
+            if side == 0 {
+                commands.entity(hero_id).insert(Player);
+            } else if config.versus {
+                commands.entity(hero_id).insert(Team::Enemy);
+            }
+            let healthbars = spawn_healthbar(&mut commands, &asset_server, &atlas_layout.handle);
+            commands.entity(hero_id).add_children(&healthbars);
+        }
+    } else {
+        let player_id = commands
+            .spawn((
+                hero_bundle(tile_pos, character.sprite_color()),
+                Player,
+                Name::new("Player"),
+            ))
+            .id();
+        let healthbars = spawn_healthbar(&mut commands, &asset_server, &atlas_layout.handle);
+        commands.entity(player_id).add_children(&healthbars);
+    }
+    if scenario_player.is_none() {
+        apply_upgrades_event_writer.send(ApplyUpgrades(
+            catalog.get_movement_upgrade(&MovementType::King),
+        ));
+    }
 }
@@ -1,39 +1,72 @@
-use super::stats::StatEffect;
+use std::fmt;
+
+use super::{stats::StatEffect, unique_upgrades::ability_tier};
 use crate::{
+    game_logic::balance::{apply_mode_balance, Balance},
     globals::{
-        CONVERT_ENEMY_TURNS_TO_CONVERT, PRIMARY_COLOR, QUEEN_UNIQUE_CHANCE,
-        SHOP_PIECE_VALUE_GOLD_MULTIPLIER, SPRITESHEET_WIDTH, UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER,
-        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER, WIP_SPRITE_INDEX,
+        ABILITY_TIER_UNLOCKS, PRIMARY_COLOR, SECONDARY_COLOR, SPRITESHEET_WIDTH,
+        UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER, UNIQUE_UPGRADE_DAMAGE_MULTIPLIER,
+        UPGRADES_ASSET_PATH, WIP_SPRITE_INDEX,
     },
     pieces::{
-        enemies::{
-            bishop::WHITE_BISHOP_INFO,
-            king::WHITE_KING_INFO,
-            knight::WHITE_KNIGHT_INFO,
-            pawn::{BLACK_PAWN_INFO, WHITE_PAWN_INFO},
-            queen::WHITE_QUEEN_INFO,
-            rook::WHITE_ROOK_INFO,
-        },
+        enemies::{roster::EnemyRoster, PieceInfo},
         movement_type::MovementType,
-        player::upgrades::stats::StatVariant,
     },
     utils::rng::Weighted,
 };
 use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
     prelude::*,
     utils::{HashMap, HashSet},
 };
-use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+/// Embedded so the upgrades exist before the asset server loads the file
+const UPGRADES_FILE: &str = include_str!("../../../../assets/shop.upgrades.ron");
 
 #[derive(Clone, Debug, Component)]
 pub struct Upgrade {
     weight: f32,
     pub display_name: String,
     pub description: Vec<(TextSpan, TextColor)>,
+    /// Unique ability of each tier, in the order of `ABILITY_TIER_UNLOCKS`
+    pub tiers: Vec<Vec<(TextSpan, TextColor)>>,
     pub cost: usize,
     pub rarity: Rarity,
     pub effect: Effect,
     pub icon_index: usize,
+    /// Only sold once the profile unlocks it
+    pub locked: bool,
+}
+
+impl Upgrade {
+    /// Same upgrade, drawn with the odds of its rarity after `steps` of progression
+    pub fn with_rarity_odds(mut self, steps: usize, balance: &Balance) -> Upgrade {
+        self.weight *= self.rarity.weight_multiplier(steps, balance);
+        self
+    }
+
+    /// Description followed by the ability tier reached with `owned` upgrades and the next one
+    pub fn description_with_tiers(&self, owned: usize) -> Vec<(TextSpan, TextColor)> {
+        let mut spans = self.description.clone();
+        let tier = ability_tier(owned);
+        let mut push_tier = |label: String, index: usize| {
+            if let Some(tier_spans) = self.tiers.get(index) {
+                spans.push((TextSpan(label), TextColor(PRIMARY_COLOR)));
+                spans.extend(tier_spans.iter().cloned());
+            }
+        };
+        if tier > 0 {
+            push_tier(
+                format!("\nCurrent (Level {}+): ", ABILITY_TIER_UNLOCKS[tier - 1]),
+                tier - 1,
+            );
+        }
+        if let Some(unlock) = ABILITY_TIER_UNLOCKS.get(tier) {
+            push_tier(format!("\nNext (Level {}): ", unlock), tier);
+        }
+        spans
+    }
 }
 
 #[derive(Clone, Debug, Component, Default)]
@@ -52,6 +85,32 @@ impl Upgrades {
         set
     }
 
+    /// False when `upgrade` grants a new movement type past the limit
+    pub fn within_movement_limit(&self, upgrade: &Upgrade, limit: usize) -> bool {
+        let Effect::MovementType(movement_types) = &upgrade.effect else {
+            return true;
+        };
+        let movement_types_set = self.get_movement_types_set();
+        movement_types
+            .first()
+            .is_none_or(|movement_type| movement_types_set.contains(movement_type))
+            || movement_types_set.len() < limit
+    }
+
+    /// Upgrades already owned of the movement types `upgrade` grants
+    pub fn owned_movement_upgrades(&self, upgrade: &Upgrade) -> usize {
+        let Effect::MovementType(movement_types) = &upgrade.effect else {
+            return 0;
+        };
+        let counts = self.get_movement_types_count();
+        movement_types
+            .iter()
+            .filter_map(|movement_type| counts.get(movement_type))
+            .max()
+            .copied()
+            .unwrap_or(0)
+    }
+
     pub fn get_movement_types_count(&self) -> HashMap<MovementType, usize> {
         let mut map = HashMap::new();
         for upgrade in &self.0 {
@@ -65,336 +124,329 @@ impl Upgrades {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum Rarity {
     Common,
     Rare,
     Epic,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl Rarity {
+    /// Rare and epic upgrades get likelier with each step of progression in the run
+    pub fn weight_multiplier(&self, steps: usize, balance: &Balance) -> f32 {
+        let bonus = balance.rarity_weight_per_step * steps as f32;
+        match self {
+            Rarity::Common => 1.0,
+            Rarity::Rare => balance.rare_weight + bonus,
+            Rarity::Epic => balance.epic_weight + bonus,
+        }
+    }
+}
+
+/// Progression steps of a run, one per level up and per `rarity_turns_per_step` turns
+pub fn rarity_steps(level: usize, turn: usize, balance: &Balance) -> usize {
+    level.saturating_sub(1) + turn / balance.rarity_turns_per_step.max(1)
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum Effect {
     MovementType(Vec<MovementType>),
     StatEffect(StatEffect),
 }
 
-pub static UPGRADES_MOVEMENT: Lazy<Vec<Upgrade>> = Lazy::new(|| {
-    vec![
-        Upgrade {
-            weight: 0.0,
-            display_name: "White Pawn Movement".to_string(),
-            description: vec![(
-                TextSpan("White Pawn movement".to_string()),
-                TextColor::default(),
-            )],
-            cost: (WHITE_PAWN_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::WhitePawn]),
-            icon_index: WHITE_PAWN_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 0.0,
-            display_name: "Black Pawn Movement".to_string(),
-            description: vec![(
-                TextSpan("Black Pawn movement".to_string()),
-                TextColor::default(),
-            )],
-            cost: (BLACK_PAWN_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::BlackPawn]),
-            icon_index: BLACK_PAWN_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Pawn Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a pawn.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases pawn damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(" Converts enemies to allies on hit for ".to_string()),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("{} turns.", CONVERT_ENEMY_TURNS_TO_CONVERT)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-            ],
-            cost: (WHITE_PAWN_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::WhitePawn, MovementType::BlackPawn]),
-            icon_index: WHITE_PAWN_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "King Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a king.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases king damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (TextSpan(" King ".to_string()), TextColor::default()),
-                (
-                    TextSpan("attacks twice".to_string()),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(": from the start and from the end of movement.".to_string()),
-                    TextColor::default(),
-                ),
-            ],
-            cost: (WHITE_KING_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::King]),
-            icon_index: WHITE_KING_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Queen Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a queen.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases queen damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(" All attacks have a ".to_string()),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("{}%", QUEEN_UNIQUE_CHANCE * 100.0)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (TextSpan(" chance to ".to_string()), TextColor::default()),
-                (TextSpan("repeat.".to_string()), TextColor(PRIMARY_COLOR)),
-            ],
-            cost: (WHITE_QUEEN_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::Queen]),
-            icon_index: WHITE_QUEEN_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Knight Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a knight.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases knight damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(" Knight attacks ".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Chain".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" once.".to_string()), TextColor::default()),
-            ],
-            cost: (WHITE_KNIGHT_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::Knight]),
-            icon_index: WHITE_KNIGHT_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Bishop Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a bishop.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases bishop damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(" Bishop attacks ".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Pierce".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" enemies.".to_string()), TextColor::default()),
-            ],
-            cost: (WHITE_BISHOP_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::Bishop]),
-            icon_index: WHITE_BISHOP_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Rook Movement".to_string(),
-            description: vec![
-                (
-                    TextSpan("Allows the player to move and attack like a rook.\n".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Level 2+:".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(format!(
-                        " Increases rook damage by {}% per level.\n",
-                        UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0
-                    )),
-                    TextColor::default(),
-                ),
-                (
-                    TextSpan(format!("Level {}+:", UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER)),
-                    TextColor(PRIMARY_COLOR),
-                ),
-                (
-                    TextSpan(" Rook attacks grant ".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Block(1)".to_string()), TextColor(PRIMARY_COLOR)),
-                (
-                    TextSpan(". It does not stack.".to_string()),
-                    TextColor::default(),
-                ),
-            ],
-            cost: (WHITE_ROOK_INFO.value as f32 * SHOP_PIECE_VALUE_GOLD_MULTIPLIER) as usize,
-            rarity: Rarity::Common,
-            effect: Effect::MovementType(vec![MovementType::Rook]),
-            icon_index: WHITE_ROOK_INFO.sprite_index + SPRITESHEET_WIDTH,
-        },
-    ]
-});
-
-pub static UPGRADES_STATS: Lazy<Vec<Upgrade>> = Lazy::new(|| {
-    vec![
-        Upgrade {
-            weight: 1.0,
-            display_name: "Health +10".to_string(),
-            description: vec![
-                (
-                    TextSpan("Increases maximum ".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Health".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" by 10.".to_string()), TextColor::default()),
-            ],
-            cost: 100,
-            rarity: Rarity::Common,
-            effect: Effect::StatEffect(StatEffect {
-                stat: StatVariant::MaxHealth,
-                additive: 10.0,
-                multiplicative: 1.0,
-            }),
-            icon_index: WIP_SPRITE_INDEX,
-        },
-        Upgrade {
-            weight: 0.3,
-            display_name: "Health +20".to_string(),
-            description: vec![
-                (
-                    TextSpan("Increases maximum ".to_string()),
-                    TextColor::default(),
-                ),
-                (TextSpan("Health".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" by ".to_string()), TextColor::default()),
-                (TextSpan("20".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(".".to_string()), TextColor::default()),
-            ],
-            cost: 150,
-            icon_index: WIP_SPRITE_INDEX,
-            rarity: Rarity::Rare,
-            effect: Effect::StatEffect(StatEffect {
-                stat: StatVariant::MaxHealth,
-                additive: 20.0,
-                multiplicative: 1.0,
-            }),
-        },
-        Upgrade {
-            weight: 1.0,
-            display_name: "Attack +1".to_string(),
-            description: vec![
-                (TextSpan("Increases ".to_string()), TextColor::default()),
-                (TextSpan("Attack".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" by ".to_string()), TextColor::default()),
-                (TextSpan("1".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(".".to_string()), TextColor::default()),
-            ],
-            cost: 100,
-            icon_index: WIP_SPRITE_INDEX,
-            rarity: Rarity::Common,
-            effect: Effect::StatEffect(StatEffect {
-                stat: StatVariant::Attack,
-                additive: 1.0,
-                multiplicative: 1.0,
-            }),
-        },
-        Upgrade {
-            weight: 0.3,
-            display_name: "Attack +2".to_string(),
-            description: vec![
-                (TextSpan("Increases ".to_string()), TextColor::default()),
-                (TextSpan("Attack".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(" by ".to_string()), TextColor::default()),
-                (TextSpan("2".to_string()), TextColor(PRIMARY_COLOR)),
-                (TextSpan(".".to_string()), TextColor::default()),
-            ],
-            cost: 150,
-            icon_index: WIP_SPRITE_INDEX,
-            rarity: Rarity::Rare,
-            effect: Effect::StatEffect(StatEffect {
-                stat: StatVariant::Attack,
-                additive: 2.0,
-                multiplicative: 1.0,
-            }),
-        },
-    ]
-});
+#[derive(Deserialize, Clone, Debug)]
+pub enum UpgradeCost {
+    Gold(usize),
+    /// Value of the enemy moving like this, times the shop multiplier
+    PieceValue(MovementType),
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum UpgradeIcon {
+    /// Attack sprite of the enemy moving like this
+    Piece(MovementType),
+    Index(usize),
+    Wip,
+}
+
+/// Upgrade as written in the upgrades file
+#[derive(Deserialize, Clone, Debug)]
+pub struct UpgradeDefinition {
+    pub name: String,
+    pub weight: f32,
+    pub cost: UpgradeCost,
+    pub rarity: Rarity,
+    pub effect: Effect,
+    pub icon: UpgradeIcon,
+    /// Colored with `{c:primary}...{/c}`, see `parse_description`
+    pub description: String,
+    /// Unique ability of each tier, shown with the upgrades owned
+    #[serde(default)]
+    pub tiers: Vec<String>,
+    /// Kept out of the shop and the drafts until the profile unlocks it
+    #[serde(default)]
+    pub locked: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MarkupError {
+    UnknownColor(String),
+    UnknownPlaceholder(String),
+    /// A `{` or a color without its end
+    Unclosed,
+    /// `{/c}` without a color to end
+    UnexpectedClose,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupError::UnknownColor(color) => write!(f, "unknown color {:?}", color),
+            MarkupError::UnknownPlaceholder(name) => write!(f, "unknown placeholder {:?}", name),
+            MarkupError::Unclosed => write!(f, "unclosed tag"),
+            MarkupError::UnexpectedClose => write!(f, "{{/c}} without a color"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InvalidUpgrades {
+    Parse(String),
+    Description(String, MarkupError),
+    UnknownPiece(MovementType),
+    /// More ability tiers than `ABILITY_TIER_UNLOCKS`
+    TooManyTiers(String),
+    /// Every movement type needs an upgrade granting only it
+    MissingMovement(MovementType),
+}
+
+impl fmt::Display for InvalidUpgrades {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidUpgrades::Parse(error) => write!(f, "could not parse upgrades: {}", error),
+            InvalidUpgrades::Description(name, error) => {
+                write!(f, "invalid description of {}: {}", name, error)
+            }
+            InvalidUpgrades::UnknownPiece(movement_type) => {
+                write!(f, "no enemy moves like a {:?}", movement_type)
+            }
+            InvalidUpgrades::TooManyTiers(name) => {
+                write!(
+                    f,
+                    "{} has more than {} ability tiers",
+                    name,
+                    ABILITY_TIER_UNLOCKS.len()
+                )
+            }
+            InvalidUpgrades::MissingMovement(movement_type) => {
+                write!(f, "no upgrade grants only {:?}", movement_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidUpgrades {}
+
+fn markup_color(name: &str) -> Result<Color, MarkupError> {
+    match name {
+        "primary" => Ok(PRIMARY_COLOR),
+        "secondary" => Ok(SECONDARY_COLOR),
+        _ => Err(MarkupError::UnknownColor(name.to_string())),
+    }
+}
+
+/// Game rules the descriptions can mention
+fn placeholder_value(name: &str, balance: &Balance) -> Result<String, MarkupError> {
+    match name {
+        "damage_per_level" => Ok((UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * 100.0).to_string()),
+        "unique_unlock" => Ok(UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER.to_string()),
+        "pierce_falloff" => Ok(((1.0 - balance.pierce_damage_falloff) * 100.0)
+            .round()
+            .to_string()),
+        "rook_block" => Ok(balance.advanced_rook_block.to_string()),
+        "convert_turns" => Ok(balance.convert_enemy_turns_to_convert.to_string()),
+        "queen_chance" => Ok((balance.queen_unique_chance * 100.0).to_string()),
+        _ => Err(MarkupError::UnknownPlaceholder(name.to_string())),
+    }
+}
+
+/// Splits a description into text spans, `{c:primary}Pierce{/c}` colors the word
+/// and `{queen_chance}` is replaced by the rule value
+pub fn parse_description(
+    template: &str,
+    balance: &Balance,
+) -> Result<Vec<(TextSpan, TextColor)>, MarkupError> {
+    fn push_span(spans: &mut Vec<(TextSpan, TextColor)>, text: &mut String, color: TextColor) {
+        if !text.is_empty() {
+            spans.push((TextSpan(std::mem::take(text)), color));
+        }
+    }
+
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut color = None;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let end = start + rest[start..].find('}').ok_or(MarkupError::Unclosed)?;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        if let Some(name) = tag.strip_prefix("c:") {
+            if color.is_some() {
+                return Err(MarkupError::Unclosed);
+            }
+            push_span(&mut spans, &mut text, TextColor::default());
+            color = Some(markup_color(name)?);
+        } else if tag == "/c" {
+            let span_color = color.take().ok_or(MarkupError::UnexpectedClose)?;
+            push_span(&mut spans, &mut text, TextColor(span_color));
+        } else {
+            text.push_str(&placeholder_value(tag, balance)?);
+        }
+    }
+    if color.is_some() {
+        return Err(MarkupError::Unclosed);
+    }
+    text.push_str(rest);
+    push_span(&mut spans, &mut text, TextColor::default());
+    Ok(spans)
+}
+
+fn roster_piece(
+    movement_type: &MovementType,
+    balance: &Balance,
+    roster: &EnemyRoster,
+) -> Result<PieceInfo, InvalidUpgrades> {
+    roster
+        .pieces(balance)
+        .into_iter()
+        .find(|piece| piece.movement_type == *movement_type)
+        .ok_or_else(|| InvalidUpgrades::UnknownPiece(movement_type.clone()))
+}
+
+impl UpgradeDefinition {
+    fn build(&self, balance: &Balance, roster: &EnemyRoster) -> Result<Upgrade, InvalidUpgrades> {
+        let description = parse_description(&self.description, balance)
+            .map_err(|error| InvalidUpgrades::Description(self.name.clone(), error))?;
+        if self.tiers.len() > ABILITY_TIER_UNLOCKS.len() {
+            return Err(InvalidUpgrades::TooManyTiers(self.name.clone()));
+        }
+        let tiers = self
+            .tiers
+            .iter()
+            .map(|tier| parse_description(tier, balance))
+            .collect::<Result<_, _>>()
+            .map_err(|error| InvalidUpgrades::Description(self.name.clone(), error))?;
+        let cost = match &self.cost {
+            UpgradeCost::Gold(amount) => *amount,
+            UpgradeCost::PieceValue(movement_type) => {
+                (roster_piece(movement_type, balance, roster)?.value as f32
+                    * balance.shop_piece_value_gold_multiplier) as usize
+            }
+        };
+        let icon_index = match &self.icon {
+            UpgradeIcon::Piece(movement_type) => {
+                roster_piece(movement_type, balance, roster)?.sprite_index + SPRITESHEET_WIDTH
+            }
+            UpgradeIcon::Index(index) => *index,
+            UpgradeIcon::Wip => WIP_SPRITE_INDEX,
+        };
+        Ok(Upgrade {
+            weight: self.weight,
+            display_name: self.name.clone(),
+            description,
+            tiers,
+            cost,
+            rarity: self.rarity.clone(),
+            effect: self.effect.clone(),
+            icon_index,
+            locked: self.locked,
+        })
+    }
+}
+
+/// Upgrades the shop sells, loaded from a `.upgrades.ron` file
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Debug)]
+pub struct UpgradeTable {
+    pub movement: Vec<UpgradeDefinition>,
+    pub stats: Vec<UpgradeDefinition>,
+}
+
+/// Upgrades ready to be sold, built from the table with the current balance and roster
+#[derive(Resource)]
+pub struct UpgradeCatalog {
+    pub movement: Vec<Upgrade>,
+    pub stats: Vec<Upgrade>,
+}
+
+impl UpgradeTable {
+    pub fn parse(content: &str) -> Result<Self, InvalidUpgrades> {
+        ron::from_str(content).map_err(|error| InvalidUpgrades::Parse(error.to_string()))
+    }
+
+    pub fn build(
+        &self,
+        balance: &Balance,
+        roster: &EnemyRoster,
+    ) -> Result<UpgradeCatalog, InvalidUpgrades> {
+        let build = |definition: &UpgradeDefinition| definition.build(balance, roster);
+        let catalog = UpgradeCatalog {
+            movement: self.movement.iter().map(build).collect::<Result<_, _>>()?,
+            stats: self.stats.iter().map(build).collect::<Result<_, _>>()?,
+        };
+        for piece in roster.pieces(balance) {
+            if catalog.movement_upgrade(&piece.movement_type).is_none() {
+                return Err(InvalidUpgrades::MissingMovement(piece.movement_type));
+            }
+        }
+        Ok(catalog)
+    }
+}
+
+impl UpgradeCatalog {
+    fn movement_upgrade(&self, movement_type: &MovementType) -> Option<&Upgrade> {
+        self.movement.iter().find(|u| {
+            matches!(u.effect, Effect::MovementType(ref mts) if mts.contains(movement_type) && mts.len() == 1)
+        })
+    }
+
+    pub fn get_movement_upgrade(&self, movement_type: &MovementType) -> Upgrade {
+        debug!("Searching for movement upgrade: {:?}", movement_type);
+
+        self.movement_upgrade(movement_type)
+            .cloned()
+            .unwrap_or_else(|| panic!("No upgrade found for movement type: {:?}", movement_type))
+    }
+}
+
+/// Embedded upgrades, replaced once the asset server loads the file
+impl Default for UpgradeTable {
+    fn default() -> Self {
+        UpgradeTable::parse(UPGRADES_FILE).expect("Upgrades file is valid")
+    }
+}
+
+/// Built from the embedded files
+impl Default for UpgradeCatalog {
+    fn default() -> Self {
+        UpgradeTable::default()
+            .build(&Balance::default(), &EnemyRoster::default())
+            .expect("Upgrades file is valid")
+    }
+}
+
+/// Rebuilds the upgrades after the file, the balance values or the roster changed
+fn rebuild_upgrade_catalog(
+    table: Res<UpgradeTable>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    mut catalog: ResMut<UpgradeCatalog>,
+) {
+    match table.build(&balance, &roster) {
+        Ok(built) => *catalog = built,
+        Err(error) => warn!("Keeping the previous upgrades: {}", error),
+    }
+}
 
 impl Weighted for Upgrade {
     fn weight(&self) -> f32 {
@@ -402,12 +454,206 @@ impl Weighted for Upgrade {
     }
 }
 
-pub fn get_movement_upgrade(movement_type: &MovementType) -> Upgrade {
-    debug!("Searching for movement upgrade: {:?}", movement_type);
+#[derive(Default)]
+struct UpgradeTableLoader;
+
+impl AssetLoader for UpgradeTableLoader {
+    type Asset = UpgradeTable;
+    type Settings = ();
+    type Error = InvalidUpgrades;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<UpgradeTable, InvalidUpgrades> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(|error| InvalidUpgrades::Parse(error.to_string()))?;
+        let content = std::str::from_utf8(&bytes)
+            .map_err(|error| InvalidUpgrades::Parse(error.to_string()))?;
+        UpgradeTable::parse(content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["upgrades.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct UpgradeTableHandle(Handle<UpgradeTable>);
+
+fn load_upgrade_table(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(UpgradeTableHandle(asset_server.load(UPGRADES_ASSET_PATH)));
+}
+
+/// Swaps in the upgrades of the file, also when it changes on disk in debug builds
+fn reload_upgrades(
+    mut events: EventReader<AssetEvent<UpgradeTable>>,
+    tables: Res<Assets<UpgradeTable>>,
+    handle: Res<UpgradeTableHandle>,
+    balance: Res<Balance>,
+    roster: Res<EnemyRoster>,
+    mut upgrade_table: ResMut<UpgradeTable>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        let Some(table) = tables.get(*id) else {
+            continue;
+        };
+        // rejected files keep the previous upgrades
+        match table.build(&balance, &roster) {
+            Ok(_) => {
+                *upgrade_table = table.clone();
+                debug!("Upgrades loaded from {}", UPGRADES_ASSET_PATH);
+            }
+            Err(error) => warn!("Keeping the previous upgrades: {}", error),
+        }
+    }
+}
+
+pub struct UpgradeDataPlugin;
+
+impl Plugin for UpgradeDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<UpgradeTable>()
+            .init_asset_loader::<UpgradeTableLoader>()
+            .init_resource::<UpgradeTable>()
+            .init_resource::<UpgradeCatalog>()
+            .add_systems(Startup, load_upgrade_table)
+            .add_systems(
+                Update,
+                reload_upgrades.run_if(on_event::<AssetEvent<UpgradeTable>>),
+            )
+            // shop prices and icons come from the balance and the roster
+            .add_systems(
+                PostUpdate,
+                rebuild_upgrade_catalog.after(apply_mode_balance).run_if(
+                    resource_changed::<UpgradeTable>
+                        .or(resource_changed::<Balance>)
+                        .or(resource_changed::<EnemyRoster>),
+                ),
+            );
+    }
+}
 
-    UPGRADES_MOVEMENT
-        .iter()
-        .find(|u| matches!(u.effect, Effect::MovementType(ref mts) if mts.contains(movement_type) && mts.len() == 1))
-        .cloned()
-        .unwrap_or_else(|| panic!("No upgrade found for movement type: {:?}", movement_type))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrades_file_is_valid() {
+        let (balance, roster) = (Balance::default(), EnemyRoster::default());
+        let catalog = UpgradeTable::parse(UPGRADES_FILE)
+            .and_then(|table| table.build(&balance, &roster))
+            .unwrap();
+        assert_eq!(catalog.movement.len(), 10);
+        assert_eq!(catalog.stats.len(), 6);
+        assert!(catalog
+            .stats
+            .iter()
+            .any(|upgrade| upgrade.rarity == Rarity::Epic));
+        let queen = catalog.movement_upgrade(&MovementType::Queen).unwrap();
+        assert!(queen.tiers[0].iter().any(|(text, color)| text.0
+            == format!("{}%", balance.queen_unique_chance * 100.0)
+            && color.0 == PRIMARY_COLOR));
+        assert!(queen
+            .description
+            .iter()
+            .chain(queen.tiers.iter().flatten())
+            .all(|(text, _)| !text.0.contains('{')));
+
+        let missing_rook = UPGRADES_FILE.replace("MovementType([Rook])", "MovementType([])");
+        assert_eq!(
+            UpgradeTable::parse(&missing_rook)
+                .and_then(|table| table.build(&balance, &roster))
+                .err(),
+            Some(InvalidUpgrades::MissingMovement(MovementType::Rook))
+        );
+    }
+
+    #[test]
+    fn test_rarity_odds_improve_with_progression() {
+        let balance = Balance::default();
+        assert_eq!(rarity_steps(1, 0, &balance), 0);
+        assert_eq!(
+            rarity_steps(3, balance.rarity_turns_per_step * 2 + 1, &balance),
+            4
+        );
+        let odds = |rarity: Rarity, steps| {
+            rarity.weight_multiplier(steps, &balance)
+                / Rarity::Common.weight_multiplier(steps, &balance)
+        };
+        assert!(odds(Rarity::Epic, 0) < odds(Rarity::Rare, 0));
+        assert!(odds(Rarity::Rare, 5) > odds(Rarity::Rare, 0));
+        assert!(odds(Rarity::Epic, 5) > odds(Rarity::Epic, 0));
+    }
+
+    #[test]
+    fn test_description_with_tiers() {
+        let knight = UpgradeCatalog::default().get_movement_upgrade(&MovementType::Knight);
+        let text = |owned| {
+            knight
+                .description_with_tiers(owned)
+                .iter()
+                .map(|(text, _)| text.0.clone())
+                .collect::<String>()
+        };
+        assert!(!text(0).contains("Current"));
+        assert!(text(0).contains("Next (Level 2): Knight attacks Chain once."));
+        assert!(text(3).contains("Current (Level 2+): Knight attacks Chain once."));
+        assert!(text(3).contains("Next (Level 4): Knight attacks Chain twice"));
+        assert!(text(4).contains("Current (Level 4+): Knight attacks Chain twice"));
+        assert!(!text(4).contains("Next"));
+    }
+
+    #[test]
+    fn test_parse_description() {
+        let balance = Balance::default();
+        let spans =
+            parse_description("Bishop attacks {c:primary}Pierce{/c} enemies.", &balance).unwrap();
+        let texts: Vec<(&str, Color)> = spans
+            .iter()
+            .map(|(text, color)| (text.0.as_str(), color.0))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                ("Bishop attacks ", TextColor::default().0),
+                ("Pierce", PRIMARY_COLOR),
+                (" enemies.", TextColor::default().0),
+            ]
+        );
+        assert_eq!(
+            parse_description("Lasts {convert_turns} turns", &balance).unwrap()[0]
+                .0
+                 .0,
+            format!("Lasts {} turns", balance.convert_enemy_turns_to_convert)
+        );
+        assert_eq!(
+            parse_description("{c:pink}x{/c}", &balance).err(),
+            Some(MarkupError::UnknownColor("pink".to_string()))
+        );
+        assert_eq!(
+            parse_description("{c:primary}x", &balance).err(),
+            Some(MarkupError::Unclosed)
+        );
+        assert_eq!(
+            parse_description("x{/c}", &balance).err(),
+            Some(MarkupError::UnexpectedClose)
+        );
+        assert_eq!(
+            parse_description("{nope}", &balance).err(),
+            Some(MarkupError::UnknownPlaceholder("nope".to_string()))
+        );
+    }
 }
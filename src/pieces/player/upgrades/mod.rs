@@ -1,3 +1,4 @@
 pub mod data;
 pub mod stats;
+pub mod synergies;
 pub mod unique_upgrades;
@@ -1,6 +1,8 @@
+use serde::Deserialize;
+
 use super::data::{Effect, Upgrades};
 
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Default)]
 pub enum StatVariant {
     #[default]
     MaxHealth,
@@ -36,7 +38,7 @@ impl Stat {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct StatEffect {
     pub stat: StatVariant,
     pub additive: f32,
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use crate::{globals::SYNERGY_UPGRADE_NUMBER, pieces::movement_type::MovementType};
+
+use super::data::Upgrades;
+
+/// Bonus of owning enough upgrades of two movement types
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Synergy {
+    /// Knight and Bishop: chained attacks pierce one more piece
+    PiercingChains,
+    /// Rook and King: Block adds up instead of being replaced
+    StackingBlock,
+    /// Pawn and Queen: converted pieces may repeat their attacks like the queen
+    InheritedRepeat,
+}
+
+impl Synergy {
+    pub const ALL: [Synergy; 3] = [
+        Synergy::PiercingChains,
+        Synergy::StackingBlock,
+        Synergy::InheritedRepeat,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Synergy::PiercingChains => "Chains pierce",
+            Synergy::StackingBlock => "Block stacks",
+            Synergy::InheritedRepeat => "Converted pieces repeat attacks like the queen",
+        }
+    }
+
+    pub fn movement_types(&self) -> [MovementType; 2] {
+        match self {
+            Synergy::PiercingChains => [MovementType::Knight, MovementType::Bishop],
+            Synergy::StackingBlock => [MovementType::Rook, MovementType::King],
+            Synergy::InheritedRepeat => [MovementType::WhitePawn, MovementType::Queen],
+        }
+    }
+
+    /// Upgrades owned of each movement type, capped at the number needed
+    pub fn progress(&self, upgrades: &Upgrades) -> [(MovementType, usize); 2] {
+        let counts = upgrades.get_movement_types_count();
+        self.movement_types().map(|movement_type| {
+            let owned = match movement_type {
+                // both pawns count as the same piece
+                MovementType::WhitePawn | MovementType::BlackPawn => counts
+                    .get(&MovementType::WhitePawn)
+                    .max(counts.get(&MovementType::BlackPawn))
+                    .copied(),
+                _ => counts.get(&movement_type).copied(),
+            };
+            (
+                movement_type,
+                owned.unwrap_or(0).min(SYNERGY_UPGRADE_NUMBER),
+            )
+        })
+    }
+
+    pub fn is_active(&self, upgrades: &Upgrades) -> bool {
+        self.progress(upgrades)
+            .iter()
+            .all(|(_, owned)| *owned >= SYNERGY_UPGRADE_NUMBER)
+    }
+}
+
+impl fmt::Display for Synergy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Synergy::PiercingChains => write!(f, "Piercing Chains"),
+            Synergy::StackingBlock => write!(f, "Fortress"),
+            Synergy::InheritedRepeat => write!(f, "Royal Court"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::player::upgrades::data::UpgradeCatalog;
+
+    #[test]
+    fn test_synergy_progress() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![
+            catalog.get_movement_upgrade(&MovementType::Queen),
+            catalog.get_movement_upgrade(&MovementType::Queen),
+            catalog.get_movement_upgrade(&MovementType::BlackPawn),
+            catalog.get_movement_upgrade(&MovementType::Knight),
+            catalog.get_movement_upgrade(&MovementType::Knight),
+            catalog.get_movement_upgrade(&MovementType::Knight),
+        ]);
+        assert_eq!(
+            Synergy::InheritedRepeat.progress(&upgrades),
+            [(MovementType::WhitePawn, 1), (MovementType::Queen, 2)]
+        );
+        assert!(!Synergy::InheritedRepeat.is_active(&upgrades));
+        assert_eq!(
+            Synergy::PiercingChains.progress(&upgrades),
+            [(MovementType::Knight, 2), (MovementType::Bishop, 0)]
+        );
+
+        let mut upgrades = upgrades;
+        upgrades
+            .0
+            .push(catalog.get_movement_upgrade(&MovementType::BlackPawn));
+        assert!(Synergy::InheritedRepeat.is_active(&upgrades));
+        assert!(!Synergy::StackingBlock.is_active(&upgrades));
+    }
+}
@@ -5,9 +5,11 @@ use crate::{
     board::position::BoardPosition,
     globals::SPRITESHEET_WIDTH,
     pieces::{
-        attack::AttackPieceEvent,
+        attack::{AttackPieceEvent, AttackSource},
         common::{Piece, Team},
+        enemies::roster::EnemyRoster,
     },
+    utils::rng::GameRng,
 };
 
 use super::SideEffect;
@@ -15,7 +17,9 @@ use super::SideEffect;
 pub fn apply_side_effect(
     mut side_effect_event: EventReader<SideEffect>,
     mut attack_writer: EventWriter<AttackPieceEvent>,
+    roster: Res<EnemyRoster>,
     pieces: Query<(&BoardPosition, &Team, Entity), With<Piece>>,
+    mut rng: ResMut<GameRng>,
 ) {
     for side_effect in side_effect_event.read() {
         if let SideEffect::AttackRandomTarget {
@@ -25,7 +29,7 @@ pub fn apply_side_effect(
         {
             let (_, attacker_team, _) = pieces.get(generator_event.attacker).unwrap();
             let enemies = pieces.iter().filter(|(_, &team, _)| team != *attacker_team);
-            let (target_pos, _, target_entity) = enemies.choose(&mut rand::thread_rng()).unwrap();
+            let (target_pos, _, target_entity) = enemies.choose(&mut rng.0).unwrap();
 
             attack_writer.send(AttackPieceEvent {
                 attacker: generator_event.attacker,
@@ -33,12 +37,12 @@ pub fn apply_side_effect(
                 target: target_entity,
                 damage: *damage,
                 sprite_index: Some(
-                    generator_event.movement_type.sprite_index() + SPRITESHEET_WIDTH,
+                    generator_event.movement_type.sprite_index(&roster) + SPRITESHEET_WIDTH,
                 ),
                 movement_type: generator_event.movement_type.clone(),
                 delay: generator_event.delay,
                 origin: generator_event.origin,
-                upgrades_applied: true,
+                source: AttackSource::SideEffect,
             });
         }
     }
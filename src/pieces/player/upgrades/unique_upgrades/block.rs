@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::pieces::common::Piece;
+use crate::pieces::{
+    common::Piece,
+    player::upgrades::{data::Upgrades, synergies::Synergy},
+};
 
 use super::SideEffect;
 
@@ -11,15 +14,22 @@ pub struct Block {
 
 pub fn apply_side_effect(
     mut side_effect_event: EventReader<SideEffect>,
-    pieces: Query<&Piece>,
+    pieces: Query<(Option<&Block>, Option<&Upgrades>), With<Piece>>,
     mut commands: Commands,
 ) {
     for event in side_effect_event.read() {
         if let SideEffect::Block { amount, entity } = event {
             // ensure the entity is a piece
-            if pieces.get(*entity).is_ok() {
-                commands.entity(*entity).insert(Block { amount: *amount });
-            }
+            let Ok((block, upgrades)) = pieces.get(*entity) else {
+                continue;
+            };
+            let stacks =
+                upgrades.is_some_and(|upgrades| Synergy::StackingBlock.is_active(upgrades));
+            let amount = match block {
+                Some(block) if stacks => block.amount + amount,
+                _ => *amount,
+            };
+            commands.entity(*entity).insert(Block { amount });
         }
     }
 }
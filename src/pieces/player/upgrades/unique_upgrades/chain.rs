@@ -1,11 +1,19 @@
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 use crate::{
     board::position::BoardPosition,
-    globals::{ATTACK_ANIMATION_DURATION, SPRITESHEET_WIDTH},
+    globals::{ATTACK_ANIMATION_DURATION, SPRITESHEET_WIDTH, SYNERGY_PIERCE_COUNT},
     pieces::{
-        attack::AttackPieceEvent,
+        attack::{AttackPieceEvent, AttackSource},
         common::{Piece, Team},
+        enemies::roster::EnemyRoster,
+        player::{
+            character::Passive,
+            upgrades::{data::Upgrades, synergies::Synergy},
+        },
     },
     states::{game_state::GameState, turn_state::TurnState},
 };
@@ -18,58 +26,110 @@ pub struct HasChainedTo;
 pub fn apply_side_effect(
     mut side_effect_event: EventReader<SideEffect>,
     mut attack_writer: EventWriter<AttackPieceEvent>,
+    roster: Res<EnemyRoster>,
     pieces: Query<(&BoardPosition, &Team, Entity), (With<Piece>, Without<HasChainedTo>)>,
+    passives: Query<&Passive>,
+    upgrades: Query<&Upgrades>,
     mut commands: Commands,
 ) {
+    let mut pierces = Vec::new();
+    // pieces chained to this frame, before their component is inserted
+    let mut chained = HashSet::new();
     for side_effect in side_effect_event.read() {
         if let SideEffect::Chain {
             damage,
             generator_event,
+            hit_twice,
         } = side_effect
         {
             let (_, attacker_team, _) = pieces.get(generator_event.attacker).unwrap();
-            let enemies = pieces
-                .iter()
-                .filter(|(_, &team, _)| team != *attacker_team)
-                .map(|(pos, _, _)| *pos)
-                .collect();
-            let other_pieces = pieces
-                .iter()
-                .filter(|(_, _, entity)| *entity != generator_event.attacker)
-                .map(|(pos, _, _)| *pos)
-                .collect();
-            let position_to_entity: HashMap<BoardPosition, Entity> = pieces
+            let board: Vec<(BoardPosition, Team, Entity)> = pieces
                 .iter()
-                .map(|(pos, _, entity)| (*pos, entity))
+                .filter(|(_, _, entity)| !chained.contains(entity))
+                .map(|(pos, team, entity)| (*pos, *team, entity))
                 .collect();
-            let targets = generator_event
-                .movement_type
-                .get_valid_moves(&generator_event.destination, &other_pieces, &enemies)
-                .valid_attacks;
+            let targets = get_chain_targets(generator_event, *attacker_team, &board);
 
             let previous_delay = generator_event.delay.unwrap_or(0.0);
-            let delay = previous_delay + ATTACK_ANIMATION_DURATION;
+            // the second knight tier and the passive both hit twice, they don't add up
+            let double_chain = matches!(
+                passives.get(generator_event.attacker),
+                Ok(Passive::DoubleChain)
+            );
+            let hits = if *hit_twice || double_chain { 2 } else { 1 };
+            let piercing = upgrades
+                .get(generator_event.attacker)
+                .is_ok_and(|upgrades| Synergy::PiercingChains.is_active(upgrades));
 
-            for target in targets {
+            for (target, target_entity) in targets {
                 debug!("Chaining to: {:?}", target);
-                let target_entity = *position_to_entity.get(&target).unwrap();
-                attack_writer.send(AttackPieceEvent {
-                    attacker: generator_event.attacker,
-                    destination: target,
-                    origin: generator_event.destination,
-                    target: target_entity,
-                    damage: *damage,
-                    sprite_index: Some(
-                        generator_event.movement_type.sprite_index() + SPRITESHEET_WIDTH,
-                    ),
-                    movement_type: generator_event.movement_type.clone(),
-                    delay: Some(delay),
-                    upgrades_applied: true,
-                });
+                for hit in 1..=hits {
+                    let chain_event = AttackPieceEvent {
+                        attacker: generator_event.attacker,
+                        destination: target,
+                        origin: generator_event.destination,
+                        target: target_entity,
+                        damage: *damage,
+                        sprite_index: Some(
+                            generator_event.movement_type.sprite_index(&roster) + SPRITESHEET_WIDTH,
+                        ),
+                        movement_type: generator_event.movement_type.clone(),
+                        delay: Some(previous_delay + ATTACK_ANIMATION_DURATION * hit as f32),
+                        source: AttackSource::SideEffect,
+                    };
+                    if piercing && hit == hits {
+                        pierces.push(SideEffect::Pierce {
+                            pierce_count: Some(SYNERGY_PIERCE_COUNT),
+                            damage: *damage,
+                            damage_multiplier: 1.0,
+                            generator_event: chain_event.clone(),
+                        });
+                    }
+                    attack_writer.send(chain_event);
+                }
+                chained.insert(target_entity);
                 commands.entity(target_entity).insert(HasChainedTo);
             }
         }
     }
+
+    for side_effect in pierces {
+        commands.queue(move |world: &mut World| {
+            world.send_event(side_effect);
+        });
+    }
+}
+
+/// Pieces the attack described by `generator_event` chains to
+///
+/// `board` should not contain the pieces that have already been chained to.
+pub fn get_chain_targets(
+    generator_event: &AttackPieceEvent,
+    attacker_team: Team,
+    board: &[(BoardPosition, Team, Entity)],
+) -> Vec<(BoardPosition, Entity)> {
+    let enemies = board
+        .iter()
+        .filter(|(_, team, _)| *team != attacker_team)
+        .map(|(pos, _, _)| *pos)
+        .collect();
+    let other_pieces = board
+        .iter()
+        .filter(|(_, _, entity)| *entity != generator_event.attacker)
+        .map(|(pos, _, _)| *pos)
+        .collect();
+    let position_to_entity: HashMap<BoardPosition, Entity> = board
+        .iter()
+        .map(|(pos, _, entity)| (*pos, *entity))
+        .collect();
+
+    generator_event
+        .movement_type
+        .get_valid_moves(&generator_event.destination, &other_pieces, &enemies)
+        .valid_attacks
+        .into_iter()
+        .map(|target| (target, *position_to_entity.get(&target).unwrap()))
+        .collect()
 }
 
 pub fn remove_chain_component(mut commands: Commands, pieces: Query<Entity, With<HasChainedTo>>) {
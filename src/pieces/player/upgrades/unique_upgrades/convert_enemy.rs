@@ -16,6 +16,7 @@ pub fn apply_side_effect(
             turns_to_convert,
             team,
             entity,
+            repeat_chance,
         } = side_effect
         {
             if let Ok((original_team, mut sprite)) = pieces.get_mut(*entity) {
@@ -24,6 +25,7 @@ pub fn apply_side_effect(
                     turns_to_convert: *turns_to_convert,
                     original_team: *original_team,
                     original_sprite_index: sprite.texture_atlas.as_mut().unwrap().index,
+                    repeat_chance: *repeat_chance,
                 });
                 commands
                     .entity(*entity)
@@ -36,23 +38,37 @@ pub fn apply_side_effect(
     }
 }
 
+/// Converted piece back in its original team
+#[derive(Event)]
+pub struct ConversionEndedEvent {
+    pub entity: Entity,
+}
+
 #[derive(Component)]
 pub struct Converted {
-    turns_to_convert: usize,
+    /// Permanent conversion if not set
+    turns_to_convert: Option<usize>,
     original_team: Team,
     original_sprite_index: usize,
+    /// Inherited from the queen of the hero that converted the piece
+    pub repeat_chance: f32,
 }
 
 pub fn decrement_turns_to_convert(
     mut converted_query: Query<(Entity, &mut Converted, &mut Sprite, &Team)>,
     mut commands: Commands,
+    mut conversion_ended_writer: EventWriter<ConversionEndedEvent>,
 ) {
     for (entity, mut converted, mut sprite, _) in converted_query.iter_mut() {
-        converted.turns_to_convert -= 1;
-        if converted.turns_to_convert == 0 {
+        let Some(turns_to_convert) = converted.turns_to_convert.as_mut() else {
+            continue;
+        };
+        *turns_to_convert -= 1;
+        if *turns_to_convert == 0 {
             commands.entity(entity).insert(converted.original_team);
             commands.entity(entity).remove::<Converted>();
             sprite.texture_atlas.as_mut().unwrap().index = converted.original_sprite_index;
+            conversion_ended_writer.send(ConversionEndedEvent { entity });
         } else {
             sprite.texture_atlas.as_mut().unwrap().index += SPRITESHEET_WIDTH;
         }
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    globals::MOVEMENT_TYPE_LIMITS,
+    game_logic::balance::Balance,
     pieces::player::{
         spawn::Player,
         upgrades::data::{Effect, Upgrades},
@@ -16,6 +16,7 @@ pub struct MovementTypeLimit {
 pub fn apply_movement_type_limit(
     mut commands: Commands,
     query: Query<(&Upgrades, Entity), With<Player>>,
+    balance: Res<Balance>,
 ) {
     let mut n_upgrades = 0;
     let (upgrades, entity) = query.get_single().unwrap();
@@ -25,12 +26,7 @@ pub fn apply_movement_type_limit(
         }
     }
 
-    for limit in MOVEMENT_TYPE_LIMITS.iter().rev() {
-        if n_upgrades >= limit.0 {
-            commands
-                .entity(entity)
-                .insert(MovementTypeLimit { limit: limit.1 });
-            return;
-        }
-    }
+    commands.entity(entity).insert(MovementTypeLimit {
+        limit: balance.movement_type_limit(n_upgrades),
+    });
 }
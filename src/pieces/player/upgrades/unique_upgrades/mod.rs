@@ -1,26 +1,32 @@
 use bevy::prelude::*;
 use limit::apply_movement_type_limit;
+use rand::Rng;
 
 use crate::{
-    globals::{
-        ATTACK_ANIMATION_DURATION, CONVERT_ENEMY_TURNS_TO_CONVERT, QUEEN_UNIQUE_CHANCE,
-        UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER, UNIQUE_UPGRADE_DAMAGE_MULTIPLIER,
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+    },
+    globals::{ABILITY_TIER_UNLOCKS, ATTACK_ANIMATION_DURATION, UNIQUE_UPGRADE_DAMAGE_MULTIPLIER},
+    pieces::{
+        attack::{AttackPieceEvent, AttackSource},
+        common::Team,
+        movement_type::MovementType,
     },
-    pieces::{attack::AttackPieceEvent, common::Team, movement_type::MovementType},
     states::{game_state::GameState, turn_state::TurnState},
     ui::shop::ApplyUpgrades,
 };
 
-use super::data::Upgrades;
+use super::{data::Upgrades, synergies::Synergy};
 mod attack_random_target;
 pub mod block;
-mod chain;
+pub mod chain;
 pub mod convert_enemy;
 pub mod immortal;
 pub mod limit;
-mod pierce;
+pub mod pierce;
 
-#[derive(Event)]
+#[derive(Event, Clone)]
 pub enum SideEffect {
     AttackRandomTarget {
         damage: f32,
@@ -29,15 +35,24 @@ pub enum SideEffect {
     Chain {
         damage: f32,
         generator_event: AttackPieceEvent,
+        /// Second knight tier, each chained piece is hit twice
+        ///
+        /// Does not stack with `Passive::DoubleChain`, which hits twice as well.
+        hit_twice: bool,
     },
     ConvertPiece {
-        turns_to_convert: usize,
+        /// Permanent conversion if not set
+        turns_to_convert: Option<usize>,
         team: Team,
         entity: Entity,
+        /// Chance for the converted piece to repeat its attacks
+        repeat_chance: f32,
     },
     Pierce {
         pierce_count: Option<usize>,
         damage: f32,
+        /// Damage multiplier of each piece pierced through
+        damage_multiplier: f32,
         generator_event: AttackPieceEvent,
     },
     Block {
@@ -47,75 +62,140 @@ pub enum SideEffect {
     Nothing,
 }
 
-pub fn apply_unique_upgrades(
+/// Outcome of the unique upgrades of an attack, before any chance is rolled
+pub struct UniqueUpgradesOutcome {
+    pub side_effect: Option<SideEffect>,
+    pub repeat_chance: f32,
+}
+
+/// Unique ability tier unlocked by this many upgrades of a movement type, 0 if locked
+pub fn ability_tier(count: usize) -> usize {
+    ABILITY_TIER_UNLOCKS
+        .iter()
+        .filter(|unlock| count >= **unlock)
+        .count()
+}
+
+/// Applies the unique upgrade damage bonus to the attack and resolves its side effect
+pub fn resolve_unique_upgrades(
     attack: &mut AttackPieceEvent,
     upgrades: &Upgrades,
-    side_effect_event_writter: &mut EventWriter<SideEffect>,
-    commands: &mut Commands,
-) {
+    balance: &Balance,
+) -> UniqueUpgradesOutcome {
     let movement_upgrades = upgrades.get_movement_types_count();
+    let mut side_effect = None;
     if let Some(&count) = movement_upgrades.get(&attack.movement_type) {
         debug!(
             "Applying unique upgrade for movement type: {:?}, count: {}",
             attack.movement_type, count
         );
-        if !attack.upgrades_applied {
+        if attack.source == AttackSource::Direct {
             attack.damage += UNIQUE_UPGRADE_DAMAGE_MULTIPLIER * (count - 1) as f32;
         }
 
-        if count >= UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER {
-            let side_effect = fetch_side_effect(attack);
-            side_effect_event_writter.send(side_effect);
+        // pierced attacks go on through their own side effect, chained attacks chain again
+        let pierced = attack.source == AttackSource::SideEffect
+            && attack.movement_type == MovementType::Bishop;
+        let tier = ability_tier(count);
+        if tier > 0 && !pierced {
+            side_effect = Some(fetch_side_effect(attack, tier, balance));
+        }
+    }
+
+    if let Some(SideEffect::ConvertPiece { repeat_chance, .. }) = side_effect.as_mut() {
+        if Synergy::InheritedRepeat.is_active(upgrades) {
+            *repeat_chance = balance.queen_unique_chance;
         }
     }
 
     // Queen unique ability is a bit different,
-    // it has a chance to repeat any attack
-    if let Some(queen_count) = movement_upgrades.get(&MovementType::Queen) {
-        if *queen_count >= UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER {
-            let chance = QUEEN_UNIQUE_CHANCE;
-            let random_value = rand::random::<f32>();
-            let mut new_event = attack.clone();
-
-            if let Some(delay) = new_event.delay {
-                new_event.delay = Some(delay + ATTACK_ANIMATION_DURATION);
-            } else {
-                new_event.delay = Some(ATTACK_ANIMATION_DURATION);
-            }
-            new_event.upgrades_applied = true;
-
-            if random_value < chance {
-                commands.queue(move |world: &mut World| {
-                    world.send_event(new_event);
-                });
-            }
+    // it has a chance to repeat any attack, and repeats from the second tier
+    let queen_tier = movement_upgrades
+        .get(&MovementType::Queen)
+        .map_or(0, |count| ability_tier(*count));
+    let repeat_chance =
+        if queen_tier == 0 || (attack.source == AttackSource::Repeat && queen_tier < 2) {
+            0.0
+        } else {
+            balance.queen_unique_chance
+        };
+
+    UniqueUpgradesOutcome {
+        side_effect,
+        repeat_chance,
+    }
+}
+
+pub fn apply_unique_upgrades(
+    attack: &mut AttackPieceEvent,
+    upgrades: &Upgrades,
+    balance: &Balance,
+    inherited_repeat_chance: f32,
+    side_effect_event_writter: &mut EventWriter<SideEffect>,
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+) {
+    let mut outcome = resolve_unique_upgrades(attack, upgrades, balance);
+    if attack.source != AttackSource::Repeat {
+        outcome.repeat_chance = outcome.repeat_chance.max(inherited_repeat_chance);
+    }
+    if let Some(side_effect) = outcome.side_effect {
+        side_effect_event_writter.send(side_effect);
+    }
+
+    if outcome.repeat_chance > 0.0 {
+        let random_value = rng.gen::<f32>();
+        let mut new_event = attack.clone();
+
+        if let Some(delay) = new_event.delay {
+            new_event.delay = Some(delay + ATTACK_ANIMATION_DURATION);
+        } else {
+            new_event.delay = Some(ATTACK_ANIMATION_DURATION);
+        }
+        new_event.source = AttackSource::Repeat;
+
+        if random_value < outcome.repeat_chance {
+            commands.queue(move |world: &mut World| {
+                world.send_event(new_event);
+                world.send_event(CombatLogEvent::new(
+                    LogCategory::SideEffect,
+                    "Queen ability repeats the attack",
+                ));
+            });
         }
     }
 }
 
-fn fetch_side_effect(attack: &AttackPieceEvent) -> SideEffect {
+fn fetch_side_effect(attack: &AttackPieceEvent, tier: usize, balance: &Balance) -> SideEffect {
+    let advanced = tier >= 2;
     match attack.movement_type {
         MovementType::Knight => SideEffect::Chain {
             damage: attack.damage,
             generator_event: attack.clone(),
+            hit_twice: advanced,
         },
-        MovementType::BlackPawn => SideEffect::ConvertPiece {
-            turns_to_convert: CONVERT_ENEMY_TURNS_TO_CONVERT,
-            team: Team::Player,
-            entity: attack.target,
-        },
-        MovementType::WhitePawn => SideEffect::ConvertPiece {
-            turns_to_convert: CONVERT_ENEMY_TURNS_TO_CONVERT,
+        MovementType::WhitePawn | MovementType::BlackPawn => SideEffect::ConvertPiece {
+            turns_to_convert: (!advanced).then_some(balance.convert_enemy_turns_to_convert),
             team: Team::Player,
             entity: attack.target,
+            repeat_chance: 0.0,
         },
         MovementType::Bishop => SideEffect::Pierce {
             pierce_count: None,
             damage: attack.damage,
+            damage_multiplier: if advanced {
+                1.0
+            } else {
+                balance.pierce_damage_falloff
+            },
             generator_event: attack.clone(),
         },
         MovementType::Rook => SideEffect::Block {
-            amount: 1,
+            amount: if advanced {
+                balance.advanced_rook_block
+            } else {
+                1
+            },
             entity: attack.attacker,
         },
         MovementType::King => SideEffect::Nothing,
@@ -127,7 +207,8 @@ pub struct UniqueUpgradesPlugin;
 
 impl Plugin for UniqueUpgradesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<SideEffect>();
+        app.add_event::<SideEffect>()
+            .add_event::<convert_enemy::ConversionEndedEvent>();
         app.add_systems(
             Update,
             (
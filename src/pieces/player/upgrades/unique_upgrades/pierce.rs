@@ -4,8 +4,9 @@ use crate::{
     board::position::BoardPosition,
     globals::{ATTACK_ANIMATION_DURATION, SPRITESHEET_WIDTH},
     pieces::{
-        attack::AttackPieceEvent,
+        attack::{AttackPieceEvent, AttackSource},
         common::{Piece, Team},
+        enemies::roster::EnemyRoster,
     },
 };
 
@@ -14,6 +15,7 @@ use super::SideEffect;
 pub fn apply_side_effect(
     mut side_effect_event: EventReader<SideEffect>,
     mut attack_writer: EventWriter<AttackPieceEvent>,
+    roster: Res<EnemyRoster>,
     pieces: Query<(&BoardPosition, &Team, Entity), With<Piece>>,
     mut commands: Commands,
 ) {
@@ -22,69 +24,54 @@ pub fn apply_side_effect(
         if let SideEffect::Pierce {
             pierce_count,
             damage,
+            damage_multiplier,
             generator_event,
         } = side_effect
         {
+            if *pierce_count == Some(0) {
+                continue;
+            }
             let (_, attacker_team, _) = pieces.get(generator_event.attacker).unwrap();
-            let enemies = pieces
-                .iter()
-                .filter(|(_, &team, _)| team != *attacker_team)
-                .map(|(pos, _, _)| *pos)
-                .collect();
-            let other_pieces = pieces
-                .iter()
-                .filter(|(_, _, entity)| *entity != generator_event.attacker)
-                .map(|(pos, _, _)| *pos)
-                .collect();
-            let position_to_entity: HashMap<BoardPosition, Entity> = pieces
+            let board: Vec<(BoardPosition, Team, Entity)> = pieces
                 .iter()
-                .map(|(pos, _, entity)| (*pos, entity))
+                .map(|(pos, team, entity)| (*pos, *team, entity))
                 .collect();
 
             let previous_delay = generator_event.delay.unwrap_or(0.0);
             let delay = previous_delay + ATTACK_ANIMATION_DURATION;
 
-            let valid_targets: Vec<BoardPosition> = generator_event
-                .movement_type
-                .get_valid_moves(&generator_event.destination, &other_pieces, &enemies)
-                .valid_attacks
-                .iter()
-                .filter(|target| {
-                    // ensure the target is in the same direction as the attack
-                    let d1 = get_direction(**target, generator_event.destination);
-                    let d2 = get_direction(generator_event.destination, generator_event.origin);
-                    d1 == d2
-                })
-                .copied()
-                .collect();
+            let valid_targets = get_pierce_targets(generator_event, *attacker_team, &board);
+            let damage = damage * damage_multiplier;
 
-            for target in valid_targets {
+            for (target, target_entity) in valid_targets {
                 debug!("Piercing to: {:?}", target);
                 let new_attack_piece_event = AttackPieceEvent {
                     attacker: generator_event.attacker,
                     destination: target,
                     origin: generator_event.destination,
-                    target: *position_to_entity.get(&target).unwrap(),
-                    damage: *damage,
+                    target: target_entity,
+                    damage,
                     sprite_index: Some(
-                        generator_event.movement_type.sprite_index() + SPRITESHEET_WIDTH,
+                        generator_event.movement_type.sprite_index(&roster) + SPRITESHEET_WIDTH,
                     ),
                     movement_type: generator_event.movement_type.clone(),
                     delay: Some(delay),
-                    upgrades_applied: true,
+                    source: AttackSource::SideEffect,
                 };
                 attack_writer.send(new_attack_piece_event.clone());
                 if let Some(pierce_count) = pierce_count {
                     new_side_effects.push(SideEffect::Pierce {
                         pierce_count: Some(pierce_count - 1),
-                        damage: *damage,
+                        damage,
+                        damage_multiplier: *damage_multiplier,
                         generator_event: new_attack_piece_event,
                     });
                 } else {
                     // infinite pierce
                     new_side_effects.push(SideEffect::Pierce {
                         pierce_count: None,
-                        damage: *damage,
+                        damage,
+                        damage_multiplier: *damage_multiplier,
                         generator_event: new_attack_piece_event,
                     });
                 }
@@ -99,6 +86,42 @@ pub fn apply_side_effect(
     }
 }
 
+/// Pieces behind the target of `generator_event`, in the direction of the attack
+pub fn get_pierce_targets(
+    generator_event: &AttackPieceEvent,
+    attacker_team: Team,
+    board: &[(BoardPosition, Team, Entity)],
+) -> Vec<(BoardPosition, Entity)> {
+    let enemies = board
+        .iter()
+        .filter(|(_, team, _)| *team != attacker_team)
+        .map(|(pos, _, _)| *pos)
+        .collect();
+    let other_pieces = board
+        .iter()
+        .filter(|(_, _, entity)| *entity != generator_event.attacker)
+        .map(|(pos, _, _)| *pos)
+        .collect();
+    let position_to_entity: HashMap<BoardPosition, Entity> = board
+        .iter()
+        .map(|(pos, _, entity)| (*pos, *entity))
+        .collect();
+
+    generator_event
+        .movement_type
+        .get_valid_moves(&generator_event.destination, &other_pieces, &enemies)
+        .valid_attacks
+        .into_iter()
+        .filter(|target| {
+            // ensure the target is in the same direction as the attack
+            let d1 = get_direction(*target, generator_event.destination);
+            let d2 = get_direction(generator_event.destination, generator_event.origin);
+            d1 == d2
+        })
+        .map(|target| (target, *position_to_entity.get(&target).unwrap()))
+        .collect()
+}
+
 fn get_direction(origin: BoardPosition, destination: BoardPosition) -> I64Vec2 {
     let d = destination - origin;
 
@@ -0,0 +1,462 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{board::position::BoardPosition, game_logic::balance::Balance};
+
+use super::{
+    attack::{AttackPieceEvent, AttackSource},
+    common::Team,
+    enemies::ai::ai_attacks,
+    health::killed_by,
+    movement_type::MovementType,
+    player::{
+        consumables::{hero_movement_types, Promoted},
+        upgrades::{
+            data::Upgrades,
+            unique_upgrades::{
+                ability_tier, chain::get_chain_targets, pierce::get_pierce_targets,
+                resolve_unique_upgrades, SideEffect,
+            },
+        },
+    },
+};
+
+/// Snapshot of a non player piece, used to preview the outcome of an action
+pub struct PreviewPiece {
+    pub entity: Entity,
+    pub position: BoardPosition,
+    pub team: Team,
+    pub health: f32,
+    pub block: usize,
+    pub movement_types: HashSet<MovementType>,
+    pub name: String,
+}
+
+pub struct AttackPreview {
+    pub target: BoardPosition,
+    pub movement_type: MovementType,
+    pub damage: f32,
+    pub chain_targets: Vec<BoardPosition>,
+    pub pierce_targets: Vec<BoardPosition>,
+    pub block: usize,
+}
+
+pub struct ActionPreview {
+    pub moved: bool,
+    pub destination: BoardPosition,
+    pub attacks: Vec<AttackPreview>,
+    pub kills: Vec<String>,
+    pub repeat_chance: f32,
+    pub retaliations: Vec<String>,
+}
+
+/// Previews what clicking `tile` would do
+///
+/// Mirrors `click_tile_update_player_position` and the attack after move,
+/// and resolves the attacks, hits and enemy attacks through the same code the game uses.
+/// Returns `None` if clicking the tile would do nothing.
+pub fn preview_action(
+    tile: BoardPosition,
+    player_entity: Entity,
+    player_position: BoardPosition,
+    player_damage: f32,
+    player_upgrades: &Upgrades,
+    promoted: Option<&Promoted>,
+    balance: &Balance,
+    pieces: &[PreviewPiece],
+) -> Option<ActionPreview> {
+    let movement_types = hero_movement_types(player_upgrades, promoted);
+    let all_pieces_positions: HashSet<BoardPosition> = pieces.iter().map(|p| p.position).collect();
+    let enemy_pieces_positions: HashSet<BoardPosition> = pieces
+        .iter()
+        .filter(|p| p.team == Team::Enemy)
+        .map(|p| p.position)
+        .collect();
+
+    let moved = movement_types.iter().any(|movement_type| {
+        movement_type
+            .get_valid_moves(
+                &player_position,
+                &all_pieces_positions,
+                &enemy_pieces_positions,
+            )
+            .valid_moves
+            .contains(&tile)
+    });
+
+    // (origin, movement type) of every attack the action triggers
+    let mut attack_sources: Vec<(BoardPosition, MovementType)> = Vec::new();
+    if moved {
+        attack_sources.extend(movement_types.iter().map(|m| (tile, m.clone())));
+        // king unique ability, attack from original position and the squares on the way
+        let king_tier = player_upgrades
+            .get_movement_types_count()
+            .get(&MovementType::King)
+            .map_or(0, |count| ability_tier(*count));
+        if king_tier > 0 {
+            attack_sources.push((player_position, MovementType::King));
+        }
+        if king_tier >= 2 {
+            attack_sources.extend(
+                player_position
+                    .squares_between(&tile)
+                    .into_iter()
+                    .map(|square| (square, MovementType::King)),
+            );
+        }
+    } else {
+        attack_sources.extend(movement_types.iter().map(|m| (player_position, m.clone())));
+    }
+
+    let board: Vec<(BoardPosition, Team, Entity)> = pieces
+        .iter()
+        .map(|p| (p.position, p.team, p.entity))
+        .collect();
+    let entity_position: HashMap<Entity, BoardPosition> = board
+        .iter()
+        .map(|(pos, _, entity)| (*entity, *pos))
+        .collect();
+
+    let mut attacks = Vec::new();
+    let mut hits: HashMap<Entity, Vec<f32>> = HashMap::new();
+    let mut converted = HashSet::new();
+    let mut repeat_chance: f32 = 0.0;
+    for (origin, movement_type) in attack_sources {
+        let targets = movement_type
+            .get_valid_moves(&origin, &all_pieces_positions, &enemy_pieces_positions)
+            .valid_attacks;
+        for target in targets {
+            let Some(&(_, _, target_entity)) = board.iter().find(|(pos, _, _)| *pos == target)
+            else {
+                continue;
+            };
+            let mut event = AttackPieceEvent {
+                destination: target,
+                attacker: player_entity,
+                origin,
+                target: target_entity,
+                damage: player_damage,
+                sprite_index: None,
+                movement_type: movement_type.clone(),
+                delay: None,
+                source: AttackSource::Direct,
+            };
+            let outcome = resolve_unique_upgrades(&mut event, player_upgrades, balance);
+            repeat_chance = repeat_chance.max(outcome.repeat_chance);
+            hits.entry(target_entity).or_default().push(event.damage);
+
+            let mut preview = AttackPreview {
+                target,
+                movement_type: movement_type.clone(),
+                damage: event.damage,
+                chain_targets: Vec::new(),
+                pierce_targets: Vec::new(),
+                block: 0,
+            };
+            match outcome.side_effect {
+                Some(SideEffect::Chain {
+                    damage, hit_twice, ..
+                }) => {
+                    for (_, entity) in follow_chain(&event, &board) {
+                        let damages = hits.entry(entity).or_default();
+                        damages.extend(std::iter::repeat_n(damage, if hit_twice { 2 } else { 1 }));
+                        preview.chain_targets.push(entity_position[&entity]);
+                    }
+                }
+                Some(SideEffect::Pierce {
+                    damage,
+                    damage_multiplier,
+                    ..
+                }) => {
+                    for (_, entity, damage) in
+                        follow_pierce(&event, &board, damage, damage_multiplier)
+                    {
+                        hits.entry(entity).or_default().push(damage);
+                        preview.pierce_targets.push(entity_position[&entity]);
+                    }
+                }
+                Some(SideEffect::Block { amount, .. }) => {
+                    preview.block = amount;
+                }
+                Some(SideEffect::ConvertPiece { entity, .. }) => {
+                    converted.insert(entity);
+                }
+                _ => {}
+            }
+            attacks.push(preview);
+        }
+    }
+
+    // without moving, only clicking one of the attacked pieces counts
+    if !moved && !attacks.iter().any(|attack| attack.target == tile) {
+        return None;
+    }
+
+    let mut dead = HashSet::new();
+    let mut kills = Vec::new();
+    for piece in pieces.iter() {
+        let Some(damages) = hits.get(&piece.entity) else {
+            continue;
+        };
+        if killed_by(piece.health, piece.block, damages) {
+            dead.insert(piece.entity);
+            kills.push(piece.name.clone());
+        }
+    }
+
+    let destination = if moved { tile } else { player_position };
+    let survivors_positions: HashSet<BoardPosition> = pieces
+        .iter()
+        .filter(|p| !dead.contains(&p.entity))
+        .map(|p| p.position)
+        .chain([destination])
+        .collect();
+    let player_positions = HashSet::from([destination]);
+    let retaliations = pieces
+        .iter()
+        .filter(|p| p.team == Team::Enemy)
+        .filter(|p| !dead.contains(&p.entity) && !converted.contains(&p.entity))
+        .filter(|p| {
+            ai_attacks(
+                &p.position,
+                &p.movement_types,
+                &survivors_positions,
+                &player_positions,
+            )
+            .iter()
+            .any(|(_, attack)| *attack == destination)
+        })
+        .map(|p| p.name.clone())
+        .collect();
+
+    Some(ActionPreview {
+        moved,
+        destination,
+        attacks,
+        kills,
+        repeat_chance,
+        retaliations,
+    })
+}
+
+/// Every piece a chain started by `event` ends up hitting,
+/// chained pieces can not be chained to again
+fn follow_chain(
+    event: &AttackPieceEvent,
+    board: &[(BoardPosition, Team, Entity)],
+) -> Vec<(BoardPosition, Entity)> {
+    let mut chained: HashSet<Entity> = HashSet::new();
+    let mut frontier = vec![event.clone()];
+    let mut hit = Vec::new();
+    while let Some(generator_event) = frontier.pop() {
+        let remaining: Vec<_> = board
+            .iter()
+            .filter(|(_, _, entity)| !chained.contains(entity))
+            .copied()
+            .collect();
+        for (target, target_entity) in get_chain_targets(&generator_event, Team::Player, &remaining)
+        {
+            chained.insert(target_entity);
+            hit.push((target, target_entity));
+            frontier.push(AttackPieceEvent {
+                destination: target,
+                origin: generator_event.destination,
+                target: target_entity,
+                source: AttackSource::SideEffect,
+                ..generator_event.clone()
+            });
+        }
+    }
+    hit
+}
+
+/// Every piece a pierce started by `event` goes through, with the damage it takes
+fn follow_pierce(
+    event: &AttackPieceEvent,
+    board: &[(BoardPosition, Team, Entity)],
+    damage: f32,
+    damage_multiplier: f32,
+) -> Vec<(BoardPosition, Entity, f32)> {
+    let mut pierced: HashSet<Entity> = HashSet::new();
+    let mut frontier = vec![(event.clone(), damage)];
+    let mut hit = Vec::new();
+    while let Some((generator_event, damage)) = frontier.pop() {
+        let damage = damage * damage_multiplier;
+        for (target, target_entity) in get_pierce_targets(&generator_event, Team::Player, board) {
+            if !pierced.insert(target_entity) {
+                continue;
+            }
+            hit.push((target, target_entity, damage));
+            frontier.push((
+                AttackPieceEvent {
+                    destination: target,
+                    origin: generator_event.destination,
+                    target: target_entity,
+                    source: AttackSource::SideEffect,
+                    ..generator_event.clone()
+                },
+                damage,
+            ));
+        }
+    }
+    hit
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pieces::player::upgrades::data::UpgradeCatalog;
+
+    use super::*;
+
+    fn enemy(index: u32, x: i32, y: i32, health: f32) -> PreviewPiece {
+        PreviewPiece {
+            entity: Entity::from_raw(index),
+            position: BoardPosition::new(x, y).unwrap(),
+            team: Team::Enemy,
+            health,
+            block: 0,
+            movement_types: HashSet::from([MovementType::King]),
+            name: format!("Enemy {}", index),
+        }
+    }
+
+    #[test]
+    fn test_preview_attack_kills_and_damage_bonus() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![
+            catalog.get_movement_upgrade(&MovementType::Rook),
+            catalog.get_movement_upgrade(&MovementType::Rook),
+        ]);
+        let pieces = vec![enemy(1, 0, 3, 1.0)];
+        let preview = preview_action(
+            BoardPosition::new(0, 3).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &pieces,
+        )
+        .unwrap();
+
+        assert!(!preview.moved);
+        assert_eq!(preview.attacks.len(), 1);
+        assert!(preview.attacks[0].damage > 1.0);
+        assert_eq!(preview.attacks[0].block, 1);
+        assert_eq!(preview.kills, vec!["Enemy 1".to_string()]);
+    }
+
+    #[test]
+    fn test_preview_move_retaliation() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![catalog.get_movement_upgrade(&MovementType::Rook)]);
+        let pieces = vec![enemy(1, 5, 5, 3.0)];
+        let preview = preview_action(
+            BoardPosition::new(4, 0).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &pieces,
+        )
+        .unwrap();
+        assert!(preview.moved);
+        assert!(preview.retaliations.is_empty());
+
+        let preview = preview_action(
+            BoardPosition::new(0, 4).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &[enemy(1, 1, 5, 3.0)],
+        )
+        .unwrap();
+        assert_eq!(preview.retaliations, vec!["Enemy 1".to_string()]);
+    }
+
+    #[test]
+    fn test_preview_pierce_continuation() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![
+            catalog.get_movement_upgrade(&MovementType::Bishop),
+            catalog.get_movement_upgrade(&MovementType::Bishop),
+        ]);
+        let pieces = vec![enemy(1, 2, 2, 10.0), enemy(2, 4, 4, 1.0)];
+        let preview = preview_action(
+            BoardPosition::new(2, 2).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &pieces,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.attacks[0].pierce_targets,
+            vec![BoardPosition::new(4, 4).unwrap()]
+        );
+        // the first bishop tier pierces with the full damage
+        assert_eq!(preview.kills, vec!["Enemy 2".to_string()]);
+    }
+
+    #[test]
+    fn test_preview_chain_chains_again() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![
+            catalog.get_movement_upgrade(&MovementType::Knight),
+            catalog.get_movement_upgrade(&MovementType::Knight),
+        ]);
+        let pieces = vec![
+            enemy(1, 1, 2, 10.0),
+            enemy(2, 2, 4, 10.0),
+            enemy(3, 3, 6, 10.0),
+        ];
+        let preview = preview_action(
+            BoardPosition::new(1, 2).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &pieces,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.attacks[0].chain_targets,
+            vec![
+                BoardPosition::new(2, 4).unwrap(),
+                BoardPosition::new(3, 6).unwrap(),
+                // the attacked piece is not marked as chained to
+                BoardPosition::new(1, 2).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_nothing_to_do() {
+        let catalog = UpgradeCatalog::default();
+        let upgrades = Upgrades(vec![catalog.get_movement_upgrade(&MovementType::King)]);
+        let preview = preview_action(
+            BoardPosition::new(7, 7).unwrap(),
+            Entity::from_raw(0),
+            BoardPosition::new(0, 0).unwrap(),
+            1.0,
+            &upgrades,
+            None,
+            &Balance::default(),
+            &[],
+        );
+        assert!(preview.is_none());
+    }
+}
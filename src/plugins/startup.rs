@@ -12,7 +12,7 @@ impl Plugin for StartupPlugin {
     fn build(&self, app: &mut App) {
         app
             // Set up states
-            .insert_state(states::game_state::GameState::Game)
+            .insert_state(states::game_state::GameState::MainMenu)
             .insert_state(states::pause_state::GamePauseState::Playing)
             .insert_state(states::turn_state::TurnState::PlayerInput)
             .enable_state_scoped_entities::<states::pause_state::GamePauseState>()
@@ -27,7 +27,7 @@ impl Plugin for StartupPlugin {
             .add_event::<pieces::attack::AttackPieceEvent>()
             .add_event::<pieces::health::PieceHealthChangeEvent>()
             .add_event::<pieces::health::PieceDeathEvent>()
-            .add_systems(Startup, graphics::camera::setup_camera)
+            .add_plugins(graphics::camera::CameraPlugin)
             // One off systems
             .add_systems(OnEnter(GameState::Game), (board::tile::spawn_board,))
             .add_plugins(UiPlugin)
@@ -1,7 +1,13 @@
 use bevy::prelude::*;
 
 use crate::{
-    input::click_tile::{click_tile_update_player_position, update_hovered_tile, HoveredTile},
+    input::{
+        click_tile::{click_tile_update_player_position, update_hovered_tile, HoveredTile},
+        touch::{
+            reset_pending_attack, tap_tile_update_player_position, track_touch_press, TouchControls,
+        },
+    },
+    network::is_local_turn,
     states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
 };
 
@@ -11,11 +17,19 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (click_tile_update_player_position, update_hovered_tile)
+            (
+                click_tile_update_player_position,
+                update_hovered_tile,
+                (track_touch_press, tap_tile_update_player_position).chain(),
+            )
                 .run_if(in_state(GameState::Game))
                 .run_if(in_state(TurnState::PlayerInput))
-                .run_if(in_state(GamePauseState::Playing)),
+                .run_if(in_state(GamePauseState::Playing))
+                // the other online player's hero is moved by its actions
+                .run_if(is_local_turn),
         );
+        app.add_systems(OnExit(TurnState::PlayerInput), reset_pending_attack);
         app.insert_resource(HoveredTile(None));
+        app.init_resource::<TouchControls>();
     }
 }
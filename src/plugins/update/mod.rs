@@ -4,6 +4,7 @@ use crate::{
     board::highlight,
     game_logic::{score::GameScorePlugin, GameLogicPlugin},
     input::keyboard::KeyboardPlugin,
+    network::NetworkPlugin,
     pieces::{player::PlayerPlugin, plugin::PiecePlugin},
     states::{game_state::GameStatePlugin, turn_state},
 };
@@ -27,6 +28,7 @@ impl Plugin for UpdatePlugin {
             GameStatePlugin,
             PlayerPlugin,
             KeyboardPlugin,
+            NetworkPlugin,
         ));
     }
 }
@@ -9,8 +9,15 @@ use super::{
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, States)]
 pub enum GameState {
     MainMenu,
+    CharacterSelect,
     Game,
     Defeat,
+    Victory,
+    Leaderboard,
+    Stats,
+    Profile,
+    PuzzleSelect,
+    Lobby,
     Restart,
 }
 
@@ -18,8 +25,15 @@ impl fmt::Display for GameState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             GameState::MainMenu => write!(f, "MainMenu"),
+            GameState::CharacterSelect => write!(f, "CharacterSelect"),
             GameState::Game => write!(f, "Game"),
             GameState::Defeat => write!(f, "Defeat"),
+            GameState::Victory => write!(f, "Victory"),
+            GameState::Leaderboard => write!(f, "Leaderboard"),
+            GameState::Stats => write!(f, "Stats"),
+            GameState::Profile => write!(f, "Profile"),
+            GameState::PuzzleSelect => write!(f, "PuzzleSelect"),
+            GameState::Lobby => write!(f, "Lobby"),
             GameState::Restart => write!(f, "Restart"),
         }
     }
@@ -38,8 +52,15 @@ impl GameState {
     pub fn get_color(&self) -> Color {
         match self {
             GameState::MainMenu => Color::srgb(1.0, 0.0, 0.0),
+            GameState::CharacterSelect => Color::srgb(0.5, 0.0, 1.0),
             GameState::Game => Color::srgb(0.0, 1.0, 0.0),
             GameState::Defeat => Color::srgb(0.0, 0.0, 1.0),
+            GameState::Victory => Color::srgb(1.0, 1.0, 1.0),
+            GameState::Leaderboard => Color::srgb(1.0, 0.0, 1.0),
+            GameState::Stats => Color::srgb(0.0, 1.0, 1.0),
+            GameState::Profile => Color::srgb(1.0, 0.5, 0.0),
+            GameState::PuzzleSelect => Color::srgb(0.5, 1.0, 0.0),
+            GameState::Lobby => Color::srgb(0.0, 0.5, 1.0),
             GameState::Restart => Color::srgb(1.0, 1.0, 0.0),
         }
     }
@@ -2,6 +2,8 @@ use std::fmt;
 
 use bevy::prelude::*;
 
+use crate::game_logic::coop::partner_waiting;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, States)]
 pub enum TurnState {
     PlayerInput,
@@ -54,7 +56,11 @@ pub struct TurnPlugin;
 
 impl Plugin for TurnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnExit(TurnState::PlayerAnimation), increment_turn);
+        // both co-op heroes act during the same turn
+        app.add_systems(
+            OnExit(TurnState::PlayerAnimation),
+            increment_turn.run_if(not(partner_waiting)),
+        );
     }
 }
 
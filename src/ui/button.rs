@@ -1,13 +1,85 @@
 use bevy::prelude::*;
 
 use crate::{
+    game_logic::{
+        balance::Balance,
+        combat_log::LogCategory,
+        coop::GoldSharing,
+        run_config::{GameMode, RunConfig},
+    },
     globals::{
-        PRIMARY_COLOR_GRAYED, PRIMARY_COLOR_GRAYED_BRIGHTER, REFRESH_SHOP_COST, SECONDARY_COLOR,
+        DEFEAT_SCORE_FONT_SIZE, PRIMARY_COLOR_GRAYED, PRIMARY_COLOR_GRAYED_BRIGHTER,
+        SECONDARY_COLOR, UI_FONT, UI_FONT_SIZE,
     },
+    network::protocol::OnlineMode,
+    pieces::player::consumables::Consumable,
     states::game_state::GameState,
 };
 
-use super::shop::RefreshShop;
+use super::{messages::MessageEvent, shop::RefreshShop};
+
+/// Large button used by the full screen menus
+pub fn spawn_menu_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: &str,
+    function: ButtonFunction,
+) {
+    parent
+        .spawn((
+            Node {
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            Button,
+            function,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text(label.to_string()),
+                TextFont {
+                    font_size: DEFEAT_SCORE_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Small button of the in game panels
+pub fn spawn_text_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: String,
+    function: ButtonFunction,
+) {
+    parent
+        .spawn((
+            Node {
+                justify_content: JustifyContent::Center,
+                padding: UiRect::all(Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+            Button,
+            function,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text(label),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+        });
+}
 
 #[derive(Event, Clone)]
 pub struct ButtonPressedEvent {
@@ -26,8 +98,54 @@ pub enum ButtonFunction {
     RestartGame,
     BuyUpgrade,
     RefreshShop,
+    SellUpgrade(usize),
+    SellReserved,
+    ToggleLockUpgrade(usize),
+    ReserveUpgrade(usize),
+    EquipReserved,
+    PickDraft(usize),
+    RerollDraft,
+    SkipDraft,
+    BuyConsumable(Consumable),
+    BuyRelic,
+    UseConsumable(usize),
     ShowShop,
     CloseMessage,
+    ToggleCombatLogFilter(LogCategory),
+    ExportCombatLog,
+    StartGame,
+    ShowMainMenu,
+    ShowLeaderboard,
+    ShowStats,
+    ShowProfile,
+    BuyUnlock(usize),
+    ShowCharacterSelect,
+    ShowPuzzles,
+    StartPuzzle(usize),
+    StartDaily,
+    StartTutorial,
+    StartVersus,
+    StartCoop(GoldSharing),
+    ShowLobby,
+    JoinOnline(OnlineMode),
+    ShowLeaderboardMode(GameMode),
+    WatchReplay(String),
+    SelectCharacter(usize),
+    PreviousAscension,
+    NextAscension,
+    PreviousVictory,
+    NextVictory,
+    Back,
+}
+
+/// Screen the back button of the leaderboard and stats screens returns to
+#[derive(Resource)]
+pub struct MenuReturnState(pub GameState);
+
+impl Default for MenuReturnState {
+    fn default() -> Self {
+        MenuReturnState(GameState::MainMenu)
+    }
 }
 
 pub fn button_system(
@@ -72,17 +190,64 @@ pub fn button_system(
 
 pub fn handle_button_pressed(
     mut event_reader: EventReader<ButtonPressedEvent>,
+    current_state: Res<State<GameState>>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut return_state: ResMut<MenuReturnState>,
     mut refresh_shop_event_writer: EventWriter<RefreshShop>,
+    config: Res<RunConfig>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    balance: Res<Balance>,
 ) {
     for event in event_reader.read() {
         match event.function {
             ButtonFunction::RestartGame => {
-                game_state.set(GameState::Restart);
+                if config.mode == GameMode::Daily {
+                    // the daily challenge has a single attempt
+                    message_event_writer.send(MessageEvent {
+                        message: "The daily challenge can only be played once a day.".to_string(),
+                        ..default()
+                    });
+                    game_state.set(GameState::MainMenu);
+                } else {
+                    game_state.set(GameState::Restart);
+                }
+            }
+            ButtonFunction::StartGame => {
+                game_state.set(GameState::Game);
+            }
+            ButtonFunction::ShowMainMenu => {
+                game_state.set(GameState::MainMenu);
+            }
+            ButtonFunction::ShowCharacterSelect => {
+                game_state.set(GameState::CharacterSelect);
+            }
+            ButtonFunction::ShowProfile => {
+                game_state.set(GameState::Profile);
+            }
+            ButtonFunction::ShowPuzzles => {
+                game_state.set(GameState::PuzzleSelect);
+            }
+            ButtonFunction::ShowLobby => {
+                game_state.set(GameState::Lobby);
+            }
+            ButtonFunction::ShowLeaderboard | ButtonFunction::ShowStats => {
+                // these screens return to the screen they were opened from
+                let current = *current_state.get();
+                if current != GameState::Leaderboard && current != GameState::Stats {
+                    return_state.0 = current;
+                }
+                game_state.set(if event.function == ButtonFunction::ShowStats {
+                    GameState::Stats
+                } else {
+                    GameState::Leaderboard
+                });
+            }
+            ButtonFunction::Back => {
+                game_state.set(return_state.0);
             }
             ButtonFunction::RefreshShop => {
                 refresh_shop_event_writer.send(RefreshShop {
-                    cost: REFRESH_SHOP_COST,
+                    cost: balance.refresh_shop_cost,
                 });
             }
             _ => {}
@@ -98,5 +263,6 @@ impl Plugin for ButtonPlugin {
         app.add_systems(Update, handle_button_pressed);
         app.add_event::<ButtonPressedEvent>();
         app.add_event::<ButtonHoverEvent>();
+        app.init_resource::<MenuReturnState>();
     }
 }
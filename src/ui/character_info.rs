@@ -2,21 +2,24 @@ use bevy::prelude::*;
 
 use crate::{
     globals::{
-        BLANK_SPRITE_INDEX, GOLD_UI_COLOR_DURATION, PRIMARY_COLOR, SPRITESHEET_WIDTH, UI_FONT,
-        UI_FONT_SIZE, UI_HEADER_FONT_SIZE, UI_PIECE_SPRITE_SIZE_INFO,
-        UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER,
+        BLANK_SPRITE_INDEX, GOLD_UI_COLOR_DURATION, PRIMARY_COLOR, SPRITESHEET_WIDTH,
+        SYNERGY_UPGRADE_NUMBER, UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE,
+        UI_PIECE_SPRITE_SIZE_INFO, UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER, WIP_SPRITE_INDEX,
     },
     graphics::spritesheet::SpriteSheetAtlas,
     pieces::{
         damage::Attack,
+        enemies::roster::EnemyRoster,
         health::Health,
         movement_type::MovementType,
         player::{
             experience::PlayerLevel,
             gold::Gold,
+            relics::{Relic, Relics},
             spawn::Player,
             upgrades::{
                 data::Upgrades,
+                synergies::Synergy,
                 unique_upgrades::{
                     block::Block,
                     limit::{apply_movement_type_limit, MovementTypeLimit},
@@ -42,6 +45,12 @@ struct AttackUILabel;
 #[derive(Component)]
 struct MovementTypesUIContainer;
 
+#[derive(Component)]
+struct SynergiesUIContainer;
+
+#[derive(Component)]
+struct RelicsUIContainer;
+
 #[derive(Component)]
 struct LevelUILabel;
 
@@ -147,6 +156,36 @@ pub fn setup_character_info(
                             },
                             MovementTypesUIContainer,
                         ));
+                        parent.spawn((
+                            Text("Synergies:".to_string()),
+                            TextFont {
+                                font_size: UI_FONT_SIZE,
+                                font: asset_server.load(UI_FONT),
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                ..default()
+                            },
+                            SynergiesUIContainer,
+                        ));
+                        parent.spawn((
+                            Text("Relics:".to_string()),
+                            TextFont {
+                                font_size: UI_FONT_SIZE,
+                                font: asset_server.load(UI_FONT),
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                ..default()
+                            },
+                            RelicsUIContainer,
+                        ));
                     });
             });
     });
@@ -158,6 +197,7 @@ fn update_movement_types_information(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     atlas_layout: Res<SpriteSheetAtlas>,
+    roster: Res<EnemyRoster>,
 ) {
     let container_entity = parent_query.single();
     let (upgrades, limit) = movement_types_query.single();
@@ -188,13 +228,13 @@ fn update_movement_types_information(
             if let Some((movement_type, count)) = movement_types.iter().nth(i) {
                 if *count >= UNIQUE_ABILITY_UNLOCK_UPGRADE_NUMBER {
                     (
-                        movement_type.sprite_index() + SPRITESHEET_WIDTH,
+                        movement_type.sprite_index(&roster) + SPRITESHEET_WIDTH,
                         PRIMARY_COLOR,
                         *count,
                     )
                 } else {
                     (
-                        movement_type.sprite_index(),
+                        movement_type.sprite_index(&roster),
                         Color::srgb(1.0, 1.0, 1.0),
                         *count,
                     )
@@ -247,6 +287,140 @@ fn update_movement_types_information(
     }
 }
 
+/// Lists every synergy with the upgrades owned towards it, active ones highlighted
+fn update_synergies_information(
+    upgrades_query: Query<&Upgrades, With<Player>>,
+    parent_query: Query<Entity, With<SynergiesUIContainer>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    roster: Res<EnemyRoster>,
+) {
+    let container_entity = parent_query.single();
+    let upgrades = upgrades_query.single();
+    commands.entity(container_entity).despawn_descendants();
+
+    for synergy in Synergy::ALL {
+        let active = synergy.is_active(upgrades);
+        let text_color = if active {
+            PRIMARY_COLOR
+        } else {
+            Color::srgb(0.5, 0.5, 0.5)
+        };
+        let font = TextFont {
+            font_size: UI_FONT_SIZE,
+            font: asset_server.load(UI_FONT),
+            ..default()
+        };
+        let synergy_row = commands
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text(synergy.to_string()),
+                    font.clone(),
+                    TextColor(text_color),
+                ));
+                for (movement_type, owned) in synergy.progress(upgrades) {
+                    parent.spawn((
+                        ImageNode {
+                            image: asset_server.load("custom/spritesheet.png"),
+                            texture_atlas: Some(TextureAtlas {
+                                layout: atlas_layout.handle.clone(),
+                                index: movement_type.sprite_index(&roster),
+                            }),
+                            ..default()
+                        },
+                        Node {
+                            width: Val::Px(UI_PIECE_SPRITE_SIZE_INFO),
+                            height: Val::Px(UI_PIECE_SPRITE_SIZE_INFO),
+                            ..default()
+                        },
+                    ));
+                    parent.spawn((
+                        Text(format!("{}/{}", owned, SYNERGY_UPGRADE_NUMBER)),
+                        font.clone(),
+                        TextColor(text_color),
+                    ));
+                }
+            })
+            .id();
+        commands.entity(container_entity).add_child(synergy_row);
+        if active {
+            let description = commands
+                .spawn((
+                    Text(synergy.description().to_string()),
+                    font,
+                    TextColor(text_color),
+                ))
+                .id();
+            commands.entity(container_entity).add_child(description);
+        }
+    }
+}
+
+/// Lists the relics of the hero playing, whenever they change
+fn update_relics_information(
+    relics_query: Query<&Relics, With<Player>>,
+    parent_query: Query<Entity, With<RelicsUIContainer>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    mut shown: Local<Option<Vec<Relic>>>,
+) {
+    let relics = relics_query
+        .get_single()
+        .map(|relics| relics.0.clone())
+        .unwrap_or_default();
+    if shown.as_ref() == Some(&relics) {
+        return;
+    }
+    let container_entity = parent_query.single();
+    commands.entity(container_entity).despawn_descendants();
+    commands.entity(container_entity).with_children(|parent| {
+        for relic in relics.iter() {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // relics have no art yet, like the stat upgrades
+                    parent.spawn((
+                        ImageNode {
+                            image: asset_server.load("custom/spritesheet.png"),
+                            texture_atlas: Some(TextureAtlas {
+                                layout: atlas_layout.handle.clone(),
+                                index: WIP_SPRITE_INDEX,
+                            }),
+                            ..default()
+                        },
+                        Node {
+                            width: Val::Px(UI_PIECE_SPRITE_SIZE_INFO),
+                            height: Val::Px(UI_PIECE_SPRITE_SIZE_INFO),
+                            ..default()
+                        },
+                    ));
+                    parent.spawn((
+                        Text(relic.to_string()),
+                        TextFont {
+                            font_size: UI_FONT_SIZE,
+                            font: asset_server.load(UI_FONT),
+                            ..default()
+                        },
+                    ));
+                });
+        }
+    });
+    *shown = Some(relics);
+}
+
 fn update_health_information(
     health: Query<(&Health, &Block), With<Player>>,
     mut query: Query<&mut Text, With<HealthUILabel>>,
@@ -327,7 +501,9 @@ impl Plugin for CharacterInfoPlugin {
                 update_movement_types_information
                     .run_if(on_event::<ApplyUpgrades>)
                     .after(apply_movement_type_limit),
+                update_synergies_information.run_if(on_event::<ApplyUpgrades>),
                 update_attack_information.run_if(on_event::<ApplyUpgrades>),
+                update_relics_information,
                 // update_level_information,
                 update_gold_information,
             )
@@ -0,0 +1,413 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::{
+        ascension::ASCENSION_LADDER,
+        balance::Balance,
+        profile::Profile,
+        run_config::{GameMode, RunConfig},
+        victory::VictoryCondition,
+    },
+    globals::{
+        ASCENSION_UNLOCK_TURN, DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, SECONDARY_COLOR, UI_FONT,
+        UI_FONT_SIZE,
+    },
+    pieces::player::character::{Character, CharacterRoster},
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction, ButtonPressedEvent},
+    messages::MessageEvent,
+    profile::{spawn_small_button, spawn_text},
+    RootUINode,
+};
+
+#[derive(Component)]
+struct CharacterListNode;
+
+#[derive(Component)]
+struct AscensionNode;
+
+#[derive(Component)]
+struct VictoryNode;
+
+fn show_character_select(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    characters: Res<CharacterRoster>,
+) {
+    debug!("Showing character select");
+    let root_node = root_node.single();
+
+    let select_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("CharacterSelectUI"),
+            StateScoped(GameState::CharacterSelect),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text("Choose your character".to_string()),
+                TextFont {
+                    font_size: DEFEAT_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(16.0),
+                        row_gap: Val::Px(16.0),
+                        ..default()
+                    },
+                    CharacterListNode,
+                ))
+                .with_children(|parent| {
+                    spawn_characters(parent, &asset_server, &profile, &characters, &balance)
+                });
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    },
+                    AscensionNode,
+                ))
+                .with_children(|parent| {
+                    spawn_ascension(parent, &asset_server, &profile, &config);
+                });
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    VictoryNode,
+                ))
+                .with_children(|parent| spawn_victory(parent, &asset_server, &config));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_menu_button(parent, &asset_server, "Start", ButtonFunction::StartGame);
+                    spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::ShowMainMenu);
+                });
+        })
+        .id();
+
+    commands.entity(root_node).add_child(select_node);
+}
+
+fn spawn_characters(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    profile: &Profile,
+    characters: &CharacterRoster,
+    balance: &Balance,
+) {
+    let selected = profile.character(characters);
+    for (index, character) in characters.characters.iter().enumerate() {
+        let unlocked = profile.is_character_unlocked(character);
+        let color = if std::ptr::eq(character, selected) {
+            PRIMARY_COLOR
+        } else if unlocked {
+            Color::WHITE
+        } else {
+            SECONDARY_COLOR
+        };
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                width: Val::Px(220.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                let label = if unlocked {
+                    character.name.clone()
+                } else {
+                    format!("{} (locked)", character.name)
+                };
+                spawn_small_button(
+                    parent,
+                    asset_server,
+                    label,
+                    color,
+                    ButtonFunction::SelectCharacter(index),
+                );
+                for line in character_lines(character, balance) {
+                    spawn_text(parent, asset_server, line, UI_FONT_SIZE, Color::WHITE);
+                }
+            });
+    }
+}
+
+/// Level selector and the modifiers of the selected level
+fn spawn_ascension(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    profile: &Profile,
+    config: &RunConfig,
+) {
+    let ascension = config.ascension();
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            let color = |enabled: bool| {
+                if enabled {
+                    Color::WHITE
+                } else {
+                    SECONDARY_COLOR
+                }
+            };
+            spawn_small_button(
+                parent,
+                asset_server,
+                "<".to_string(),
+                color(ascension.0 > 0),
+                ButtonFunction::PreviousAscension,
+            );
+            spawn_text(
+                parent,
+                asset_server,
+                ascension.to_string(),
+                UI_FONT_SIZE,
+                PRIMARY_COLOR,
+            );
+            spawn_small_button(
+                parent,
+                asset_server,
+                ">".to_string(),
+                color(ascension.0 < profile.max_ascension),
+                ButtonFunction::NextAscension,
+            );
+        });
+    for (level, modifier) in ASCENSION_LADDER.iter().enumerate() {
+        let color = if ascension.has(*modifier) {
+            Color::WHITE
+        } else {
+            SECONDARY_COLOR
+        };
+        spawn_text(
+            parent,
+            asset_server,
+            format!("{}: {}", level + 1, modifier),
+            UI_FONT_SIZE,
+            color,
+        );
+    }
+}
+
+/// Selector of the victory condition of the run
+fn spawn_victory(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, config: &RunConfig) {
+    spawn_small_button(
+        parent,
+        asset_server,
+        "<".to_string(),
+        Color::WHITE,
+        ButtonFunction::PreviousVictory,
+    );
+    spawn_text(
+        parent,
+        asset_server,
+        format!("Victory: {}", config.victory),
+        UI_FONT_SIZE,
+        PRIMARY_COLOR,
+    );
+    spawn_small_button(
+        parent,
+        asset_server,
+        ">".to_string(),
+        Color::WHITE,
+        ButtonFunction::NextVictory,
+    );
+}
+
+fn character_lines(character: &Character, balance: &Balance) -> Vec<String> {
+    // movement types in starting order, with their upgrade count
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for movement_type in character.movement_types.iter() {
+        let name = format!("{:?}", movement_type);
+        match counts.iter_mut().find(|(other, _)| *other == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    let movement_types = counts
+        .iter()
+        .map(|(name, count)| format!("{} x{}", name, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    vec![
+        character.description.clone(),
+        format!(
+            "Health: {}  Attack: {}",
+            character.health(balance),
+            character.attack
+        ),
+        format!("Moves: {}", movement_types),
+        format!("Movement types: up to {}", character.movement_type_limit),
+        format!("Gold: {}", character.starting_gold()),
+        format!("Passive: {}", character.passive.description()),
+    ]
+}
+
+fn handle_character_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut profile: ResMut<Profile>,
+    mut config: ResMut<RunConfig>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    characters: Res<CharacterRoster>,
+) {
+    for event in event_reader.read() {
+        match event.function {
+            ButtonFunction::SelectCharacter(index) => {
+                let Some(character) = characters.characters.get(index) else {
+                    continue;
+                };
+                if profile.is_character_unlocked(character) {
+                    profile.selected_character = character.name.clone();
+                    profile.save();
+                } else {
+                    message_event_writer.send(MessageEvent {
+                        message: format!("{} is locked, unlock it in the profile.", character.name),
+                        ..default()
+                    });
+                }
+            }
+            ButtonFunction::PreviousAscension => {
+                config.difficulty = config.difficulty.saturating_sub(1);
+            }
+            ButtonFunction::NextAscension => {
+                if config.difficulty < profile.max_ascension {
+                    config.difficulty += 1;
+                } else {
+                    message_event_writer.send(MessageEvent {
+                        message: format!(
+                            "Reach turn {} on ascension {} to unlock the next level.",
+                            ASCENSION_UNLOCK_TURN, config.difficulty
+                        ),
+                        ..default()
+                    });
+                }
+            }
+            ButtonFunction::PreviousVictory | ButtonFunction::NextVictory => {
+                let options = VictoryCondition::OPTIONS;
+                let index = options
+                    .iter()
+                    .position(|victory| *victory == config.victory)
+                    .unwrap_or_default();
+                let index = if event.function == ButtonFunction::NextVictory {
+                    (index + 1) % options.len()
+                } else {
+                    (index + options.len() - 1) % options.len()
+                };
+                config.victory = options[index];
+                config.mode = config.victory.mode();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn refresh_character_select(
+    mut commands: Commands,
+    list_node: Query<Entity, With<CharacterListNode>>,
+    ascension_node: Query<Entity, With<AscensionNode>>,
+    victory_node: Query<Entity, With<VictoryNode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+    characters: Res<CharacterRoster>,
+) {
+    for list_node in list_node.iter() {
+        commands.entity(list_node).despawn_descendants();
+        commands.entity(list_node).with_children(|parent| {
+            spawn_characters(parent, &asset_server, &profile, &characters, &balance)
+        });
+    }
+    for ascension_node in ascension_node.iter() {
+        commands.entity(ascension_node).despawn_descendants();
+        commands.entity(ascension_node).with_children(|parent| {
+            spawn_ascension(parent, &asset_server, &profile, &config);
+        });
+    }
+    for victory_node in victory_node.iter() {
+        commands.entity(victory_node).despawn_descendants();
+        commands
+            .entity(victory_node)
+            .with_children(|parent| spawn_victory(parent, &asset_server, &config));
+    }
+}
+
+/// Runs started from this screen are standard or endless runs, whatever was played before
+fn reset_run_config(mut config: ResMut<RunConfig>) {
+    // the daily challenge, scripted scenarios and versus are always endless,
+    // go back to the default condition after them
+    let victory = if config.mode == GameMode::Daily || config.is_scripted() || config.versus {
+        VictoryCondition::default()
+    } else {
+        config.victory
+    };
+    *config = RunConfig {
+        mode: victory.mode(),
+        difficulty: config.difficulty,
+        victory,
+        ..default()
+    };
+}
+
+pub struct CharacterSelectPlugin;
+
+impl Plugin for CharacterSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::CharacterSelect),
+            (reset_run_config, show_character_select).chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_character_buttons.run_if(on_event::<ButtonPressedEvent>),
+                refresh_character_select
+                    .run_if(resource_changed::<Profile>.or(resource_changed::<RunConfig>)),
+            )
+                .chain()
+                .run_if(in_state(GameState::CharacterSelect)),
+        );
+    }
+}
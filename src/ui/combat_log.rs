@@ -0,0 +1,256 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::{
+    game_logic::combat_log::{export_combat_log, CombatLog, LogCategory},
+    globals::{
+        COMBAT_LOG_DISPLAY_LIMIT, COMBAT_LOG_EXPORT_MESSAGE_DURATION, COMBAT_LOG_FONT_SIZE,
+        COMBAT_LOG_HEIGHT, COMBAT_LOG_SCROLL_SPEED, PRIMARY_COLOR, PRIMARY_COLOR_GRAYED, UI_FONT,
+        UI_FONT_SIZE, UI_HEADER_FONT_SIZE,
+    },
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{ButtonFunction, ButtonPressedEvent},
+    character_info::setup_character_info,
+    messages::MessageEvent,
+    setup_ui, LeftUINode,
+};
+
+#[derive(Component)]
+struct CombatLogEntriesNode;
+
+#[derive(Component)]
+struct CombatLogFilterLabel(LogCategory);
+
+fn setup_combat_log(
+    mut commands: Commands,
+    query: Query<Entity, With<LeftUINode>>,
+    asset_server: Res<AssetServer>,
+) {
+    debug!("Setting up combat log");
+    let root_node = query.single();
+    commands.entity(root_node).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(2.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text("Combat Log".to_string()),
+                    TextFont {
+                        font_size: UI_HEADER_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                ));
+                // filters
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        column_gap: Val::Px(4.0),
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for category in LogCategory::ALL {
+                            spawn_log_button(
+                                parent,
+                                &asset_server,
+                                category.to_string(),
+                                ButtonFunction::ToggleCombatLogFilter(category),
+                            )
+                            .insert(CombatLogFilterLabel(category));
+                        }
+                        spawn_log_button(
+                            parent,
+                            &asset_server,
+                            "Export".to_string(),
+                            ButtonFunction::ExportCombatLog,
+                        );
+                    });
+                parent.spawn((
+                    Node {
+                        height: Val::Px(COMBAT_LOG_HEIGHT),
+                        flex_direction: FlexDirection::Column,
+                        overflow: Overflow::scroll_y(),
+                        ..default()
+                    },
+                    ScrollPosition::default(),
+                    Interaction::default(),
+                    CombatLogEntriesNode,
+                ));
+            });
+    });
+}
+
+fn spawn_log_button<'a>(
+    parent: &'a mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: String,
+    function: ButtonFunction,
+) -> EntityCommands<'a> {
+    let mut button = parent.spawn((
+        Node {
+            padding: UiRect::horizontal(Val::Px(4.0)),
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BorderRadius::all(Val::Px(2.0)),
+        BackgroundColor(PRIMARY_COLOR_GRAYED),
+        Button,
+        function,
+    ));
+    button.with_children(|parent| {
+        parent.spawn((
+            Text(label),
+            TextFont {
+                font_size: COMBAT_LOG_FONT_SIZE,
+                font: asset_server.load(UI_FONT),
+                ..default()
+            },
+        ));
+    });
+    button
+}
+
+fn handle_combat_log_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut log: ResMut<CombatLog>,
+    mut message_writer: EventWriter<MessageEvent>,
+) {
+    for event in event_reader.read() {
+        match event.function {
+            ButtonFunction::ToggleCombatLogFilter(category) => {
+                log.toggle_category(category);
+            }
+            ButtonFunction::ExportCombatLog => {
+                let message = match export_combat_log(&log) {
+                    Ok(path) => format!("Combat log exported to {}", path),
+                    Err(error) => format!("Could not export the combat log: {}", error),
+                };
+                message_writer.send(MessageEvent {
+                    message,
+                    timer: Some(Timer::from_seconds(
+                        COMBAT_LOG_EXPORT_MESSAGE_DURATION,
+                        TimerMode::Once,
+                    )),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Grays out the labels of the hidden categories
+fn update_filter_labels(
+    log: Res<CombatLog>,
+    filters: Query<(&CombatLogFilterLabel, &Children)>,
+    mut texts: Query<&mut TextColor>,
+) {
+    for (filter, children) in filters.iter() {
+        let color = if log.is_visible(filter.0) {
+            Color::WHITE
+        } else {
+            Color::srgb(0.4, 0.4, 0.4)
+        };
+        for child in children.iter() {
+            if let Ok(mut text_color) = texts.get_mut(*child) {
+                text_color.0 = color;
+            }
+        }
+    }
+}
+
+/// Rebuilds the displayed entries and scrolls to the latest one
+fn update_combat_log_entries(
+    mut commands: Commands,
+    log: Res<CombatLog>,
+    mut entries_node: Query<(Entity, &mut ScrollPosition), With<CombatLogEntriesNode>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((entries_node, mut scroll_position)) = entries_node.get_single_mut() else {
+        return;
+    };
+    commands.entity(entries_node).despawn_descendants();
+    let visible: Vec<_> = log.visible_entries().collect();
+    let skipped = visible.len().saturating_sub(COMBAT_LOG_DISPLAY_LIMIT);
+    let mut current_turn = None;
+    commands.entity(entries_node).with_children(|parent| {
+        for entry in visible.into_iter().skip(skipped) {
+            if current_turn != Some(entry.turn) {
+                current_turn = Some(entry.turn);
+                parent.spawn((
+                    Text(format!("Turn {}", entry.turn)),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    TextColor(PRIMARY_COLOR),
+                ));
+            }
+            parent.spawn((
+                Text(format!("[{}] {}", entry.category, entry.message)),
+                TextFont {
+                    font_size: COMBAT_LOG_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+        }
+    });
+    // clamped to the content size by the layout
+    scroll_position.offset_y = f32::MAX;
+}
+
+fn scroll_combat_log(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut entries_node: Query<(&Interaction, &mut ScrollPosition), With<CombatLogEntriesNode>>,
+) {
+    for event in mouse_wheel_events.read() {
+        let Ok((interaction, mut scroll_position)) = entries_node.get_single_mut() else {
+            return;
+        };
+        if *interaction == Interaction::None {
+            continue;
+        }
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * COMBAT_LOG_SCROLL_SPEED,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        scroll_position.offset_y = (scroll_position.offset_y - dy).max(0.0);
+    }
+}
+
+pub struct CombatLogUIPlugin;
+
+impl Plugin for CombatLogUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            setup_combat_log.after(setup_ui).after(setup_character_info),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_combat_log_buttons.run_if(on_event::<ButtonPressedEvent>),
+                (update_filter_labels, update_combat_log_entries)
+                    .run_if(resource_changed::<CombatLog>),
+                scroll_combat_log,
+            )
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
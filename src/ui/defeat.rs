@@ -1,29 +1,56 @@
 use bevy::prelude::*;
 
 use crate::{
-    game_logic::score::GameScore,
-    globals::{DEFEAT_HEADER_FONT_SIZE, DEFEAT_SCORE_FONT_SIZE, UI_FONT},
+    game_logic::{
+        leaderboard::{record_leaderboard_entry, Leaderboard},
+        profile::{earn_meta_currency, Profile},
+        puzzle::is_puzzle,
+        run_config::RunConfig,
+        run_stats::{RunHistory, RunSummary},
+        versus::is_versus,
+    },
+    globals::{
+        DEFEAT_HEADER_FONT_SIZE, DEFEAT_SCORE_FONT_SIZE, PRIMARY_COLOR, UI_FONT, UI_FONT_SIZE,
+    },
     states::game_state::GameState,
 };
 
-use super::{button::ButtonFunction, RootUINode};
+use super::{
+    button::{spawn_menu_button, ButtonFunction},
+    RootUINode,
+};
 
 pub struct DefeatPlugin;
 
 impl Plugin for DefeatPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Defeat), show_defeat_screen);
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(
+                OnEnter(state),
+                show_defeat_screen
+                    .after(record_leaderboard_entry)
+                    .after(earn_meta_currency)
+                    .run_if(not(is_puzzle).and(not(is_versus))),
+            );
+        }
     }
 }
 
+/// End of run screen, shown on defeat and on victory
 fn show_defeat_screen(
     mut commands: Commands,
+    state: Res<State<GameState>>,
+    config: Res<RunConfig>,
     root_node: Query<Entity, With<RootUINode>>,
     asset_server: Res<AssetServer>,
-    score: Res<GameScore>,
+    history: Res<RunHistory>,
+    leaderboard: Res<Leaderboard>,
+    profile: Res<Profile>,
 ) {
-    debug!("Showing defeat screen");
+    let state = *state.get();
+    debug!("Showing {} screen", state);
     let root_node = root_node.single();
+    let summary = history.runs.last().cloned().unwrap_or_default();
 
     let defeat_node = commands
         .spawn((
@@ -36,52 +63,140 @@ fn show_defeat_screen(
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
                 ..default()
             },
             BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
             Name::new("DefeatUI"),
-            StateScoped(GameState::Defeat),
+            StateScoped(state),
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text("Defeat".to_string()),
+                Text(state.to_string()),
                 TextFont {
                     font_size: DEFEAT_HEADER_FONT_SIZE,
                     font: asset_server.load(UI_FONT),
                     ..default()
                 },
             ));
+            if state == GameState::Victory {
+                parent.spawn((
+                    Text(config.victory.to_string()),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    TextColor(PRIMARY_COLOR),
+                ));
+            }
             parent.spawn((
-                Text(format!("Score: {}", score.0)),
+                Text(format!("Score: {}", summary.score)),
                 TextFont {
                     font_size: DEFEAT_SCORE_FONT_SIZE,
                     font: asset_server.load(UI_FONT),
                     ..default()
                 },
             ));
-            parent
-                .spawn((
-                    Node {
-                        padding: UiRect::all(Val::Px(10.0)),
-                        border: UiRect::all(Val::Px(1.0)),
+            if let Some(rank) = leaderboard.last_rank {
+                parent.spawn((
+                    Text(format!("Leaderboard rank #{}", rank)),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
                         ..default()
                     },
-                    BorderRadius::all(Val::Px(2.0)),
-                    Button,
-                    ButtonFunction::RestartGame,
-                ))
+                    TextColor(PRIMARY_COLOR),
+                ));
+            }
+            parent.spawn((
+                Text(format!(
+                    "+{} meta-currency ({} total)",
+                    profile.last_earned, profile.meta_currency
+                )),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(32.0),
+                    ..default()
+                })
                 .with_children(|parent| {
-                    parent.spawn((
-                        Text("Restart".to_string()),
-                        TextFont {
-                            font_size: DEFEAT_SCORE_FONT_SIZE,
-                            font: asset_server.load(UI_FONT),
-                            ..default()
-                        },
-                    ));
+                    for column in summary_columns(&summary) {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for line in column {
+                                    parent.spawn((
+                                        Text(line),
+                                        TextFont {
+                                            font_size: UI_FONT_SIZE,
+                                            font: asset_server.load(UI_FONT),
+                                            ..default()
+                                        },
+                                    ));
+                                }
+                            });
+                    }
+                });
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_menu_button(
+                        parent,
+                        &asset_server,
+                        "Restart",
+                        ButtonFunction::RestartGame,
+                    );
+                    spawn_menu_button(parent, &asset_server, "Menu", ButtonFunction::ShowMainMenu);
+                    spawn_menu_button(
+                        parent,
+                        &asset_server,
+                        "Leaderboard",
+                        ButtonFunction::ShowLeaderboard,
+                    );
+                    spawn_menu_button(parent, &asset_server, "Stats", ButtonFunction::ShowStats);
                 });
         })
         .id();
 
     commands.entity(root_node).add_child(defeat_node);
 }
+
+/// Run summary lines, split in general stats, kills and build
+fn summary_columns(summary: &RunSummary) -> [Vec<String>; 3] {
+    let mut general = vec![
+        format!("Turns survived: {}", summary.turns),
+        format!("Damage dealt: {}", summary.damage_dealt),
+        format!("Damage taken: {}", summary.damage_taken),
+        format!("Biggest hit: {}", summary.biggest_hit),
+        format!("Gold earned: {}", summary.gold_earned),
+        format!("Gold spent: {}", summary.gold_spent),
+    ];
+    for (side_effect, count) in summary.side_effects.iter() {
+        general.push(format!("{}: {}", side_effect, count));
+    }
+
+    let mut kills = vec![format!("Kills: {}", summary.total_kills())];
+    for (name, count) in summary.kills.iter() {
+        kills.push(format!("  {}: {}", name, count));
+    }
+
+    let mut build = vec!["Build:".to_string()];
+    for (movement_type, count) in summary.build.iter() {
+        build.push(format!("  {} x{}", movement_type, count));
+    }
+    [general, kills, build]
+}
@@ -0,0 +1,450 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game_logic::{
+        balance::Balance,
+        combat_log::{CombatLogEvent, LogCategory},
+        profile::Profile,
+        run_config::RunConfig,
+    },
+    globals::{UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE, UI_PIECE_SPRITE_SIZE_SHOP},
+    graphics::spritesheet::SpriteSheetAtlas,
+    network::is_local_turn,
+    pieces::player::{
+        experience::PlayerLevelUpEvent,
+        gold::Gold,
+        spawn::Player,
+        upgrades::{
+            data::{Upgrade, UpgradeCatalog, Upgrades},
+            unique_upgrades::limit::MovementTypeLimit,
+        },
+    },
+    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+    utils::rng::{sample_weighted, GameRng, Weighted},
+};
+
+use super::{
+    button::{spawn_text_button, ButtonFunction, ButtonPressedEvent},
+    messages::MessageEvent,
+    shop::{movement_limit_message, ApplyUpgrades, ShopState},
+    RootUINode,
+};
+
+/// Upgrades offered for reaching a level
+#[derive(Clone, Debug)]
+pub struct LevelUpDraft {
+    pub level: usize,
+    pub options: Vec<Upgrade>,
+}
+
+/// Drafts the player has yet to pick from, the first one is shown
+#[derive(Resource, Default)]
+pub struct LevelUpDrafts(pub VecDeque<LevelUpDraft>);
+
+/// Answer to the shown draft, from a button or the other online player
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum DraftChoice {
+    Pick(usize),
+    /// Draw other upgrades for gold
+    Reroll,
+    /// Take gold instead of an upgrade
+    Skip,
+}
+
+#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum DraftState {
+    Open,
+    #[default]
+    Closed,
+}
+
+/// Draws different upgrades from the pool, weighted by rarity for the level
+fn draw_draft(
+    rng: &mut impl Rng,
+    pool: Vec<Upgrade>,
+    level: usize,
+    balance: &Balance,
+) -> Vec<Upgrade> {
+    let mut candidates: Vec<Upgrade> = pool
+        .into_iter()
+        .map(|upgrade| upgrade.with_rarity_odds(level.saturating_sub(1), balance))
+        .filter(|upgrade| upgrade.weight() > 0.0)
+        .collect();
+    let mut options = Vec::new();
+    while options.len() < balance.draft_size && !candidates.is_empty() {
+        let Some(drawn) = sample_weighted(rng, 1, &candidates).and_then(|mut drawn| drawn.pop())
+        else {
+            break;
+        };
+        candidates.retain(|candidate| candidate.display_name != drawn.display_name);
+        options.push(drawn);
+    }
+    options
+}
+
+/// Movement and stat upgrades of the run the player is allowed to take
+fn draft_pool(
+    profile: &Profile,
+    catalog: &UpgradeCatalog,
+    upgrades: &Upgrades,
+    limit: usize,
+) -> Vec<Upgrade> {
+    profile
+        .movement_pool(catalog)
+        .into_iter()
+        .filter(|upgrade| upgrades.within_movement_limit(upgrade, limit))
+        .chain(profile.stats_pool(catalog))
+        .collect()
+}
+
+fn queue_level_up_drafts(
+    mut level_up_events: EventReader<PlayerLevelUpEvent>,
+    mut drafts: ResMut<LevelUpDrafts>,
+    mut rng: ResMut<GameRng>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    catalog: Res<UpgradeCatalog>,
+    balance: Res<Balance>,
+    player: Query<(&Upgrades, &MovementTypeLimit), With<Player>>,
+) {
+    // read every level up, even the ones without a hero to draft for
+    let levels: Vec<usize> = level_up_events.read().map(|event| event.level).collect();
+    let Ok((upgrades, limit)) = player.get_single() else {
+        return;
+    };
+    let profile = config.run_profile(&profile);
+    for level in levels {
+        let pool = draft_pool(profile, &catalog, upgrades, limit.limit);
+        drafts.0.push_back(LevelUpDraft {
+            level,
+            options: draw_draft(&mut rng.0, pool, level, &balance),
+        });
+    }
+}
+
+/// Pauses the game on the player input until every draft is answered
+fn open_draft(
+    drafts: Res<LevelUpDrafts>,
+    mut next_state: ResMut<NextState<DraftState>>,
+    mut pause_state: ResMut<NextState<GamePauseState>>,
+) {
+    if !drafts.0.is_empty() {
+        debug!("Draft opened");
+        next_state.set(DraftState::Open);
+        pause_state.set(GamePauseState::Paused);
+    }
+}
+
+fn press_draft_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut choice_writer: EventWriter<DraftChoice>,
+) {
+    for event in event_reader.read() {
+        match event.function {
+            ButtonFunction::PickDraft(index) => {
+                choice_writer.send(DraftChoice::Pick(index));
+            }
+            ButtonFunction::RerollDraft => {
+                choice_writer.send(DraftChoice::Reroll);
+            }
+            ButtonFunction::SkipDraft => {
+                choice_writer.send(DraftChoice::Skip);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn apply_draft_choice(
+    mut choice_reader: EventReader<DraftChoice>,
+    mut drafts: ResMut<LevelUpDrafts>,
+    mut gold: ResMut<Gold>,
+    mut rng: ResMut<GameRng>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    catalog: Res<UpgradeCatalog>,
+    balance: Res<Balance>,
+    mut player: Query<(&mut Upgrades, &MovementTypeLimit), With<Player>>,
+    mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    mut log_writer: EventWriter<CombatLogEvent>,
+    draft_state: Res<State<DraftState>>,
+    mut next_state: ResMut<NextState<DraftState>>,
+    mut pause_state: ResMut<NextState<GamePauseState>>,
+) {
+    let Ok((mut upgrades, limit)) = player.get_single_mut() else {
+        return;
+    };
+    for choice in choice_reader.read() {
+        let Some(draft) = drafts.0.front_mut() else {
+            continue;
+        };
+        match choice {
+            DraftChoice::Pick(index) => {
+                let Some(upgrade) = draft.options.get(*index).cloned() else {
+                    continue;
+                };
+                if !upgrades.within_movement_limit(&upgrade, limit.limit) {
+                    message_event_writer.send(movement_limit_message());
+                    continue;
+                }
+                log_writer.send(CombatLogEvent::new(
+                    LogCategory::Reward,
+                    format!("Drafted {}", upgrade.display_name),
+                ));
+                upgrades.0.push(upgrade.clone());
+                apply_upgrades_event_writer.send(ApplyUpgrades(upgrade));
+                drafts.0.pop_front();
+            }
+            DraftChoice::Reroll => {
+                if gold.amount < balance.draft_reroll_cost {
+                    message_event_writer.send(MessageEvent {
+                        message: "Not enough gold to reroll the upgrades.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                gold.amount -= balance.draft_reroll_cost;
+                let pool = draft_pool(
+                    config.run_profile(&profile),
+                    &catalog,
+                    &upgrades,
+                    limit.limit,
+                );
+                draft.options = draw_draft(&mut rng.0, pool, draft.level, &balance);
+            }
+            DraftChoice::Skip => {
+                gold.amount += balance.draft_skip_gold;
+                log_writer.send(CombatLogEvent::new(
+                    LogCategory::Reward,
+                    format!("Skipped the draft for {} gold", balance.draft_skip_gold),
+                ));
+                drafts.0.pop_front();
+            }
+        }
+    }
+    if drafts.0.is_empty() && *draft_state.get() == DraftState::Open {
+        debug!("Draft closed");
+        next_state.set(DraftState::Closed);
+        pause_state.set(GamePauseState::Playing);
+    }
+}
+
+#[derive(Component)]
+struct DraftNode;
+
+/// Shows the first draft, again whenever it is rerolled or answered
+fn display_draft(
+    drafts: Res<LevelUpDrafts>,
+    draft_node: Query<Entity, With<DraftNode>>,
+    root_node: Query<Entity, With<RootUINode>>,
+    player_upgrades: Query<&Upgrades, With<Player>>,
+    asset_server: Res<AssetServer>,
+    atlas_layout: Res<SpriteSheetAtlas>,
+    balance: Res<Balance>,
+    mut commands: Commands,
+) {
+    if !drafts.is_changed() && !draft_node.is_empty() {
+        return;
+    }
+    for entity in draft_node.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(draft) = drafts.0.front() else {
+        return;
+    };
+    let root_node = root_node.get_single().expect("Root node not found");
+    let font = TextFont {
+        font: asset_server.load(UI_FONT),
+        font_size: UI_FONT_SIZE,
+        ..default()
+    };
+    let draft_ui = commands
+        .spawn((
+            Node {
+                width: Val::Vw(50.0),
+                top: Val::Px(0.0),
+                left: Val::Percent(25.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("DraftUI"),
+            StateScoped(DraftState::Open),
+            DraftNode,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text(format!("Level {}! Pick an upgrade", draft.level)),
+                TextFont {
+                    font_size: UI_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (index, upgrade) in draft.options.iter().enumerate() {
+                        let owned = player_upgrades
+                            .get_single()
+                            .map_or(0, |upgrades| upgrades.owned_movement_upgrades(upgrade));
+                        parent
+                            .spawn((
+                                Node {
+                                    flex_direction: FlexDirection::Column,
+                                    width: Val::Percent(100.0),
+                                    align_items: AlignItems::Center,
+                                    row_gap: Val::Px(4.0),
+                                    padding: UiRect::all(Val::Px(4.0)),
+                                    border: UiRect::all(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BorderRadius::all(Val::Px(2.0)),
+                                BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+                                Button,
+                                ButtonFunction::PickDraft(index),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((Text(upgrade.display_name.clone()), font.clone()));
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(UI_PIECE_SPRITE_SIZE_SHOP),
+                                        height: Val::Px(UI_PIECE_SPRITE_SIZE_SHOP),
+                                        ..default()
+                                    },
+                                    ImageNode::from_atlas_image(
+                                        asset_server.load("custom/spritesheet.png"),
+                                        TextureAtlas {
+                                            layout: atlas_layout.handle.clone(),
+                                            index: upgrade.icon_index,
+                                        },
+                                    ),
+                                ));
+                                parent.spawn((Text::default(), font.clone())).with_children(
+                                    |parent| {
+                                        for (text, color) in upgrade.description_with_tiers(owned) {
+                                            parent.spawn((text, color, font.clone()));
+                                        }
+                                    },
+                                );
+                            });
+                    }
+                });
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_text_button(
+                        parent,
+                        &asset_server,
+                        format!("Reroll (${})", balance.draft_reroll_cost),
+                        ButtonFunction::RerollDraft,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &asset_server,
+                        format!("Skip (+{} gold)", balance.draft_skip_gold),
+                        ButtonFunction::SkipDraft,
+                    );
+                });
+        })
+        .id();
+    commands.entity(root_node).add_child(draft_ui);
+}
+
+fn reset_drafts(mut drafts: ResMut<LevelUpDrafts>, mut next_state: ResMut<NextState<DraftState>>) {
+    drafts.0.clear();
+    next_state.set(DraftState::Closed);
+}
+
+pub struct DraftPlugin;
+
+impl Plugin for DraftPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_state(DraftState::Closed)
+            .enable_state_scoped_entities::<DraftState>()
+            .init_resource::<LevelUpDrafts>()
+            .add_event::<DraftChoice>()
+            .add_systems(OnEnter(GameState::Game), reset_drafts)
+            .add_systems(
+                Update,
+                open_draft
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(in_state(DraftState::Closed))
+                    .run_if(in_state(ShopState::Closed))
+                    .run_if(is_local_turn),
+            )
+            .add_systems(
+                Update,
+                (
+                    press_draft_buttons.run_if(on_event::<ButtonPressedEvent>),
+                    display_draft,
+                )
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(DraftState::Open)),
+            )
+            // the other online player's choices are applied while the draft is closed
+            .add_systems(
+                Update,
+                (queue_level_up_drafts, apply_draft_choice)
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_draw_draft() {
+        let profile = Profile::default();
+        let balance = Balance::default();
+        let pool = draft_pool(
+            &profile,
+            &UpgradeCatalog::default(),
+            &Upgrades::default(),
+            2,
+        );
+        // locked movement upgrades are not drafted either
+        assert!(pool.iter().all(|upgrade| !upgrade.locked));
+        assert!(UpgradeCatalog::default()
+            .movement
+            .iter()
+            .any(|upgrade| upgrade.locked));
+        let mut rng = StdRng::seed_from_u64(7);
+        let options = draw_draft(&mut rng, pool.clone(), 1, &balance);
+        assert_eq!(options.len(), balance.draft_size);
+        for (index, option) in options.iter().enumerate() {
+            assert!(options[index + 1..]
+                .iter()
+                .all(|other| other.display_name != option.display_name));
+        }
+        // never more options than upgrades that can be drawn
+        let single = pool
+            .into_iter()
+            .filter(|upgrade| upgrade.weight() > 0.0)
+            .take(1)
+            .collect();
+        assert_eq!(draw_draft(&mut rng, single, 1, &balance).len(), 1);
+    }
+}
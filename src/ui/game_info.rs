@@ -1,8 +1,17 @@
 use bevy::prelude::*;
 
 use crate::{
-    game_logic::score::GameScore,
+    game_logic::{
+        coop::Coop,
+        puzzle::PuzzleProgress,
+        run_config::RunConfig,
+        score::GameScore,
+        tutorial::{TutorialProgress, TUTORIAL},
+        versus::Versus,
+        victory::VictoryProgress,
+    },
     globals::{UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE},
+    network::NetSession,
     states::turn_state::TurnInfo,
 };
 
@@ -17,6 +26,12 @@ struct TurnUILabel;
 #[derive(Component)]
 struct ScoreUILabel;
 
+#[derive(Component)]
+struct AscensionUILabel;
+
+#[derive(Component)]
+struct GoalUILabel;
+
 pub fn setup_game_info(
     mut commands: Commands,
     query: Query<Entity, With<LeftUINode>>,
@@ -62,6 +77,24 @@ pub fn setup_game_info(
                     },
                     ScoreUILabel,
                 ));
+                parent.spawn((
+                    Text("AscensionPlaceholder".to_string()),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    AscensionUILabel,
+                ));
+                parent.spawn((
+                    Text("GoalPlaceholder".to_string()),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    GoalUILabel,
+                ));
             });
     });
 }
@@ -76,17 +109,82 @@ fn update_turn_information(
 
 fn update_score_information(
     score: Res<GameScore>,
+    config: Res<RunConfig>,
     mut query: Query<&mut Text, With<ScoreUILabel>>,
 ) {
     let mut text = query.get_single_mut().unwrap();
-    text.0 = format!("Score: {}", score.0);
+    text.0 = format!("Score: {}", config.ascension().score(score.0));
+}
+
+fn update_ascension_information(
+    config: Res<RunConfig>,
+    mut query: Query<&mut Text, With<AscensionUILabel>>,
+) {
+    let mut text = query.get_single_mut().unwrap();
+    text.0 = config.ascension().to_string();
+}
+
+fn update_goal_information(
+    config: Res<RunConfig>,
+    turn_info: Res<TurnInfo>,
+    score: Res<GameScore>,
+    progress: Res<VictoryProgress>,
+    puzzle_progress: Res<PuzzleProgress>,
+    tutorial_progress: Res<TutorialProgress>,
+    versus: Res<Versus>,
+    coop: Res<Coop>,
+    session: Res<NetSession>,
+    mut query: Query<&mut Text, With<GoalUILabel>>,
+) {
+    let mut text = query.get_single_mut().unwrap();
+    if let Some(puzzle) = config.puzzle() {
+        text.0 = format!(
+            "Puzzle: {} ({}/{} turns)",
+            puzzle.goal, puzzle_progress.player_turns, puzzle.par
+        );
+        return;
+    }
+    if config.versus {
+        text.0 = format!(
+            "Versus: Player {}'s turn{}",
+            versus.active + 1,
+            session.side_label()
+        );
+        return;
+    }
+    if config.tutorial {
+        text.0 = format!(
+            "Tutorial: step {}/{}",
+            (tutorial_progress.step + 1).min(TUTORIAL.steps.len()),
+            TUTORIAL.steps.len()
+        );
+        return;
+    }
+    let score = config.ascension().score(score.0);
+    text.0 = config.victory.progress(turn_info.number, score, &progress);
+    if config.coop.is_some() {
+        text.0 = format!(
+            "Player {}{} - {}",
+            coop.active + 1,
+            session.side_label(),
+            text.0
+        );
+    }
 }
 
 pub struct GameInfoPlugin;
 
 impl Plugin for GameInfoPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_turn_information, update_score_information))
-            .add_systems(Startup, setup_game_info.after(setup_ui));
+        app.add_systems(
+            Update,
+            (
+                update_turn_information,
+                update_score_information,
+                update_ascension_information.run_if(resource_changed::<RunConfig>),
+                update_goal_information,
+            ),
+        )
+        .add_systems(Startup, setup_game_info.after(setup_ui));
     }
 }
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::{
+    globals::{UI_FONT, UI_FONT_SIZE},
+    network::is_local_turn,
+    pieces::player::{
+        consumables::{Consumable, Inventory, UseConsumable},
+        spawn::Player,
+    },
+    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
+};
+
+use super::{
+    button::{spawn_text_button, ButtonFunction, ButtonPressedEvent},
+    right_side::setup_right_side,
+    RightUINode,
+};
+
+#[derive(Component)]
+struct HotbarNode;
+
+fn setup_hotbar(mut commands: Commands, right_side_node: Query<Entity, With<RightUINode>>) {
+    let hotbar = commands
+        .spawn((
+            Node {
+                padding: UiRect::all(Val::Px(2.0)),
+                row_gap: Val::Px(2.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+            Name::new("Hotbar"),
+            HotbarNode,
+        ))
+        .id();
+    commands.entity(right_side_node.single()).add_child(hotbar);
+}
+
+/// Shows the consumables of the hero playing, whenever they change
+fn display_hotbar(
+    mut commands: Commands,
+    hotbar: Query<Entity, With<HotbarNode>>,
+    inventory: Query<&Inventory, With<Player>>,
+    asset_server: Res<AssetServer>,
+    mut shown: Local<Option<Vec<Consumable>>>,
+) {
+    let consumables = inventory
+        .get_single()
+        .map(|inventory| inventory.0.clone())
+        .unwrap_or_default();
+    if shown.as_ref() == Some(&consumables) {
+        return;
+    }
+    let Ok(hotbar) = hotbar.get_single() else {
+        return;
+    };
+    commands.entity(hotbar).despawn_descendants();
+    commands.entity(hotbar).with_children(|parent| {
+        if consumables.is_empty() {
+            parent.spawn((
+                Text("No items".to_string()),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+        }
+        for (slot, consumable) in consumables.iter().enumerate() {
+            let free = if consumable.takes_turn() {
+                ""
+            } else {
+                " (free)"
+            };
+            spawn_text_button(
+                parent,
+                &asset_server,
+                format!("{}: {}{}", slot + 1, consumable, free),
+                ButtonFunction::UseConsumable(slot),
+            );
+        }
+    });
+    *shown = Some(consumables);
+}
+
+pub fn press_hotbar_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut use_event_writer: EventWriter<UseConsumable>,
+) {
+    for event in event_reader.read() {
+        if let ButtonFunction::UseConsumable(slot) = event.function {
+            use_event_writer.send(UseConsumable(slot));
+        }
+    }
+}
+
+pub struct HotbarPlugin;
+
+impl Plugin for HotbarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_hotbar.after(setup_right_side))
+            .add_systems(Update, display_hotbar.run_if(in_state(GameState::Game)))
+            .add_systems(
+                Update,
+                press_hotbar_buttons
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(TurnState::PlayerInput))
+                    .run_if(in_state(GamePauseState::Playing))
+                    .run_if(is_local_turn)
+                    .run_if(on_event::<ButtonPressedEvent>),
+            );
+    }
+}
@@ -2,10 +2,21 @@ use bevy::prelude::*;
 
 use crate::{
     board::position::BoardPosition,
-    globals::{UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE},
+    game_logic::balance::Balance,
+    globals::{PRIMARY_COLOR, SECONDARY_COLOR, UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE},
     input::click_tile::HoveredTile,
-    pieces::{damage::Attack, health::Health},
-    states::game_state::GameState,
+    pieces::{
+        common::{Piece, Team},
+        damage::Attack,
+        health::{DeathAnimation, Health},
+        player::{
+            consumables::Promoted,
+            spawn::Player,
+            upgrades::{data::Upgrades, unique_upgrades::block::Block},
+        },
+        preview::{preview_action, ActionPreview, PreviewPiece},
+    },
+    states::{game_state::GameState, turn_state::TurnState},
 };
 
 use super::right_side::HoverInfoNode;
@@ -16,16 +27,40 @@ fn display_enemy_information(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut hover_info_node: Query<(Entity, &mut Visibility), With<HoverInfoNode>>,
+    player: Query<
+        (
+            Entity,
+            &BoardPosition,
+            &Attack,
+            &Upgrades,
+            Option<&Promoted>,
+        ),
+        With<Player>,
+    >,
+    other_pieces: Query<
+        (
+            Entity,
+            &BoardPosition,
+            &Team,
+            &Health,
+            &Block,
+            &Upgrades,
+            &Name,
+        ),
+        (With<Piece>, Without<Player>, Without<DeathAnimation>),
+    >,
+    turn_state: Res<State<TurnState>>,
+    balance: Res<Balance>,
 ) {
     if hovered_tile.is_changed() {
         let mut displayed = false;
         let (hover_info_node, mut visibility) = hover_info_node.single_mut();
+        commands.entity(hover_info_node).despawn_descendants();
         if let Some(tile_position) = hovered_tile.0 {
             for (board_position, attack, health, name) in pieces.iter() {
                 if board_position == &tile_position {
                     displayed = true;
                     *visibility = Visibility::Visible;
-                    commands.entity(hover_info_node).despawn_descendants();
                     commands.entity(hover_info_node).with_children(|parent| {
                         parent
                             .spawn((Node {
@@ -64,14 +99,147 @@ fn display_enemy_information(
                     });
                 }
             }
+            if *turn_state.get() == TurnState::PlayerInput {
+                if let Ok((
+                    player_entity,
+                    player_position,
+                    player_attack,
+                    player_upgrades,
+                    promoted,
+                )) = player.get_single()
+                {
+                    let board: Vec<PreviewPiece> = other_pieces
+                        .iter()
+                        .map(|(entity, position, team, health, block, upgrades, name)| {
+                            PreviewPiece {
+                                entity,
+                                position: *position,
+                                team: *team,
+                                health: health.value,
+                                block: block.amount,
+                                movement_types: upgrades.get_movement_types_set(),
+                                name: name.to_string(),
+                            }
+                        })
+                        .collect();
+                    if let Some(preview) = preview_action(
+                        tile_position,
+                        player_entity,
+                        *player_position,
+                        player_attack.0.upgraded_value,
+                        player_upgrades,
+                        promoted,
+                        &balance,
+                        &board,
+                    ) {
+                        displayed = true;
+                        *visibility = Visibility::Visible;
+                        spawn_preview(&mut commands, hover_info_node, &asset_server, &preview);
+                    }
+                }
+            }
         }
         if !displayed {
-            commands.entity(hover_info_node).despawn_descendants();
             *visibility = Visibility::Hidden;
         }
     }
 }
 
+fn spawn_preview(
+    commands: &mut Commands,
+    hover_info_node: Entity,
+    asset_server: &Res<AssetServer>,
+    preview: &ActionPreview,
+) {
+    let mut lines: Vec<(String, Color)> = Vec::new();
+    for attack in preview.attacks.iter() {
+        lines.push((
+            format!(
+                "{:?} -> {}: {} dmg",
+                attack.movement_type, attack.target, attack.damage
+            ),
+            Color::WHITE,
+        ));
+        if !attack.chain_targets.is_empty() {
+            lines.push((
+                format!("  Chain: {}", join_positions(&attack.chain_targets)),
+                PRIMARY_COLOR,
+            ));
+        }
+        if !attack.pierce_targets.is_empty() {
+            lines.push((
+                format!("  Pierce: {}", join_positions(&attack.pierce_targets)),
+                PRIMARY_COLOR,
+            ));
+        }
+        if attack.block > 0 {
+            lines.push((format!("  Block +{}", attack.block), PRIMARY_COLOR));
+        }
+    }
+    if !preview.kills.is_empty() {
+        lines.push((
+            format!("Kills: {}", preview.kills.join(", ")),
+            PRIMARY_COLOR,
+        ));
+    }
+    if preview.repeat_chance > 0.0 && !preview.attacks.is_empty() {
+        lines.push((
+            format!("Repeat chance: {}%", preview.repeat_chance * 100.0),
+            PRIMARY_COLOR,
+        ));
+    }
+    if preview.retaliations.is_empty() {
+        lines.push(("Safe from retaliation".to_string(), Color::WHITE));
+    } else {
+        lines.push((
+            format!("Retaliation: {}", preview.retaliations.join(", ")),
+            SECONDARY_COLOR,
+        ));
+    }
+
+    commands.entity(hover_info_node).with_children(|parent| {
+        parent
+            .spawn((Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },))
+            .with_children(|parent| {
+                let header = if preview.moved {
+                    format!("Move to {}", preview.destination)
+                } else {
+                    "Attack".to_string()
+                };
+                parent.spawn((
+                    Text(header),
+                    TextFont {
+                        font_size: UI_HEADER_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                ));
+                for (line, color) in lines {
+                    parent.spawn((
+                        Text(line),
+                        TextFont {
+                            font_size: UI_FONT_SIZE,
+                            font: asset_server.load(UI_FONT),
+                            ..default()
+                        },
+                        TextColor(color),
+                    ));
+                }
+            });
+    });
+}
+
+fn join_positions(positions: &[BoardPosition]) -> String {
+    positions
+        .iter()
+        .map(|position| position.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub struct HoverInfoPlugin;
 
 impl Plugin for HoverInfoPlugin {
@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::{
+        daily::DailyChallenge,
+        leaderboard::Leaderboard,
+        replay::Replay,
+        run_config::{GameMode, RunConfig},
+    },
+    globals::{DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, UI_FONT, UI_FONT_SIZE},
+    pieces::player::character::CharacterRoster,
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{
+        spawn_menu_button, spawn_text_button, ButtonFunction, ButtonPressedEvent, MenuReturnState,
+    },
+    messages::MessageEvent,
+    RootUINode,
+};
+
+/// Mode of the leaderboard shown on screen
+#[derive(Resource, Default)]
+struct LeaderboardView(GameMode);
+
+#[derive(Component)]
+struct LeaderboardContentNode;
+
+fn show_leaderboard(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    config: Res<RunConfig>,
+    mut view: ResMut<LeaderboardView>,
+) {
+    debug!("Showing leaderboard");
+    let root_node = root_node.single();
+    view.0 = config.mode;
+
+    let leaderboard_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("LeaderboardUI"),
+            StateScoped(GameState::Leaderboard),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text("Leaderboard".to_string()),
+                TextFont {
+                    font_size: DEFEAT_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for mode in [GameMode::Standard, GameMode::Endless, GameMode::Daily] {
+                        spawn_menu_button(
+                            parent,
+                            &asset_server,
+                            &mode.to_string(),
+                            ButtonFunction::ShowLeaderboardMode(mode),
+                        );
+                    }
+                });
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                LeaderboardContentNode,
+            ));
+            spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::Back);
+        })
+        .id();
+
+    commands.entity(root_node).add_child(leaderboard_node);
+}
+
+fn spawn_leaderboard_content(
+    mut commands: Commands,
+    content_node: Query<Entity, With<LeaderboardContentNode>>,
+    asset_server: Res<AssetServer>,
+    leaderboard: Res<Leaderboard>,
+    config: Res<RunConfig>,
+    view: Res<LeaderboardView>,
+    return_state: Res<MenuReturnState>,
+    characters: Res<CharacterRoster>,
+) {
+    let Ok(content_node) = content_node.get_single() else {
+        return;
+    };
+    let (title, entries) = match view.0 {
+        GameMode::Daily => {
+            let daily = DailyChallenge::today(&characters);
+            (
+                format!("Daily - {}", daily.date),
+                leaderboard.top_daily(daily.seed),
+            )
+        }
+        mode => (
+            format!("{} - Ascension {}", mode, config.difficulty),
+            leaderboard.top(mode, config.difficulty),
+        ),
+    };
+    // only highlight the last run when coming from its end screen
+    let highlighted_rank = leaderboard.last_rank.filter(|_| {
+        matches!(return_state.0, GameState::Defeat | GameState::Victory) && view.0 == config.mode
+    });
+
+    commands.entity(content_node).despawn_descendants();
+    commands.entity(content_node).with_children(|parent| {
+        parent.spawn((
+            Text(title),
+            TextFont {
+                font_size: UI_FONT_SIZE,
+                font: asset_server.load(UI_FONT),
+                ..default()
+            },
+        ));
+        parent
+            .spawn(Node {
+                display: Display::Grid,
+                grid_template_columns: RepeatedGridTrack::auto(7),
+                column_gap: Val::Px(24.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                let header = ["#", "Score", "Turns", "Date", "Seed", "Build", "Replay"]
+                    .map(|cell| (cell.to_string(), Color::WHITE));
+                for (cell, color) in header {
+                    spawn_cell(parent, &asset_server, cell, color);
+                }
+                let rows = entries.iter().enumerate().map(|(index, entry)| {
+                    let color = if highlighted_rank == Some(index + 1) {
+                        PRIMARY_COLOR
+                    } else {
+                        Color::WHITE
+                    };
+                    let cells = [
+                        (index + 1).to_string(),
+                        entry.score.to_string(),
+                        entry.turns.to_string(),
+                        entry.date.clone(),
+                        entry.seed.to_string(),
+                        entry.build.clone(),
+                    ]
+                    .map(|cell| (cell, color));
+                    (cells, entry.replay.clone())
+                });
+                for (row, replay) in rows {
+                    for (cell, color) in row {
+                        spawn_cell(parent, &asset_server, cell, color);
+                    }
+                    match replay {
+                        Some(file) => spawn_text_button(
+                            parent,
+                            &asset_server,
+                            "Watch".to_string(),
+                            ButtonFunction::WatchReplay(file),
+                        ),
+                        None => spawn_cell(parent, &asset_server, "-".to_string(), Color::WHITE),
+                    }
+                }
+            });
+        if entries.is_empty() {
+            parent.spawn((
+                Text("No runs yet".to_string()),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+        }
+    });
+}
+
+fn spawn_cell(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    cell: String,
+    color: Color,
+) {
+    parent.spawn((
+        Text(cell),
+        TextFont {
+            font_size: UI_FONT_SIZE,
+            font: asset_server.load(UI_FONT),
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn change_leaderboard_mode(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut view: ResMut<LeaderboardView>,
+) {
+    for event in event_reader.read() {
+        if let ButtonFunction::ShowLeaderboardMode(mode) = event.function {
+            view.0 = mode;
+        }
+    }
+}
+
+/// Plays the replay of an entry back from its start
+fn watch_replay(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    for event in event_reader.read() {
+        let ButtonFunction::WatchReplay(file) = &event.function else {
+            continue;
+        };
+        match Replay::load(file) {
+            Ok(replay) => {
+                debug!("Watching replay {}", file);
+                replay.configure(&mut config);
+                game_state.set(GameState::Game);
+            }
+            Err(error) => {
+                warn!("Could not load replay {}: {}", file, error);
+                message_event_writer.send(MessageEvent {
+                    message: "Could not load the replay.".to_string(),
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeaderboardView>()
+            .add_systems(OnEnter(GameState::Leaderboard), show_leaderboard)
+            .add_systems(
+                Update,
+                (
+                    (change_leaderboard_mode, watch_replay).run_if(on_event::<ButtonPressedEvent>),
+                    spawn_leaderboard_content.run_if(resource_changed::<LeaderboardView>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Leaderboard)),
+            );
+    }
+}
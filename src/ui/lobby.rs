@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::coop::GoldSharing,
+    globals::{
+        DEFEAT_HEADER_FONT_SIZE, ONLINE_RELAY_ADDRESS, ONLINE_RELAY_ENV_VAR, SECONDARY_COLOR,
+        UI_FONT, UI_FONT_SIZE,
+    },
+    network::{protocol::OnlineMode, NetSession},
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction, ButtonPressedEvent},
+    profile::spawn_text,
+    RootUINode,
+};
+
+#[derive(Component)]
+struct LobbyStatusText;
+
+fn show_lobby(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    mut session: ResMut<NetSession>,
+) {
+    debug!("Showing lobby");
+    let root_node = root_node.single();
+    session.status = "Pick a game, the first player in the room hosts it.".to_string();
+
+    let lobby_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("LobbyUI"),
+            StateScoped(GameState::Lobby),
+        ))
+        .with_children(|parent| {
+            spawn_text(
+                parent,
+                &asset_server,
+                "Online".to_string(),
+                DEFEAT_HEADER_FONT_SIZE,
+                Color::WHITE,
+            );
+            for mode in [
+                OnlineMode::Versus,
+                OnlineMode::Coop(GoldSharing::Shared),
+                OnlineMode::Coop(GoldSharing::Split),
+            ] {
+                spawn_menu_button(
+                    parent,
+                    &asset_server,
+                    &mode.to_string(),
+                    ButtonFunction::JoinOnline(mode),
+                );
+            }
+            parent.spawn((
+                Text(session.status.clone()),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+                LobbyStatusText,
+            ));
+            spawn_text(
+                parent,
+                &asset_server,
+                format!(
+                    "Relay {}, set {} to use another one",
+                    std::env::var(ONLINE_RELAY_ENV_VAR)
+                        .unwrap_or_else(|_| ONLINE_RELAY_ADDRESS.to_string()),
+                    ONLINE_RELAY_ENV_VAR
+                ),
+                UI_FONT_SIZE,
+                SECONDARY_COLOR,
+            );
+            spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::ShowMainMenu);
+        })
+        .id();
+
+    commands.entity(root_node).add_child(lobby_node);
+}
+
+fn join_online(mut event_reader: EventReader<ButtonPressedEvent>, mut session: ResMut<NetSession>) {
+    for event in event_reader.read() {
+        if let ButtonFunction::JoinOnline(mode) = event.function {
+            if !session.is_connected() {
+                session.connect(mode);
+            }
+        }
+    }
+}
+
+fn update_lobby_status(
+    session: Res<NetSession>,
+    mut query: Query<&mut Text, With<LobbyStatusText>>,
+) {
+    for mut text in query.iter_mut() {
+        text.0.clone_from(&session.status);
+    }
+}
+
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Lobby), show_lobby)
+            .add_systems(
+                Update,
+                (
+                    join_online.run_if(on_event::<ButtonPressedEvent>),
+                    update_lobby_status.run_if(resource_changed::<NetSession>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Lobby)),
+            );
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::{
+        coop::GoldSharing, daily::DailyChallenge, profile::Profile, run_config::RunConfig,
+        victory::VictoryCondition,
+    },
+    globals::{DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, SECONDARY_COLOR, UI_FONT, UI_FONT_SIZE},
+    pieces::player::character::CharacterRoster,
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction, ButtonPressedEvent},
+    messages::MessageEvent,
+    RootUINode,
+};
+
+fn show_main_menu(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+    characters: Res<CharacterRoster>,
+) {
+    debug!("Showing main menu");
+    let root_node = root_node.single();
+    let daily = DailyChallenge::today(&characters);
+    let daily_played = profile.played_daily(&daily.date);
+
+    let menu_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("MainMenuUI"),
+            StateScoped(GameState::MainMenu),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text("Bullet Chess Heaven".to_string()),
+                TextFont {
+                    font_size: DEFEAT_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Play",
+                ButtonFunction::ShowCharacterSelect,
+            );
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Tutorial",
+                ButtonFunction::StartTutorial,
+            );
+            if !profile.tutorial_done {
+                parent.spawn((
+                    Text("New here? Start with the tutorial".to_string()),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    TextColor(PRIMARY_COLOR),
+                ));
+            }
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Puzzles",
+                ButtonFunction::ShowPuzzles,
+            );
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Daily Challenge",
+                ButtonFunction::StartDaily,
+            );
+            let mut daily_lines = vec![format!("{} - {}", daily.date, daily.character)];
+            daily_lines.extend(daily.mutators.iter().map(|mutator| mutator.to_string()));
+            if daily_played {
+                daily_lines.push("Already played today".to_string());
+            }
+            for line in daily_lines {
+                parent.spawn((
+                    Text(line),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                    TextColor(if daily_played {
+                        SECONDARY_COLOR
+                    } else {
+                        PRIMARY_COLOR
+                    }),
+                ));
+            }
+            spawn_menu_button(parent, &asset_server, "Versus", ButtonFunction::StartVersus);
+            for gold_sharing in [GoldSharing::Shared, GoldSharing::Split] {
+                spawn_menu_button(
+                    parent,
+                    &asset_server,
+                    &format!("Co-op ({})", gold_sharing),
+                    ButtonFunction::StartCoop(gold_sharing),
+                );
+            }
+            // the web build has no sockets to reach the relay with
+            #[cfg(not(target_arch = "wasm32"))]
+            spawn_menu_button(parent, &asset_server, "Online", ButtonFunction::ShowLobby);
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Leaderboard",
+                ButtonFunction::ShowLeaderboard,
+            );
+            spawn_menu_button(parent, &asset_server, "Stats", ButtonFunction::ShowStats);
+            spawn_menu_button(
+                parent,
+                &asset_server,
+                "Profile",
+                ButtonFunction::ShowProfile,
+            );
+        })
+        .id();
+
+    commands.entity(root_node).add_child(menu_node);
+}
+
+/// Starts today's challenge, the attempt is used as soon as it starts
+fn start_daily(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut profile: ResMut<Profile>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    characters: Res<CharacterRoster>,
+) {
+    for event in event_reader.read() {
+        if event.function != ButtonFunction::StartDaily {
+            continue;
+        }
+        let daily = DailyChallenge::today(&characters);
+        if profile.played_daily(&daily.date) {
+            message_event_writer.send(MessageEvent {
+                message: "You already played today's challenge, come back tomorrow.".to_string(),
+                ..default()
+            });
+            continue;
+        }
+        debug!("Starting daily challenge {}", daily.date);
+        daily.configure(&mut config);
+        profile.last_daily = Some(daily.date);
+        profile.save();
+        game_state.set(GameState::Game);
+    }
+}
+
+fn start_tutorial(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for event in event_reader.read() {
+        if event.function == ButtonFunction::StartTutorial {
+            debug!("Starting tutorial");
+            *config = RunConfig {
+                tutorial: true,
+                // the tutorial ends after its last step instead
+                victory: VictoryCondition::Endless,
+                ..default()
+            };
+            game_state.set(GameState::Game);
+        }
+    }
+}
+
+/// Local match between two heroes taking turns on the same machine
+fn start_versus(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for event in event_reader.read() {
+        if event.function == ButtonFunction::StartVersus {
+            debug!("Starting versus match");
+            *config = RunConfig {
+                versus: true,
+                // the last hero standing wins instead
+                victory: VictoryCondition::Endless,
+                ..default()
+            };
+            game_state.set(GameState::Game);
+        }
+    }
+}
+
+/// Run played by two heroes on the same machine
+fn start_coop(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for event in event_reader.read() {
+        if let ButtonFunction::StartCoop(gold_sharing) = event.function {
+            debug!("Starting co-op run with {}", gold_sharing);
+            *config = RunConfig {
+                coop: Some(gold_sharing),
+                ..default()
+            };
+            game_state.set(GameState::Game);
+        }
+    }
+}
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::MainMenu), show_main_menu)
+            .add_systems(
+                Update,
+                (start_daily, start_tutorial, start_versus, start_coop)
+                    .run_if(on_event::<ButtonPressedEvent>)
+                    .run_if(in_state(GameState::MainMenu)),
+            );
+    }
+}
@@ -10,6 +10,10 @@ pub struct MessageEvent {
     pub timer: Option<Timer>,
 }
 
+/// Removes every message on screen
+#[derive(Event)]
+pub struct ClearMessages;
+
 #[derive(Component)]
 struct MessageContainer;
 
@@ -100,6 +104,12 @@ fn despawn_message_container(
     }
 }
 
+fn clear_messages(mut commands: Commands, message_query: Query<Entity, With<Message>>) {
+    for entity in message_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 fn close_message(
     mut commands: Commands,
     message_query: Query<Entity, With<Message>>,
@@ -119,7 +129,15 @@ pub struct MessagesPlugin;
 impl Plugin for MessagesPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<MessageEvent>();
-        app.add_systems(Update, message_system.run_if(on_event::<MessageEvent>));
+        app.add_event::<ClearMessages>();
+        app.add_systems(
+            Update,
+            (
+                clear_messages.run_if(on_event::<ClearMessages>),
+                message_system.run_if(on_event::<MessageEvent>),
+            )
+                .chain(),
+        );
         app.add_systems(Startup, spawn_message_container.after(setup_ui));
         app.add_systems(Update, despawn_message_container);
         app.add_systems(Update, close_message.run_if(on_event::<ButtonPressedEvent>));
@@ -1,23 +1,48 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
 use button::ButtonPlugin;
 use character_info::CharacterInfoPlugin;
+use character_select::CharacterSelectPlugin;
+use combat_log::CombatLogUIPlugin;
 #[cfg(debug_assertions)]
 use debug::DebugPlugin;
 use defeat::DefeatPlugin;
+use draft::DraftPlugin;
 use game_info::GameInfoPlugin;
+use hotbar::HotbarPlugin;
 use hover_info::HoverInfoPlugin;
+use leaderboard::LeaderboardPlugin;
+use lobby::LobbyPlugin;
+use main_menu::MainMenuPlugin;
 use messages::MessagesPlugin;
+use profile::ProfilePlugin;
+use puzzle::PuzzleUIPlugin;
 use right_side::RightSidePlugin;
 use shop::ShopPlugin;
+use stats::StatsPlugin;
+use versus::VersusUIPlugin;
 mod button;
 mod character_info;
+mod character_select;
+mod combat_log;
 mod debug;
 mod defeat;
+pub mod draft;
 mod game_info;
+pub mod hotbar;
+mod leaderboard;
+mod lobby;
+mod main_menu;
 pub mod messages;
+mod profile;
+mod puzzle;
 mod right_side;
 pub mod shop;
-use crate::states::turn_state::TurnInfo;
+mod stats;
+mod versus;
+use crate::{globals::PORTRAIT_PANEL_HEIGHT, states::turn_state::TurnInfo};
 mod hover_info;
 
 fn display_turn_information(turn_info: Res<TurnInfo>) {
@@ -70,18 +95,74 @@ fn setup_ui(mut commands: Commands) {
         });
 }
 
+/// Lays out the side panels above and below the board on portrait screens,
+/// and to its sides otherwise
+fn update_ui_layout(
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut root_node: Query<&mut Node, With<RootUINode>>,
+    mut side_nodes: Query<
+        &mut Node,
+        (
+            Or<(With<LeftUINode>, With<RightUINode>)>,
+            Without<RootUINode>,
+        ),
+    >,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    let portrait = window.height() > window.width();
+    let mut root_node = root_node.single_mut();
+    root_node.flex_direction = if portrait {
+        FlexDirection::Column
+    } else {
+        FlexDirection::Row
+    };
+    for mut node in side_nodes.iter_mut() {
+        if portrait {
+            node.width = Val::Percent(100.0);
+            node.height = Val::Percent(PORTRAIT_PANEL_HEIGHT);
+            node.flex_direction = FlexDirection::Row;
+            node.flex_wrap = FlexWrap::Wrap;
+            node.overflow = Overflow::clip();
+            node.padding = UiRect::all(Val::Px(4.0));
+        } else {
+            node.width = Val::Percent(25.0);
+            node.height = Val::Auto;
+            node.flex_direction = FlexDirection::Column;
+            node.flex_wrap = FlexWrap::NoWrap;
+            node.overflow = Overflow::visible();
+            node.padding = UiRect::all(Val::Px(16.0));
+        }
+    }
+}
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_ui)
+        app.add_systems(Startup, (setup_ui, update_ui_layout).chain())
+            .add_systems(Update, update_ui_layout.run_if(on_event::<WindowResized>))
             .add_plugins((GameInfoPlugin, CharacterInfoPlugin))
-            .add_plugins(DefeatPlugin)
+            .add_plugins((
+                DefeatPlugin,
+                StatsPlugin,
+                LeaderboardPlugin,
+                MainMenuPlugin,
+                ProfilePlugin,
+                CharacterSelectPlugin,
+                PuzzleUIPlugin,
+                VersusUIPlugin,
+                LobbyPlugin,
+            ))
             .add_plugins(ButtonPlugin)
             .add_plugins(ShopPlugin)
+            .add_plugins(DraftPlugin)
             .add_plugins(RightSidePlugin)
+            .add_plugins(HotbarPlugin)
             .add_plugins(MessagesPlugin)
-            .add_plugins(HoverInfoPlugin);
+            .add_plugins(HoverInfoPlugin)
+            .add_plugins(CombatLogUIPlugin);
 
         #[cfg(debug_assertions)]
         app.add_plugins(DebugPlugin);
@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::profile::{Profile, UNLOCKS},
+    globals::{
+        DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, SECONDARY_COLOR, UI_FONT, UI_FONT_SIZE,
+        UI_HEADER_FONT_SIZE,
+    },
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction, ButtonPressedEvent},
+    messages::MessageEvent,
+    RootUINode,
+};
+
+#[derive(Component)]
+struct ProfileContentNode;
+
+fn show_profile_screen(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+) {
+    debug!("Showing profile screen");
+    let root_node = root_node.single();
+
+    let profile_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("ProfileUI"),
+            StateScoped(GameState::Profile),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text("Profile".to_string()),
+                TextFont {
+                    font_size: DEFEAT_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(32.0),
+                        ..default()
+                    },
+                    ProfileContentNode,
+                ))
+                .with_children(|parent| spawn_profile_content(parent, &asset_server, &profile));
+            spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::ShowMainMenu);
+        })
+        .id();
+
+    commands.entity(root_node).add_child(profile_node);
+}
+
+fn spawn_profile_content(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    profile: &Profile,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_text(
+                parent,
+                asset_server,
+                format!("Unlocks ({} meta-currency)", profile.meta_currency),
+                UI_HEADER_FONT_SIZE,
+                Color::WHITE,
+            );
+            for (index, info) in UNLOCKS.iter().enumerate() {
+                let (label, color) = if profile.has(&info.unlock) {
+                    (format!("{} - owned", info.name), PRIMARY_COLOR)
+                } else if profile.can_unlock(info).is_ok() {
+                    (format!("{} - {}", info.name, info.cost), Color::WHITE)
+                } else {
+                    (format!("{} - {}", info.name, info.cost), SECONDARY_COLOR)
+                };
+                spawn_small_button(
+                    parent,
+                    asset_server,
+                    label,
+                    color,
+                    ButtonFunction::BuyUnlock(index),
+                );
+            }
+        });
+}
+
+pub fn spawn_text(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    text: String,
+    font_size: f32,
+    color: Color,
+) {
+    parent.spawn((
+        Text(text),
+        TextFont {
+            font_size,
+            font: asset_server.load(UI_FONT),
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+/// The text color shows the state, the button system overwrites the background
+pub fn spawn_small_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: String,
+    color: Color,
+    function: ButtonFunction,
+) {
+    parent
+        .spawn((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(2.0)),
+            Button,
+            function,
+        ))
+        .with_children(|parent| {
+            spawn_text(parent, asset_server, label, UI_FONT_SIZE, color);
+        });
+}
+
+fn handle_profile_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut profile: ResMut<Profile>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+) {
+    for event in event_reader.read() {
+        if let ButtonFunction::BuyUnlock(index) = event.function {
+            let info = &UNLOCKS[index];
+            match profile.unlock(info) {
+                Ok(()) => {
+                    debug!("Unlocked {}", info.name);
+                    profile.save();
+                }
+                Err(error) => {
+                    message_event_writer.send(MessageEvent {
+                        message: error.to_string(),
+                        ..default()
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn refresh_profile_screen(
+    mut commands: Commands,
+    content_node: Query<Entity, With<ProfileContentNode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+) {
+    for content_node in content_node.iter() {
+        commands.entity(content_node).despawn_descendants();
+        commands
+            .entity(content_node)
+            .with_children(|parent| spawn_profile_content(parent, &asset_server, &profile));
+    }
+}
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Profile), show_profile_screen)
+            .add_systems(
+                Update,
+                (
+                    handle_profile_buttons.run_if(on_event::<ButtonPressedEvent>),
+                    refresh_profile_screen.run_if(resource_changed::<Profile>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Profile)),
+            );
+    }
+}
@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::{
+        profile::Profile,
+        puzzle::{is_puzzle, PuzzleProgress, PUZZLES},
+        run_config::RunConfig,
+        victory::VictoryCondition,
+    },
+    globals::{DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, SECONDARY_COLOR, UI_FONT_SIZE},
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction, ButtonPressedEvent},
+    profile::{spawn_small_button, spawn_text},
+    RootUINode,
+};
+
+fn stars_text(stars: usize) -> String {
+    format!("{}{}", "*".repeat(stars), "-".repeat(3 - stars.min(3)))
+}
+
+fn show_puzzle_select(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+) {
+    debug!("Showing puzzle select");
+    let root_node = root_node.single();
+
+    let select_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("PuzzleSelectUI"),
+            StateScoped(GameState::PuzzleSelect),
+        ))
+        .with_children(|parent| {
+            spawn_text(
+                parent,
+                &asset_server,
+                "Puzzles".to_string(),
+                DEFEAT_HEADER_FONT_SIZE,
+                Color::WHITE,
+            );
+            for (index, puzzle) in PUZZLES.iter().enumerate() {
+                let stars = profile
+                    .puzzle_stars
+                    .get(&puzzle.name)
+                    .copied()
+                    .unwrap_or_default();
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        spawn_small_button(
+                            parent,
+                            &asset_server,
+                            format!("{} {}", puzzle.name, stars_text(stars)),
+                            if stars > 0 {
+                                PRIMARY_COLOR
+                            } else {
+                                Color::WHITE
+                            },
+                            ButtonFunction::StartPuzzle(index),
+                        );
+                        spawn_text(
+                            parent,
+                            &asset_server,
+                            puzzle.description.clone(),
+                            UI_FONT_SIZE,
+                            Color::WHITE,
+                        );
+                        spawn_text(
+                            parent,
+                            &asset_server,
+                            format!("{}, par {} turns", puzzle.goal, puzzle.par),
+                            UI_FONT_SIZE,
+                            SECONDARY_COLOR,
+                        );
+                    });
+            }
+            spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::ShowMainMenu);
+        })
+        .id();
+
+    commands.entity(root_node).add_child(select_node);
+}
+
+fn start_puzzle(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut config: ResMut<RunConfig>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for event in event_reader.read() {
+        if let ButtonFunction::StartPuzzle(index) = event.function {
+            debug!("Starting puzzle {}", PUZZLES[index].name);
+            *config = RunConfig {
+                puzzle: Some(index),
+                // the puzzle goal ends the game instead
+                victory: VictoryCondition::Endless,
+                ..default()
+            };
+            game_state.set(GameState::Game);
+        }
+    }
+}
+
+/// Result of a puzzle, replaces the end of run screen
+fn show_puzzle_result(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    state: Res<State<GameState>>,
+    config: Res<RunConfig>,
+    progress: Res<PuzzleProgress>,
+) {
+    let Some(puzzle) = config.puzzle() else {
+        return;
+    };
+    let state = *state.get();
+    debug!("Showing puzzle result");
+    let root_node = root_node.single();
+    let solved = state == GameState::Victory;
+
+    let result_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("PuzzleResultUI"),
+            StateScoped(state),
+        ))
+        .with_children(|parent| {
+            spawn_text(
+                parent,
+                &asset_server,
+                if solved { "Solved" } else { "Failed" }.to_string(),
+                DEFEAT_HEADER_FONT_SIZE,
+                Color::WHITE,
+            );
+            spawn_text(
+                parent,
+                &asset_server,
+                format!("{} - {}", puzzle.name, puzzle.goal),
+                UI_FONT_SIZE,
+                Color::WHITE,
+            );
+            spawn_text(
+                parent,
+                &asset_server,
+                format!("Turns used: {} (par {})", progress.player_turns, puzzle.par),
+                UI_FONT_SIZE,
+                Color::WHITE,
+            );
+            if solved {
+                spawn_text(
+                    parent,
+                    &asset_server,
+                    stars_text(puzzle.stars(progress.player_turns)),
+                    DEFEAT_HEADER_FONT_SIZE,
+                    PRIMARY_COLOR,
+                );
+            }
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_menu_button(parent, &asset_server, "Retry", ButtonFunction::RestartGame);
+                    spawn_menu_button(
+                        parent,
+                        &asset_server,
+                        "Puzzles",
+                        ButtonFunction::ShowPuzzles,
+                    );
+                    spawn_menu_button(parent, &asset_server, "Menu", ButtonFunction::ShowMainMenu);
+                });
+        })
+        .id();
+
+    commands.entity(root_node).add_child(result_node);
+}
+
+pub struct PuzzleUIPlugin;
+
+impl Plugin for PuzzleUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::PuzzleSelect), show_puzzle_select)
+            .add_systems(
+                Update,
+                start_puzzle
+                    .run_if(on_event::<ButtonPressedEvent>)
+                    .run_if(in_state(GameState::PuzzleSelect)),
+            );
+        for state in [GameState::Defeat, GameState::Victory] {
+            app.add_systems(OnEnter(state), show_puzzle_result.run_if(is_puzzle));
+        }
+    }
+}
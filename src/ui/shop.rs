@@ -1,32 +1,46 @@
 use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     board::highlight::HighlightCache,
+    game_logic::{
+        ascension::Ascension, balance::Balance, daily::Mutator, profile::Profile,
+        run_config::RunConfig,
+    },
     globals::{
-        SHOP_UPGRADES_COUNT_MOVEMENT, SHOP_UPGRADES_COUNT_STATS, UI_FONT, UI_FONT_SIZE,
-        UI_HEADER_FONT_SIZE, UI_PIECE_SPRITE_SIZE_SHOP,
+        SECONDARY_COLOR, UI_FONT, UI_FONT_SIZE, UI_HEADER_FONT_SIZE, UI_PIECE_SPRITE_SIZE_SHOP,
     },
     graphics::spritesheet::SpriteSheetAtlas,
     input::keyboard::ToggleShop,
+    network::is_local_turn,
     pieces::{
         damage::Attack,
         health::Health,
         player::{
+            consumables::{Consumable, Inventory},
+            experience::PlayerLevel,
             gold::Gold,
+            relics::{Relic, Relics},
             spawn::Player,
             upgrades::{
-                data::{Effect, Upgrade, Upgrades, UPGRADES_MOVEMENT, UPGRADES_STATS},
+                data::{rarity_steps, Effect, Upgrade, UpgradeCatalog, Upgrades},
                 stats::StatVariant,
                 unique_upgrades::limit::MovementTypeLimit,
             },
         },
     },
-    states::{game_state::GameState, pause_state::GamePauseState, turn_state::TurnState},
-    utils::rng::sample_weighted,
+    states::{
+        game_state::GameState,
+        pause_state::GamePauseState,
+        turn_state::{TurnInfo, TurnState},
+    },
+    utils::rng::{sample_weighted, GameRng},
 };
 
 use super::{
-    button::{ButtonFunction, ButtonHoverEvent, ButtonPressedEvent},
+    button::{spawn_text_button, ButtonFunction, ButtonHoverEvent, ButtonPressedEvent},
+    draft::DraftState,
     messages::MessageEvent,
     right_side::get_shop_button,
     RootUINode,
@@ -35,8 +49,26 @@ use super::{
 #[derive(Component)]
 struct ShopNode;
 
-#[derive(Resource)]
-struct ShopUpgrades(Vec<Upgrade>);
+/// Upgrade for sale in a slot of the shop
+#[derive(Clone, Debug)]
+pub struct ShopOffer {
+    pub upgrade: Upgrade,
+    /// Locked offers stay in their slot when the shop is refreshed
+    pub locked: bool,
+    /// Sold for `shop_sale_percent` of its price
+    pub on_sale: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct ShopUpgrades {
+    pub offers: Vec<ShopOffer>,
+    /// Upgrade bought to be equipped later, when the movement limit allows it
+    pub reserved: Option<Upgrade>,
+    /// Special slot, empty once bought until the next refresh
+    pub relic: Option<Relic>,
+    /// Whether the shop was drawn for this run, the pools can leave some slots empty
+    pub drawn: bool,
+}
 
 fn toggle_shop(
     mut event_reader: EventReader<ToggleShop>,
@@ -108,19 +140,108 @@ pub enum ShopState {
     Closed,
 }
 
-fn update_shop(shop_upgrades: &mut ResMut<ShopUpgrades>) {
-    let upgrades_mov = sample_weighted(SHOP_UPGRADES_COUNT_MOVEMENT, &UPGRADES_MOVEMENT);
-    let upgrades_stats = sample_weighted(SHOP_UPGRADES_COUNT_STATS, &UPGRADES_STATS);
-    let chosen_upgrades = upgrades_mov.into_iter().chain(upgrades_stats);
-    **shop_upgrades = ShopUpgrades(chosen_upgrades.collect());
+/// Refreshes done on the current turn, each one costs more
+#[derive(Resource, Default)]
+pub struct ShopVisit {
+    pub refreshes: usize,
+}
+
+/// Cost of the next refresh, before the ascension prices
+pub fn refresh_cost(base_cost: usize, refreshes: usize, balance: &Balance) -> usize {
+    base_cost + refreshes * balance.shop_refresh_cost_increase
+}
+
+/// Draws the shop from the upgrades unlocked by the profile, with the rarity odds of
+/// `rarity_steps` of progression, priced for the ascension level
+fn update_shop(
+    shop_upgrades: &mut ResMut<ShopUpgrades>,
+    profile: &Profile,
+    catalog: &UpgradeCatalog,
+    balance: &Balance,
+    ascension: Ascension,
+    rarity_steps: usize,
+    owned_relics: &[Relic],
+    rng: &mut impl Rng,
+) {
+    let with_odds = |pool: Vec<Upgrade>| -> Vec<Upgrade> {
+        pool.into_iter()
+            .map(|upgrade| upgrade.with_rarity_odds(rarity_steps, balance))
+            .collect()
+    };
+    let mut sample_pool = |count: usize, pool: Vec<Upgrade>| {
+        sample_weighted(rng, count, &with_odds(pool)).unwrap_or_else(|| {
+            warn!("No upgrade can be drawn for the shop");
+            Vec::new()
+        })
+    };
+    let upgrades_mov = sample_pool(
+        profile.shop_movement_count(),
+        profile.movement_pool(catalog),
+    );
+    let upgrades_stats = sample_pool(profile.shop_stats_count(), profile.stats_pool(catalog));
+    let mut offers: Vec<ShopOffer> = upgrades_mov
+        .into_iter()
+        .chain(upgrades_stats)
+        .map(|mut upgrade| {
+            upgrade.cost = ascension.shop_price(upgrade.cost);
+            let on_sale = rng.gen_bool(balance.shop_sale_chance);
+            if on_sale {
+                upgrade.cost = upgrade.cost * balance.shop_sale_percent / 100;
+            }
+            ShopOffer {
+                upgrade,
+                locked: false,
+                on_sale,
+            }
+        })
+        .collect();
+    keep_locked_offers(&shop_upgrades.offers, &mut offers);
+    shop_upgrades.offers = offers;
+    shop_upgrades.relic = Relic::draw(rng, owned_relics);
+    shop_upgrades.drawn = true;
+}
+
+/// Locked offers take back their slot in the refreshed shop
+fn keep_locked_offers(previous: &[ShopOffer], offers: &mut [ShopOffer]) {
+    for (offer, previous) in offers.iter_mut().zip(previous) {
+        if previous.locked {
+            *offer = previous.clone();
+        }
+    }
+}
+
+/// Gold back for an upgrade bought at this price
+pub fn sell_price(cost: usize, balance: &Balance) -> usize {
+    cost * balance.shop_sell_percent / 100
 }
 
 #[derive(Event)]
 pub struct ApplyUpgrades(pub Upgrade);
 
+/// Index of an upgrade in the shop
+#[derive(Component, Clone, Copy)]
+struct ShopSlot(usize);
+
+/// Buys the upgrade in this slot of the shop, from a button or the other online player
+#[derive(Event, Clone, Copy)]
+pub struct BuyShopUpgrade(pub usize);
+
 fn buy_upgrade(
     mut event_reader: EventReader<ButtonPressedEvent>,
-    upgrade: Query<&Upgrade>,
+    slots: Query<&ShopSlot>,
+    mut buy_event_writer: EventWriter<BuyShopUpgrade>,
+) {
+    for event in event_reader.read() {
+        if event.function == ButtonFunction::BuyUpgrade {
+            let slot = slots.get(event.entity).expect("Upgrade not found");
+            buy_event_writer.send(BuyShopUpgrade(slot.0));
+        }
+    }
+}
+
+fn buy_shop_upgrade(
+    mut event_reader: EventReader<BuyShopUpgrade>,
+    mut shop_upgrades: ResMut<ShopUpgrades>,
     mut gold: ResMut<Gold>,
     mut refresh_event_writer: EventWriter<RefreshShop>,
     mut player_upgrades_query: Query<(&mut Upgrades, &MovementTypeLimit), With<Player>>,
@@ -128,41 +249,213 @@ fn buy_upgrade(
     mut message_event_writer: EventWriter<MessageEvent>,
 ) {
     for event in event_reader.read() {
-        if event.function == ButtonFunction::BuyUpgrade {
-            let upgrade = upgrade.get(event.entity).expect("Upgrade not found");
-            if gold.amount >= upgrade.cost {
-                if let Effect::MovementType(movement_types) = &upgrade.effect {
-                    let (player_upgrades, player_limit) = player_upgrades_query
-                        .get_single()
-                        .expect("Player upgrades not found");
-                    let movement_types_set = player_upgrades.get_movement_types_set();
-                    if !movement_types_set
-                        .contains(movement_types.first().expect("Movement type not found"))
-                    {
-                        let limit = player_limit.limit;
-                        let current_count = movement_types_set.len();
-                        println!("{:?} {:?} {:?}", limit, current_count, movement_types_set);
-                        if current_count >= limit {
-                            message_event_writer.send(MessageEvent {
-                                message: "You already have the maximum number of movement types. Upgrade your existing movement types to unlock new ones.".to_string(),
-                                ..default()
-                            });
-                            return;
-                        }
-                    }
+        let Some(offer) = shop_upgrades.offers.get_mut(event.0) else {
+            continue;
+        };
+        let upgrade = offer.upgrade.clone();
+        if gold.amount >= upgrade.cost {
+            let (player_upgrades, player_limit) = player_upgrades_query
+                .get_single()
+                .expect("Player upgrades not found");
+            if !player_upgrades.within_movement_limit(&upgrade, player_limit.limit) {
+                message_event_writer.send(movement_limit_message());
+                return;
+            }
+            gold.amount -= upgrade.cost;
+            // the slot gets a new upgrade
+            offer.locked = false;
+            let (mut player_upgrades, _) = player_upgrades_query.single_mut();
+            player_upgrades.0.push(upgrade.clone());
+            refresh_event_writer.send(RefreshShop { cost: 0 });
+            apply_upgrades_event_writer.send(ApplyUpgrades(upgrade.clone()));
+        } else {
+            message_event_writer.send(MessageEvent {
+                message: "Not enough gold to buy upgrade.".to_string(),
+                ..default()
+            });
+        }
+    }
+}
+
+/// Shop action besides buying, from a button or the other online player
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ShopAction {
+    /// Sell an owned upgrade, by its index in the player upgrades
+    Sell(usize),
+    SellReserved,
+    /// Keep the upgrade of this slot when the shop is refreshed
+    ToggleLock(usize),
+    /// Buy the upgrade of this slot without equipping it
+    Reserve(usize),
+    EquipReserved,
+    BuyConsumable(Consumable),
+    BuyRelic,
+}
+
+fn press_shop_action_buttons(
+    mut event_reader: EventReader<ButtonPressedEvent>,
+    mut action_writer: EventWriter<ShopAction>,
+) {
+    for event in event_reader.read() {
+        let action = match event.function {
+            ButtonFunction::SellUpgrade(index) => ShopAction::Sell(index),
+            ButtonFunction::SellReserved => ShopAction::SellReserved,
+            ButtonFunction::ToggleLockUpgrade(slot) => ShopAction::ToggleLock(slot),
+            ButtonFunction::ReserveUpgrade(slot) => ShopAction::Reserve(slot),
+            ButtonFunction::EquipReserved => ShopAction::EquipReserved,
+            ButtonFunction::BuyConsumable(consumable) => ShopAction::BuyConsumable(consumable),
+            ButtonFunction::BuyRelic => ShopAction::BuyRelic,
+            _ => continue,
+        };
+        action_writer.send(action);
+    }
+}
+
+/// Removed upgrades are sent with `ApplyUpgrades` too, so the stats and the movement limit
+/// are evaluated again without them
+fn apply_shop_action(
+    mut event_reader: EventReader<ShopAction>,
+    mut shop_upgrades: ResMut<ShopUpgrades>,
+    mut gold: ResMut<Gold>,
+    mut player_upgrades_query: Query<
+        (
+            &mut Upgrades,
+            &MovementTypeLimit,
+            &mut Inventory,
+            &mut Relics,
+        ),
+        With<Player>,
+    >,
+    mut refresh_event_writer: EventWriter<RefreshShop>,
+    mut refresh_ui_event_writer: EventWriter<RefreshShopUI>,
+    mut apply_upgrades_event_writer: EventWriter<ApplyUpgrades>,
+    mut message_event_writer: EventWriter<MessageEvent>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
+) {
+    let Ok((mut player_upgrades, player_limit, mut inventory, mut relics)) =
+        player_upgrades_query.get_single_mut()
+    else {
+        return;
+    };
+    for action in event_reader.read() {
+        match *action {
+            ShopAction::Sell(index) => {
+                let Some(upgrade) = player_upgrades.0.get(index) else {
+                    continue;
+                };
+                let mut remaining = player_upgrades.clone();
+                remaining.0.remove(index);
+                let movement_upgrades = remaining
+                    .0
+                    .iter()
+                    .filter(|upgrade| matches!(upgrade.effect, Effect::MovementType(_)))
+                    .count();
+                if matches!(upgrade.effect, Effect::MovementType(_)) && movement_upgrades == 0 {
+                    message_event_writer.send(MessageEvent {
+                        message: "You can not sell your last movement upgrade.".to_string(),
+                        ..default()
+                    });
+                    continue;
                 }
-                gold.amount -= upgrade.cost;
-                let (mut player_upgrades, _) = player_upgrades_query.single_mut();
-                player_upgrades.0.push(upgrade.clone());
+                // the limit is computed again from the movement upgrades left
+                if remaining.get_movement_types_set().len()
+                    > balance.movement_type_limit(movement_upgrades)
+                {
+                    message_event_writer.send(MessageEvent {
+                        message:
+                            "Selling this upgrade would leave you more movement types than allowed."
+                                .to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                let upgrade = player_upgrades.0.remove(index);
+                gold.amount += sell_price(upgrade.cost, &balance);
+                apply_upgrades_event_writer.send(ApplyUpgrades(upgrade));
+            }
+            ShopAction::SellReserved => {
+                if let Some(upgrade) = shop_upgrades.reserved.take() {
+                    gold.amount += sell_price(upgrade.cost, &balance);
+                }
+            }
+            ShopAction::ToggleLock(slot) => {
+                if let Some(offer) = shop_upgrades.offers.get_mut(slot) {
+                    offer.locked = !offer.locked;
+                }
+            }
+            ShopAction::Reserve(slot) => {
+                if shop_upgrades.reserved.is_some() {
+                    message_event_writer.send(MessageEvent {
+                        message: "The reserve slot is taken.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                let Some(offer) = shop_upgrades.offers.get_mut(slot) else {
+                    continue;
+                };
+                if gold.amount < offer.upgrade.cost {
+                    message_event_writer.send(MessageEvent {
+                        message: "Not enough gold to reserve upgrade.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                gold.amount -= offer.upgrade.cost;
+                offer.locked = false;
+                shop_upgrades.reserved = Some(offer.upgrade.clone());
                 refresh_event_writer.send(RefreshShop { cost: 0 });
-                apply_upgrades_event_writer.send(ApplyUpgrades(upgrade.clone()));
-            } else {
-                message_event_writer.send(MessageEvent {
-                    message: "Not enough gold to buy upgrade.".to_string(),
-                    ..default()
-                });
+            }
+            ShopAction::EquipReserved => {
+                let Some(upgrade) = shop_upgrades.reserved.clone() else {
+                    continue;
+                };
+                if !player_upgrades.within_movement_limit(&upgrade, player_limit.limit) {
+                    message_event_writer.send(movement_limit_message());
+                    continue;
+                }
+                shop_upgrades.reserved = None;
+                player_upgrades.0.push(upgrade.clone());
+                apply_upgrades_event_writer.send(ApplyUpgrades(upgrade));
+            }
+            ShopAction::BuyConsumable(consumable) => {
+                let cost = config.ascension().shop_price(balance.consumable_cost);
+                if gold.amount < cost {
+                    message_event_writer.send(MessageEvent {
+                        message: "Not enough gold to buy item.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                if !inventory.add(consumable) {
+                    message_event_writer.send(MessageEvent {
+                        message: "Your inventory is full.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                gold.amount -= cost;
+            }
+            ShopAction::BuyRelic => {
+                let Some(relic) = shop_upgrades.relic else {
+                    continue;
+                };
+                let cost = config.ascension().shop_price(balance.relic_cost);
+                if gold.amount < cost {
+                    message_event_writer.send(MessageEvent {
+                        message: "Not enough gold to buy relic.".to_string(),
+                        ..default()
+                    });
+                    continue;
+                }
+                if relics.add(relic) {
+                    gold.amount -= cost;
+                    shop_upgrades.relic = None;
+                }
             }
         }
+        refresh_ui_event_writer.send(RefreshShopUI);
     }
 }
 
@@ -170,10 +463,36 @@ fn update_shop_description(
     mut hover_event_reader: EventReader<ButtonHoverEvent>,
     description_ui: Query<Entity, With<ShopDescriptionUI>>,
     upgrade_query: Query<&Upgrade>,
+    player_upgrades: Query<&Upgrades, With<Player>>,
+    shop_upgrades: Res<ShopUpgrades>,
+    balance: Res<Balance>,
     asset_server: Res<AssetServer>,
     mut commands: Commands,
 ) {
     for event in hover_event_reader.read() {
+        let description = match event.function {
+            ButtonFunction::BuyConsumable(consumable) => Some(consumable.description(&balance)),
+            ButtonFunction::BuyRelic => {
+                shop_upgrades.relic.map(|relic| relic.description(&balance))
+            }
+            _ => None,
+        };
+        if let Some(description) = description {
+            let description_ui = description_ui
+                .get_single()
+                .expect("Description UI not found");
+            commands.entity(description_ui).despawn_descendants();
+            commands.entity(description_ui).with_children(|parent| {
+                parent.spawn((
+                    TextSpan(description),
+                    TextFont {
+                        font: asset_server.load(UI_FONT),
+                        font_size: UI_FONT_SIZE,
+                        ..default()
+                    },
+                ));
+            });
+        }
         if event.function == ButtonFunction::BuyUpgrade {
             let description_ui = description_ui
                 .get_single()
@@ -183,8 +502,11 @@ fn update_shop_description(
             // but given that it is not critical to the game, I will just
             // ignore the error
             if let Ok(upgrade) = upgrade_query.get(event.entity) {
+                let owned = player_upgrades
+                    .get_single()
+                    .map_or(0, |upgrades| upgrades.owned_movement_upgrades(upgrade));
                 commands.entity(description_ui).with_children(|parent| {
-                    for (text, text_color) in upgrade.description.iter() {
+                    for (text, text_color) in upgrade.description_with_tiers(owned).iter() {
                         parent.spawn((
                             text.clone(),
                             *text_color,
@@ -216,7 +538,12 @@ fn apply_upgrades(
                     health.max_value.apply_upgrades(upgrades);
                     let new_health = health.max_value.upgraded_value;
                     let health_diff = (new_health / prev_health * health.value) - health.value;
-                    health.heal(health_diff);
+                    if health_diff >= 0.0 {
+                        health.heal(health_diff);
+                    } else {
+                        // a sold max health upgrade lowers the health without damaging the hero
+                        health.value = (health.value + health_diff).min(new_health);
+                    }
                 }
                 StatVariant::Attack => {
                     attack.0.apply_upgrades(upgrades);
@@ -229,6 +556,13 @@ fn apply_upgrades(
     }
 }
 
+pub fn movement_limit_message() -> MessageEvent {
+    MessageEvent {
+        message: "You already have the maximum number of movement types. Upgrade your existing movement types to unlock new ones.".to_string(),
+        ..default()
+    }
+}
+
 #[derive(Event)]
 pub struct RefreshShop {
     pub cost: usize,
@@ -238,21 +572,82 @@ fn update_shop_system(
     mut shop_upgrades: ResMut<ShopUpgrades>,
     mut refresh_event: EventReader<RefreshShop>,
     mut gold: ResMut<Gold>,
+    mut rng: ResMut<GameRng>,
+    mut visit: ResMut<ShopVisit>,
+    profile: Res<Profile>,
+    config: Res<RunConfig>,
+    catalog: Res<UpgradeCatalog>,
+    balance: Res<Balance>,
+    turn_info: Res<TurnInfo>,
+    player_level: Res<PlayerLevel>,
+    player_relics: Query<&Relics, With<Player>>,
+    mut message_event_writer: EventWriter<MessageEvent>,
 ) {
+    let ascension = config.ascension();
+    let profile = config.run_profile(&profile);
+    let steps = rarity_steps(player_level.level, turn_info.number, &balance);
+    let owned_relics = player_relics
+        .get_single()
+        .map(|relics| relics.0.clone())
+        .unwrap_or_default();
     // ensure the shop is filled
-    if shop_upgrades.0.len() != (SHOP_UPGRADES_COUNT_MOVEMENT + SHOP_UPGRADES_COUNT_STATS) {
-        update_shop(&mut shop_upgrades);
+    if !shop_upgrades.drawn {
+        update_shop(
+            &mut shop_upgrades,
+            profile,
+            &catalog,
+            &balance,
+            ascension,
+            steps,
+            &owned_relics,
+            &mut rng.0,
+        );
     }
 
     for event in refresh_event.read() {
+        // the free refreshes after buying do not count
+        let paid = event.cost > 0;
+        if paid && config.has_mutator(Mutator::NoShopRefresh) {
+            message_event_writer.send(MessageEvent {
+                message: "Shop refresh is disabled by today's mutator.".to_string(),
+                ..default()
+            });
+            continue;
+        }
         debug!("Refreshing shop");
-        if gold.amount >= event.cost {
-            gold.amount -= event.cost;
-            update_shop(&mut shop_upgrades);
+        let cost = if paid {
+            ascension.shop_price(refresh_cost(event.cost, visit.refreshes, &balance))
+        } else {
+            0
+        };
+        if gold.amount >= cost {
+            gold.amount -= cost;
+            if paid {
+                visit.refreshes += 1;
+            }
+            update_shop(
+                &mut shop_upgrades,
+                profile,
+                &catalog,
+                &balance,
+                ascension,
+                steps,
+                &owned_relics,
+                &mut rng.0,
+            );
         }
     }
 }
 
+fn reset_shop_visit(mut visit: ResMut<ShopVisit>) {
+    visit.refreshes = 0;
+}
+
+/// Empties the shop so a new run draws it from the freshly seeded rng
+fn reset_shop(mut shop_upgrades: ResMut<ShopUpgrades>) {
+    *shop_upgrades = ShopUpgrades::default();
+}
+
 #[derive(Event)]
 struct RefreshShopUI;
 
@@ -270,6 +665,10 @@ fn display_shop(
     asset_server: Res<AssetServer>,
     mut refresh_event: EventReader<RefreshShopUI>,
     atlas_layout: Res<SpriteSheetAtlas>,
+    player_upgrades: Query<&Upgrades, With<Player>>,
+    visit: Res<ShopVisit>,
+    config: Res<RunConfig>,
+    balance: Res<Balance>,
 ) {
     if shop_upgrades.is_changed() || refresh_event.read().count() > 0 {
         // reset shop
@@ -290,7 +689,16 @@ fn display_shop(
             .id();
         commands.entity(shop_node).add_child(upgrades_container);
         // display shop
-        for upgrade in shop_upgrades.0.iter() {
+        for (slot, offer) in shop_upgrades.offers.iter().enumerate() {
+            let upgrade = &offer.upgrade;
+            let slot_ui = commands
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    width: Val::Percent(100.0),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                })
+                .id();
             let shop_upgrade_ui = commands
                 .spawn((
                     Node {
@@ -309,6 +717,7 @@ fn display_shop(
                     Button,
                     ButtonFunction::BuyUpgrade,
                     upgrade.clone(),
+                    ShopSlot(slot),
                 ))
                 .with_children(|parent| {
                     parent.spawn((
@@ -319,8 +728,14 @@ fn display_shop(
                             ..default()
                         },
                     ));
+                    let (price, price_color) = if offer.on_sale {
+                        (format!("${} (sale)", upgrade.cost), SECONDARY_COLOR)
+                    } else {
+                        (format!("${}", upgrade.cost), Color::WHITE)
+                    };
                     parent.spawn((
-                        Text(format!("${}", upgrade.cost)),
+                        Text(price),
+                        TextColor(price_color),
                         TextFont {
                             font_size: UI_FONT_SIZE,
                             font: asset_server.load(UI_FONT),
@@ -343,9 +758,22 @@ fn display_shop(
                     ));
                 })
                 .id();
-            commands
-                .entity(upgrades_container)
-                .add_child(shop_upgrade_ui);
+            commands.entity(slot_ui).add_child(shop_upgrade_ui);
+            commands.entity(slot_ui).with_children(|parent| {
+                spawn_text_button(
+                    parent,
+                    &asset_server,
+                    if offer.locked { "Unlock" } else { "Lock" }.to_string(),
+                    ButtonFunction::ToggleLockUpgrade(slot),
+                );
+                spawn_text_button(
+                    parent,
+                    &asset_server,
+                    "Reserve".to_string(),
+                    ButtonFunction::ReserveUpgrade(slot),
+                );
+            });
+            commands.entity(upgrades_container).add_child(slot_ui);
         }
         let description_box = commands
             .spawn((
@@ -371,6 +799,136 @@ fn display_shop(
             })
             .id();
         commands.entity(shop_node).add_child(description_box);
+        let font = TextFont {
+            font_size: UI_FONT_SIZE,
+            font: asset_server.load(UI_FONT),
+            ..default()
+        };
+        let reserve_container = commands
+            .spawn((
+                ShopUpgradeUI,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                },
+                Name::new("ReserveContainer"),
+            ))
+            .with_children(|parent| match &shop_upgrades.reserved {
+                Some(upgrade) => {
+                    parent.spawn((
+                        Text(format!("Reserved: {}", upgrade.display_name)),
+                        font.clone(),
+                    ));
+                    spawn_text_button(
+                        parent,
+                        &asset_server,
+                        "Equip".to_string(),
+                        ButtonFunction::EquipReserved,
+                    );
+                    spawn_text_button(
+                        parent,
+                        &asset_server,
+                        format!("Sell (+${})", sell_price(upgrade.cost, &balance)),
+                        ButtonFunction::SellReserved,
+                    );
+                }
+                None => {
+                    parent.spawn((Text("Reserved: empty".to_string()), font.clone()));
+                }
+            })
+            .id();
+        commands.entity(shop_node).add_child(reserve_container);
+        let consumables_container = commands
+            .spawn((
+                ShopUpgradeUI,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    row_gap: Val::Px(4.0),
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                },
+                Name::new("ConsumablesContainer"),
+            ))
+            .with_children(|parent| {
+                let cost = config.ascension().shop_price(balance.consumable_cost);
+                if let Some(relic) = shop_upgrades.relic {
+                    parent
+                        .spawn(Node {
+                            width: Val::Percent(30.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_text_button(
+                                parent,
+                                &asset_server,
+                                format!(
+                                    "Relic: {} ${}",
+                                    relic,
+                                    config.ascension().shop_price(balance.relic_cost)
+                                ),
+                                ButtonFunction::BuyRelic,
+                            );
+                        });
+                }
+                for consumable in Consumable::ALL {
+                    parent
+                        .spawn(Node {
+                            width: Val::Percent(30.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_text_button(
+                                parent,
+                                &asset_server,
+                                format!("{} ${}", consumable, cost),
+                                ButtonFunction::BuyConsumable(consumable),
+                            );
+                        });
+                }
+            })
+            .id();
+        commands.entity(shop_node).add_child(consumables_container);
+        let owned_container = commands
+            .spawn((
+                ShopUpgradeUI,
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    row_gap: Val::Px(4.0),
+                    column_gap: Val::Px(4.0),
+                    ..default()
+                },
+                Name::new("OwnedContainer"),
+            ))
+            .with_children(|parent| {
+                let Ok(upgrades) = player_upgrades.get_single() else {
+                    return;
+                };
+                for (index, upgrade) in upgrades.0.iter().enumerate() {
+                    parent
+                        .spawn(Node {
+                            width: Val::Percent(30.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_text_button(
+                                parent,
+                                &asset_server,
+                                format!(
+                                    "Sell {} (+${})",
+                                    upgrade.display_name,
+                                    sell_price(upgrade.cost, &balance)
+                                ),
+                                ButtonFunction::SellUpgrade(index),
+                            );
+                        });
+                }
+            })
+            .id();
+        commands.entity(shop_node).add_child(owned_container);
         let refresh_button = commands
             .spawn((
                 Node {
@@ -390,7 +948,14 @@ fn display_shop(
             ))
             .with_children(|parent| {
                 parent.spawn((
-                    Text("Refresh (R)".to_string()),
+                    Text(format!(
+                        "Refresh ${} (R)",
+                        config.ascension().shop_price(refresh_cost(
+                            balance.refresh_shop_cost,
+                            visit.refreshes,
+                            &balance
+                        ))
+                    )),
                     TextFont {
                         font_size: UI_FONT_SIZE,
                         font: asset_server.load(UI_FONT),
@@ -425,31 +990,91 @@ impl Plugin for ShopPlugin {
     fn build(&self, app: &mut App) {
         app.insert_state(ShopState::Closed)
             .enable_state_scoped_entities::<ShopState>()
-            .insert_resource(ShopUpgrades(Vec::new()));
+            .init_resource::<ShopUpgrades>()
+            .init_resource::<ShopVisit>();
         app.add_systems(
             Update,
             toggle_shop
                 .run_if(in_state(GameState::Game))
-                .run_if(in_state(TurnState::PlayerInput)),
+                .run_if(in_state(DraftState::Closed))
+                .run_if(in_state(TurnState::PlayerInput))
+                .run_if(is_local_turn),
         );
         app.add_systems(
             Update,
             (
                 buy_upgrade.run_if(on_event::<ButtonPressedEvent>),
-                update_shop_system,
+                press_shop_action_buttons.run_if(on_event::<ButtonPressedEvent>),
                 display_shop,
                 update_shop_description.run_if(on_event::<ButtonHoverEvent>),
             )
                 .run_if(in_state(GameState::Game))
                 .run_if(in_state(ShopState::Open)),
         );
+        // the shop is also drawn and bought from while closed, on the other online player's turns
+        app.add_systems(
+            Update,
+            (buy_shop_upgrade, apply_shop_action, update_shop_system)
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
         app.add_systems(
             Update,
             apply_upgrades
                 .run_if(in_state(GameState::Game))
                 .run_if(on_event::<ApplyUpgrades>),
         );
-        app.add_event::<RefreshShop>().add_event::<RefreshShopUI>();
+        app.add_systems(OnEnter(GameState::Game), reset_shop);
+        app.add_systems(OnEnter(TurnState::PlayerInput), reset_shop_visit);
+        app.add_event::<RefreshShop>()
+            .add_event::<RefreshShopUI>()
+            .add_event::<BuyShopUpgrade>()
+            .add_event::<ShopAction>();
         app.add_event::<ApplyUpgrades>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pieces::{movement_type::MovementType, player::upgrades::data::UpgradeCatalog};
+
+    fn offer(movement_type: MovementType, locked: bool) -> ShopOffer {
+        ShopOffer {
+            upgrade: UpgradeCatalog::default().get_movement_upgrade(&movement_type),
+            locked,
+            on_sale: false,
+        }
+    }
+
+    #[test]
+    fn test_locked_offers_survive_refresh() {
+        let previous = vec![
+            offer(MovementType::Rook, false),
+            offer(MovementType::Bishop, true),
+        ];
+        let mut offers = vec![
+            offer(MovementType::Knight, false),
+            offer(MovementType::Queen, false),
+        ];
+        keep_locked_offers(&previous, &mut offers);
+        assert_eq!(
+            offers[0].upgrade.effect,
+            Effect::MovementType(vec![MovementType::Knight])
+        );
+        assert_eq!(
+            offers[1].upgrade.effect,
+            Effect::MovementType(vec![MovementType::Bishop])
+        );
+        assert!(offers[1].locked);
+        let balance = Balance::default();
+        assert_eq!(
+            sell_price(150, &balance),
+            150 * balance.shop_sell_percent / 100
+        );
+        assert_eq!(
+            refresh_cost(1, 2, &balance),
+            1 + balance.shop_refresh_cost_increase * 2
+        );
+    }
+}
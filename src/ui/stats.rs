@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::run_stats::RunHistory,
+    globals::{
+        DEFEAT_HEADER_FONT_SIZE, PRIMARY_COLOR, STATS_CHART_WIDTH, STATS_RECENT_RUNS, UI_FONT,
+        UI_FONT_SIZE, UI_HEADER_FONT_SIZE,
+    },
+    states::game_state::GameState,
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction},
+    RootUINode,
+};
+
+fn show_stats_screen(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    history: Res<RunHistory>,
+) {
+    debug!("Showing stats screen");
+    let root_node = root_node.single();
+    let recent_runs: Vec<(String, usize)> = history
+        .runs
+        .iter()
+        .enumerate()
+        .skip(history.runs.len().saturating_sub(STATS_RECENT_RUNS))
+        .map(|(index, run)| (format!("Run {}", index + 1), run.score))
+        .collect();
+
+    let stats_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("StatsUI"),
+            StateScoped(GameState::Stats),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text("Stats".to_string()),
+                TextFont {
+                    font_size: DEFEAT_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text(format!(
+                    "Runs: {}   Best score: {}   Average turns: {:.1}",
+                    history.runs.len(),
+                    history.best_score(),
+                    history.average_turns()
+                )),
+                TextFont {
+                    font_size: UI_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            spawn_bar_chart(
+                parent,
+                &asset_server,
+                "Best score per build",
+                &history.best_score_per_build(),
+            );
+            spawn_bar_chart(parent, &asset_server, "Recent scores", &recent_runs);
+            spawn_menu_button(parent, &asset_server, "Back", ButtonFunction::Back);
+        })
+        .id();
+
+    commands.entity(root_node).add_child(stats_node);
+}
+
+/// Horizontal bars, scaled to the biggest value
+fn spawn_bar_chart(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    title: &str,
+    values: &[(String, usize)],
+) {
+    let max_value = values
+        .iter()
+        .map(|(_, value)| *value)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text(title.to_string()),
+                TextFont {
+                    font_size: UI_HEADER_FONT_SIZE,
+                    font: asset_server.load(UI_FONT),
+                    ..default()
+                },
+            ));
+            if values.is_empty() {
+                parent.spawn((
+                    Text("No runs yet".to_string()),
+                    TextFont {
+                        font_size: UI_FONT_SIZE,
+                        font: asset_server.load(UI_FONT),
+                        ..default()
+                    },
+                ));
+            }
+            for (label, value) in values.iter() {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text(label.clone()),
+                            TextFont {
+                                font_size: UI_FONT_SIZE,
+                                font: asset_server.load(UI_FONT),
+                                ..default()
+                            },
+                            Node {
+                                width: Val::Px(160.0),
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(
+                                    STATS_CHART_WIDTH * *value as f32 / max_value as f32,
+                                ),
+                                height: Val::Px(UI_FONT_SIZE / 2.0),
+                                ..default()
+                            },
+                            BackgroundColor(PRIMARY_COLOR),
+                        ));
+                        parent.spawn((
+                            Text(value.to_string()),
+                            TextFont {
+                                font_size: UI_FONT_SIZE,
+                                font: asset_server.load(UI_FONT),
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Stats), show_stats_screen);
+    }
+}
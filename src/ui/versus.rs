@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_logic::versus::{is_versus, Versus},
+    globals::{DEFEAT_HEADER_FONT_SIZE, HERO_COLORS, UI_FONT_SIZE},
+    states::{game_state::GameState, turn_state::TurnInfo},
+};
+
+use super::{
+    button::{spawn_menu_button, ButtonFunction},
+    profile::spawn_text,
+    RootUINode,
+};
+
+/// Result of a versus match, replaces the end of run screen
+fn show_versus_result(
+    mut commands: Commands,
+    root_node: Query<Entity, With<RootUINode>>,
+    asset_server: Res<AssetServer>,
+    versus: Res<Versus>,
+    turn_info: Res<TurnInfo>,
+) {
+    debug!("Showing versus result");
+    let root_node = root_node.single();
+    let (header, color) = match versus.winner {
+        Some(side) => (format!("Player {} wins", side + 1), HERO_COLORS[side]),
+        None => ("Draw".to_string(), Color::WHITE),
+    };
+
+    let result_node = commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.0),
+                height: Val::Vh(100.0),
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.0, 0.0, 0.0)),
+            Name::new("VersusResultUI"),
+            StateScoped(GameState::Victory),
+        ))
+        .with_children(|parent| {
+            spawn_text(
+                parent,
+                &asset_server,
+                header,
+                DEFEAT_HEADER_FONT_SIZE,
+                color,
+            );
+            spawn_text(
+                parent,
+                &asset_server,
+                format!("Turns played: {}", turn_info.number.saturating_sub(1)),
+                UI_FONT_SIZE,
+                Color::WHITE,
+            );
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(16.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    spawn_menu_button(
+                        parent,
+                        &asset_server,
+                        "Rematch",
+                        ButtonFunction::RestartGame,
+                    );
+                    spawn_menu_button(parent, &asset_server, "Menu", ButtonFunction::ShowMainMenu);
+                });
+        })
+        .id();
+
+    commands.entity(root_node).add_child(result_node);
+}
+
+pub struct VersusUIPlugin;
+
+impl Plugin for VersusUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Victory),
+            show_versus_result.run_if(is_versus),
+        );
+    }
+}
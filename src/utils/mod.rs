@@ -1,2 +1,4 @@
+pub mod date;
 pub mod math;
 pub mod rng;
+pub mod storage;
@@ -1,12 +1,43 @@
-use rand::{thread_rng, Rng};
+use bevy::prelude::*;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 
 pub trait Weighted {
     fn weight(&self) -> f32;
 }
 
-pub fn sample_weighted<T: Weighted + Clone>(n: usize, items: &[T]) -> Vec<T> {
-    let mut rng = thread_rng();
+/// Random number generator of the enemy spawns, the shop, drops, relics and attack rolls,
+/// seeded at the start of each run so the same seed played with the same actions
+/// gives the same run, any other action changes what is drawn next
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng(StdRng::from_entropy())
+    }
+}
+
+impl GameRng {
+    pub fn reseed(&mut self, seed: u64) {
+        self.0 = StdRng::seed_from_u64(seed);
+    }
+}
+
+pub fn random_seed() -> u64 {
+    thread_rng().gen()
+}
+
+/// Draws `n` items, each as likely as its weight,
+/// or nothing when the items have no weight to draw from
+pub fn sample_weighted<T: Weighted + Clone>(
+    rng: &mut impl Rng,
+    n: usize,
+    items: &[T],
+) -> Option<Vec<T>> {
     let total_weight = items.iter().map(|i| i.weight()).sum::<f32>();
+    if total_weight <= 0.0 {
+        return None;
+    }
     let mut upgrades: Vec<T> = Vec::with_capacity(n);
     for _ in 0..n {
         let draw = rng.gen_range(0.0..total_weight);
@@ -19,5 +50,27 @@ pub fn sample_weighted<T: Weighted + Clone>(n: usize, items: &[T]) -> Vec<T> {
             }
         }
     }
-    upgrades
+    Some(upgrades)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Weighted for f32 {
+        fn weight(&self) -> f32 {
+            *self
+        }
+    }
+
+    #[test]
+    fn test_sample_weighted() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(sample_weighted::<f32>(&mut rng, 3, &[]), None);
+        assert_eq!(sample_weighted(&mut rng, 3, &[0.0, 0.0]), None);
+        assert_eq!(
+            sample_weighted(&mut rng, 3, &[0.0, 1.0]),
+            Some(vec![1.0; 3])
+        );
+    }
 }
@@ -0,0 +1,74 @@
+//! Small helpers to persist data in local files
+//!
+//! Files are stored next to the executable, the web build has no file system
+//! so they are kept in the browser's local storage under their path.
+
+use std::io;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_file(path: &str) -> io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_file(path: &str, content: &str) -> io::Result<()> {
+    std::fs::write(path, content)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove_file(path: &str) -> io::Result<()> {
+    std::fs::remove_file(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn append_line(path: &str, line: &str) -> io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read_file(path: &str) -> io::Result<String> {
+    local_storage()?
+        .get_item(path)
+        .map_err(|_| storage_error("could not read from the local storage"))?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.to_string()))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_file(path: &str, content: &str) -> io::Result<()> {
+    local_storage()?
+        .set_item(path, content)
+        .map_err(|_| storage_error("could not write to the local storage"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn remove_file(path: &str) -> io::Result<()> {
+    local_storage()?
+        .remove_item(path)
+        .map_err(|_| storage_error("could not remove from the local storage"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn append_line(path: &str, line: &str) -> io::Result<()> {
+    let mut content = read_file(path).unwrap_or_default();
+    content.push_str(line);
+    content.push('\n');
+    write_file(path, &content)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> io::Result<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| storage_error("the local storage is not available"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, message.to_string())
+}